@@ -0,0 +1,87 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use tracing::warn;
+
+/// Ceiling on the requests one search, fetch, or research call sends, from
+/// `SCOUT_MAX_REQUESTS_PER_CALL` (unset: no ceiling). Every HTTP attempt takes
+/// one: Gemini retries and fallback-model calls, page downloads and their
+/// retries, pagination hops, robots.txt lookups, and playwright renders. Once
+/// it is spent, the remaining requests fail with a budget error.
+#[derive(Debug, Default)]
+pub(crate) struct RequestBudget {
+    limit: Option<usize>,
+    used: AtomicUsize,
+    refused: AtomicBool,
+}
+
+impl RequestBudget {
+    pub(crate) fn new(limit: Option<usize>) -> Self {
+        Self {
+            limit,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn from_env() -> Self {
+        Self::from_env_with(|var| std::env::var(var).ok())
+    }
+
+    fn from_env_with(env_reader: impl Fn(&str) -> Option<String>) -> Self {
+        let Some(raw) = env_reader("SCOUT_MAX_REQUESTS_PER_CALL") else {
+            return Self::default();
+        };
+        match raw.trim().parse::<usize>() {
+            Ok(n) if n > 0 => Self::new(Some(n)),
+            _ => {
+                warn!(value = %raw, "invalid SCOUT_MAX_REQUESTS_PER_CALL, not capping requests");
+                Self::default()
+            }
+        }
+    }
+
+    /// Take one request from the budget; `false` once it is spent.
+    pub(crate) fn try_acquire(&self) -> bool {
+        let Some(limit) = self.limit else {
+            return true;
+        };
+        let acquired = self
+            .used
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                (n < limit).then_some(n + 1)
+            })
+            .is_ok();
+        if !acquired {
+            self.refused.store(true, Ordering::SeqCst);
+        }
+        acquired
+    }
+
+    /// The cap, if it turned any request away.
+    pub(crate) fn exhausted_at(&self) -> Option<usize> {
+        self.limit.filter(|_| self.refused.load(Ordering::SeqCst))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_cap_from_env() {
+        let budget = |value: &'static str| {
+            RequestBudget::from_env_with(move |var| {
+                (var == "SCOUT_MAX_REQUESTS_PER_CALL").then(|| value.to_string())
+            })
+        };
+        assert_eq!(RequestBudget::from_env_with(|_| None).limit, None);
+        assert_eq!(budget(" 25 ").limit, Some(25));
+        assert_eq!(budget("0").limit, None);
+        assert_eq!(budget("lots").limit, None);
+
+        let capped = budget("1");
+        assert!(capped.try_acquire());
+        assert_eq!(capped.exhausted_at(), None);
+        assert!(!capped.try_acquire());
+        assert_eq!(capped.exhausted_at(), Some(1));
+    }
+}
@@ -0,0 +1,38 @@
+use tracing::warn;
+
+/// Boolean environment flag: `1`/`true`/`yes` or `0`/`false`/`no` (any
+/// case, empty counts as false). `None` when the variable is unset or holds
+/// anything else, which is logged rather than guessed at.
+pub(crate) fn parse_bool_env(
+    env_reader: impl Fn(&str) -> Option<String>,
+    var: &str,
+) -> Option<bool> {
+    let value = env_reader(var)?;
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" => Some(true),
+        "" | "0" | "false" | "no" => Some(false),
+        _ => {
+            warn!(var, %value, "ignoring non-boolean value");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_boolean_flags() {
+        let flag = |value: &'static str| {
+            parse_bool_env(|var| (var == "FLAG").then(|| value.to_string()), "FLAG")
+        };
+        assert_eq!(parse_bool_env(|_| None, "FLAG"), None);
+        assert_eq!(flag(" TRUE "), Some(true));
+        assert_eq!(flag("yes"), Some(true));
+        assert_eq!(flag("1"), Some(true));
+        assert_eq!(flag(""), Some(false));
+        assert_eq!(flag("No"), Some(false));
+        assert_eq!(flag("maybe"), None);
+    }
+}
@@ -0,0 +1,99 @@
+//! Code-block extraction for `--code-only` fetches.
+
+use dom_query::{Document, Selection};
+
+/// Class prefixes that carry a language name (Prism/highlight.js, Google
+/// prettify, GitHub).
+const LANG_CLASS_PREFIXES: &[&str] = &["language-", "lang-", "highlight-source-"];
+
+#[derive(Debug, PartialEq)]
+pub(super) struct CodeBlock {
+    pub lang: Option<String>,
+    pub code: String,
+}
+
+/// Collect every `<pre>` block in `html`, in document order, with its
+/// language hint when the markup declares one.
+pub(super) fn extract_code_blocks(html: &str) -> Vec<CodeBlock> {
+    let doc = Document::from(html);
+    doc.select("pre")
+        .iter()
+        .filter_map(|pre| {
+            let code = pre.text().trim_matches('\n').to_string();
+            if code.trim().is_empty() {
+                return None;
+            }
+            let lang = language_hint(&pre).or_else(|| language_hint(&pre.select("code")));
+            Some(CodeBlock { lang, code })
+        })
+        .collect()
+}
+
+fn language_hint(sel: &Selection) -> Option<String> {
+    sel.iter().find_map(|node| {
+        if let Some(lang) = node
+            .attr("data-lang")
+            .or_else(|| node.attr("data-language"))
+            .filter(|l| !l.trim().is_empty())
+        {
+            return Some(lang.trim().to_lowercase());
+        }
+        let class = node.attr("class")?;
+        class.split_whitespace().find_map(|c| {
+            LANG_CLASS_PREFIXES
+                .iter()
+                .find_map(|p| c.strip_prefix(p))
+                .filter(|l| !l.is_empty())
+                .map(str::to_lowercase)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collects_blocks_with_language_hints() {
+        let html = r#"<article>
+            <p>Install the crate first.</p>
+            <pre><code class="hljs language-toml">[dependencies]
+serde = "1"</code></pre>
+            <p>Then derive the traits:</p>
+            <pre class="lang-rust">#[derive(Serialize)]
+struct Point { x: i32 }</pre>
+            <pre data-lang="Shell"><code>cargo build</code></pre>
+            <pre><code>plain text</code></pre>
+        </article>"#;
+
+        let blocks = extract_code_blocks(html);
+
+        assert_eq!(
+            blocks,
+            vec![
+                CodeBlock {
+                    lang: Some("toml".into()),
+                    code: "[dependencies]\nserde = \"1\"".into(),
+                },
+                CodeBlock {
+                    lang: Some("rust".into()),
+                    code: "#[derive(Serialize)]\nstruct Point { x: i32 }".into(),
+                },
+                CodeBlock {
+                    lang: Some("shell".into()),
+                    code: "cargo build".into(),
+                },
+                CodeBlock {
+                    lang: None,
+                    code: "plain text".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_empty_blocks_and_inline_code() {
+        let html = "<p>Use <code>let</code> here.</p><pre>\n  \n</pre>";
+        assert!(extract_code_blocks(html).is_empty());
+    }
+}
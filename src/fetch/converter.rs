@@ -1,6 +1,9 @@
 use std::fmt::Write;
 
-use super::extractor::ExtractedArticle;
+use super::code_blocks::extract_code_blocks;
+use super::extractor::{ExtractedArticle, PageLink, PageMetadata};
+use super::ssrf::SsrfOutcome;
+use crate::markdown::{escape_md_link, fenced, truncate_with_note};
 
 /// Fetched page content converted to Markdown.
 #[derive(Debug)]
@@ -8,11 +11,32 @@ pub struct FetchResult {
     pub url: String,
     pub markdown: String,
     pub used_raw_fallback: bool,
+    /// The extract looks like a "subscribe to continue" stub, not the article.
+    pub paywalled: bool,
 }
 
+/// Response headers and download stats reported in metadata output. Absent
+/// values stay `None` and are omitted from the frontmatter.
+#[derive(Debug, Default)]
+pub(super) struct ResponseHeaders {
+    pub status: Option<u16>,
+    pub content_type: Option<String>,
+    pub last_modified: Option<String>,
+    /// Time from sending the request to the end of the body.
+    pub fetch_time_ms: Option<u64>,
+    /// Body size as received, before decoding.
+    pub response_bytes: Option<usize>,
+}
+
+/// Combined budget for the two sections of [`to_compare_result`], leaving
+/// room for the frontmatter under the fetch tool's 100K output cap.
+const COMPARE_MAX_BYTES: usize = 90_000;
+
 pub(crate) const RAW_FALLBACK_NOTE: &str =
     "> Note: Readability extraction failed. Showing raw page conversion.\n\n";
 
+pub(crate) const PAYWALL_NOTE: &str = "> Note: Likely paywalled. Only a subscription stub was readable; the full article is not shown.\n\n";
+
 pub(super) fn to_fetch_result(article: ExtractedArticle, url: String) -> FetchResult {
     let markdown = html2md::rewrite_html(&article.content_html, false);
     let output = format_with_frontmatter(&article, &markdown);
@@ -21,6 +45,206 @@ pub(super) fn to_fetch_result(article: ExtractedArticle, url: String) -> FetchRe
         url,
         markdown: output,
         used_raw_fallback: article.used_raw_fallback,
+        paywalled: false,
+    }
+}
+
+/// Frontmatter-only result for metadata mode. `url` is the canonical URL when
+/// the page declares one, otherwise the final (post-redirect) URL. Selected
+/// response headers follow the page fields, then how the final host passed
+/// the SSRF check, then the download time and size.
+pub(super) fn to_metadata_result(
+    meta: PageMetadata,
+    final_url: String,
+    headers: &ResponseHeaders,
+    ssrf: &SsrfOutcome,
+) -> FetchResult {
+    let mut fm = String::from("---\n");
+    let fields = [
+        ("title", &meta.title),
+        ("author", &meta.byline),
+        ("date", &meta.published_time),
+        ("description", &meta.description),
+        ("site_name", &meta.site_name),
+        ("image", &meta.image),
+    ];
+    for (key, value) in fields {
+        if let Some(value) = value {
+            let _ = writeln!(fm, "{key}: \"{}\"", escape_yaml(value));
+        }
+    }
+    let url = meta.canonical_url.as_deref().unwrap_or(&final_url);
+    let _ = writeln!(fm, "url: \"{}\"", escape_yaml(url));
+    if let Some(status) = headers.status {
+        let _ = writeln!(fm, "status: {status}");
+    }
+    let header_fields = [
+        ("content_type", &headers.content_type),
+        ("last_modified", &headers.last_modified),
+    ];
+    for (key, value) in header_fields {
+        if let Some(value) = value {
+            let _ = writeln!(fm, "{key}: \"{}\"", escape_yaml(value));
+        }
+    }
+    match ssrf {
+        SsrfOutcome::IpLiteral => fm.push_str("ssrf_check: \"ip-literal\"\n"),
+        SsrfOutcome::Resolved(ips) => {
+            let ips: Vec<_> = ips.iter().map(|ip| format!("\"{ip}\"")).collect();
+            let _ = writeln!(
+                fm,
+                "ssrf_check: \"dns\"\nresolved_ips: [{}]",
+                ips.join(", ")
+            );
+        }
+    }
+    if let Some(ms) = headers.fetch_time_ms {
+        let _ = writeln!(fm, "fetch_time_ms: {ms}");
+    }
+    if let Some(bytes) = headers.response_bytes {
+        let _ = writeln!(fm, "response_bytes: {bytes}");
+    }
+    fm.push_str("---\n");
+
+    FetchResult {
+        url: final_url,
+        markdown: fm,
+        used_raw_fallback: false,
+        paywalled: false,
+    }
+}
+
+/// Frontmatter followed by only the article's code blocks, each fenced with
+/// its language hint.
+pub(super) fn to_code_result(article: ExtractedArticle, url: String) -> FetchResult {
+    let blocks = extract_code_blocks(&article.content_html);
+    let body = if blocks.is_empty() {
+        "_No code blocks found._\n".to_string()
+    } else {
+        blocks
+            .iter()
+            .map(|b| fenced(&b.code, b.lang.as_deref().unwrap_or("")) + "\n")
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    FetchResult {
+        url,
+        markdown: format_with_frontmatter(&article, &body),
+        used_raw_fallback: article.used_raw_fallback,
+        paywalled: false,
+    }
+}
+
+/// Frontmatter, then the Readability extraction and the whole-page
+/// conversion under their own headings. The extracted section gets up to
+/// half of [`COMPARE_MAX_BYTES`] and the raw section the rest.
+pub(super) fn to_compare_result(
+    article: ExtractedArticle,
+    raw: &ExtractedArticle,
+    url: String,
+) -> FetchResult {
+    let extracted = html2md::rewrite_html(&article.content_html, false);
+    let extracted = truncate_with_note(extracted.trim_end(), COMPARE_MAX_BYTES / 2);
+    let whole_page = html2md::rewrite_html(&raw.content_html, false);
+    let whole_page = truncate_with_note(
+        whole_page.trim_end(),
+        COMPARE_MAX_BYTES.saturating_sub(extracted.len()),
+    );
+
+    let mut body = String::new();
+    if article.used_raw_fallback {
+        body.push_str(RAW_FALLBACK_NOTE);
+    }
+    let _ = write!(
+        body,
+        "## Extracted (Readability)\n\n{extracted}\n\n## Raw (whole page)\n\n{whole_page}\n"
+    );
+
+    FetchResult {
+        url,
+        markdown: format_with_frontmatter(&article, &body),
+        used_raw_fallback: article.used_raw_fallback,
+        paywalled: false,
+    }
+}
+
+/// Frontmatter followed by the page's links as a Markdown list.
+pub(super) fn to_links_result(
+    title: Option<String>,
+    links: &[PageLink],
+    url: String,
+) -> FetchResult {
+    let mut markdown = String::from("---\n");
+    if let Some(title) = &title {
+        let _ = writeln!(markdown, "title: \"{}\"", escape_yaml(title));
+    }
+    markdown.push_str("---\n\n");
+    format_link_list(links, &mut markdown);
+
+    FetchResult {
+        url,
+        markdown,
+        used_raw_fallback: false,
+        paywalled: false,
+    }
+}
+
+/// Append a "Links" section listing `links` after the page body.
+pub(super) fn append_links_section(result: &mut FetchResult, links: &[PageLink]) {
+    let markdown = &mut result.markdown;
+    if !markdown.ends_with('\n') {
+        markdown.push('\n');
+    }
+    markdown.push_str("\n## Links\n\n");
+    format_link_list(links, markdown);
+}
+
+fn format_link_list(links: &[PageLink], out: &mut String) {
+    if links.is_empty() {
+        out.push_str("_No links found._\n");
+    }
+    for link in links {
+        let _ = writeln!(
+            out,
+            "- [{}]({})",
+            escape_md_link(&link.text),
+            escape_md_link(&link.url)
+        );
+    }
+}
+
+/// Stand-in for the page body when the server answered 304 to a conditional
+/// fetch.
+pub(super) fn to_not_modified_result(since: std::time::SystemTime, url: String) -> FetchResult {
+    FetchResult {
+        url,
+        markdown: format!(
+            "---\nstatus: 304\n---\n\nNot modified since {}.\n",
+            httpdate::fmt_http_date(since)
+        ),
+        used_raw_fallback: false,
+        paywalled: false,
+    }
+}
+
+const PDF_DEGRADED_NOTE: &str = "> Note: Some characters could not be extracted from this PDF.\n\n";
+
+/// Plain text extracted from a PDF, under an empty frontmatter block so the
+/// output shape matches HTML pages.
+pub(super) fn to_pdf_result(text: &str, url: String) -> FetchResult {
+    let mut markdown = String::from("---\n---\n\n");
+    if super::pdf::is_degraded(text) {
+        markdown.push_str(PDF_DEGRADED_NOTE);
+    }
+    markdown.push_str(text);
+    markdown.push('\n');
+
+    FetchResult {
+        url,
+        markdown,
+        used_raw_fallback: false,
+        paywalled: false,
     }
 }
 
@@ -37,6 +261,9 @@ fn format_with_frontmatter(article: &ExtractedArticle, markdown: &str) -> String
     if let Some(date) = &article.published_time {
         let _ = writeln!(fm, "date: \"{}\"", escape_yaml(date));
     }
+    if let Some(image) = &article.image {
+        let _ = writeln!(fm, "image: \"{}\"", escape_yaml(image));
+    }
 
     fm.push_str("---\n\n");
     fm.push_str(markdown);
@@ -62,6 +289,7 @@ mod tests {
             title: Some("My Title".into()),
             byline: Some("Jane Doe".into()),
             published_time: Some("2026-01-15".into()),
+            image: None,
             content_html: "<p>Body text</p>".into(),
             used_raw_fallback: false,
         };
@@ -82,6 +310,7 @@ mod tests {
             title: Some("Only Title".into()),
             byline: None,
             published_time: None,
+            image: None,
             content_html: "<p>Text</p>".into(),
             used_raw_fallback: false,
         };
@@ -93,6 +322,152 @@ mod tests {
         assert!(!result.markdown.contains("date:"));
     }
 
+    #[test]
+    fn compare_result_splits_budget_between_sections() {
+        let article = |html: String| ExtractedArticle {
+            title: None,
+            byline: None,
+            published_time: None,
+            image: None,
+            content_html: html,
+            used_raw_fallback: false,
+        };
+        let long = format!("<p>{}</p>", "x".repeat(COMPARE_MAX_BYTES));
+        let result = to_compare_result(
+            article(long.clone()),
+            &article(long),
+            "https://example.com".into(),
+        );
+
+        let (extracted, raw) = result
+            .markdown
+            .split_once("## Raw (whole page)")
+            .expect("raw section");
+        assert!(extracted.contains("## Extracted (Readability)"));
+        assert!(extracted.contains(&format!("(truncated: showing {} / ", COMPARE_MAX_BYTES / 2)));
+        assert!(raw.contains("(truncated: showing"));
+        assert!(result.markdown.len() < COMPARE_MAX_BYTES + 1_000);
+    }
+
+    #[test]
+    fn metadata_result_is_frontmatter_only() {
+        let meta = PageMetadata {
+            title: Some("My Title".into()),
+            byline: Some("Jane Doe".into()),
+            published_time: Some("2026-01-15".into()),
+            description: Some("Summary".into()),
+            site_name: None,
+            canonical_url: Some("https://example.com/canonical".into()),
+            image: Some("https://example.com/cover.png".into()),
+        };
+
+        let result = to_metadata_result(
+            meta,
+            "https://example.com/page?ref=1".into(),
+            &ResponseHeaders::default(),
+            &SsrfOutcome::Resolved(vec!["93.184.216.34".parse().unwrap()]),
+        );
+
+        assert_eq!(
+            result.markdown,
+            "---\n\
+             title: \"My Title\"\n\
+             author: \"Jane Doe\"\n\
+             date: \"2026-01-15\"\n\
+             description: \"Summary\"\n\
+             image: \"https://example.com/cover.png\"\n\
+             url: \"https://example.com/canonical\"\n\
+             ssrf_check: \"dns\"\n\
+             resolved_ips: [\"93.184.216.34\"]\n\
+             ---\n"
+        );
+        assert_eq!(result.url, "https://example.com/page?ref=1");
+    }
+
+    #[test]
+    fn metadata_result_falls_back_to_final_url() {
+        let meta = PageMetadata {
+            title: None,
+            byline: None,
+            published_time: None,
+            description: None,
+            site_name: None,
+            canonical_url: None,
+            image: None,
+        };
+
+        let result = to_metadata_result(
+            meta,
+            "https://example.com/final".into(),
+            &ResponseHeaders::default(),
+            &SsrfOutcome::IpLiteral,
+        );
+
+        assert_eq!(
+            result.markdown,
+            "---\nurl: \"https://example.com/final\"\nssrf_check: \"ip-literal\"\n---\n"
+        );
+    }
+
+    #[test]
+    fn metadata_result_includes_response_headers() {
+        let headers = ResponseHeaders {
+            status: Some(200),
+            content_type: Some("text/html; charset=\"utf-8\"".into()),
+            last_modified: None,
+            fetch_time_ms: Some(12),
+            response_bytes: Some(3456),
+        };
+
+        let result = to_metadata_result(
+            PageMetadata::default(),
+            "https://example.com/".into(),
+            &headers,
+            &SsrfOutcome::IpLiteral,
+        );
+
+        assert_eq!(
+            result.markdown,
+            "---\n\
+             url: \"https://example.com/\"\n\
+             status: 200\n\
+             content_type: \"text/html; charset=\\\"utf-8\\\"\"\n\
+             ssrf_check: \"ip-literal\"\n\
+             fetch_time_ms: 12\n\
+             response_bytes: 3456\n\
+             ---\n"
+        );
+    }
+
+    #[test]
+    fn code_result_notes_missing_code() {
+        let article = ExtractedArticle {
+            title: None,
+            byline: None,
+            published_time: None,
+            image: None,
+            content_html: "<p>Only prose.</p>".into(),
+            used_raw_fallback: false,
+        };
+
+        let result = to_code_result(article, "https://example.com".into());
+
+        assert_eq!(result.markdown, "---\n---\n\n_No code blocks found._\n");
+    }
+
+    #[test]
+    fn pdf_result_notes_degraded_text() {
+        let clean = to_pdf_result("Spec text", "https://example.com/a.pdf".into());
+        assert_eq!(clean.markdown, "---\n---\n\nSpec text\n");
+
+        let lossy = to_pdf_result("Sp\u{FFFD}c", "https://example.com/a.pdf".into());
+        assert!(
+            lossy.markdown.contains(PDF_DEGRADED_NOTE),
+            "got: {}",
+            lossy.markdown
+        );
+    }
+
     #[test]
     fn escapes_yaml_special_chars() {
         assert_eq!(escape_yaml(r#"He said "hello""#), r#"He said \"hello\""#);
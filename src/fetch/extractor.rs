@@ -5,14 +5,159 @@ pub(super) struct ExtractedArticle {
     pub title: Option<String>,
     pub byline: Option<String>,
     pub published_time: Option<String>,
+    /// Preview image URL, filled in only when requested (see [`extract_image`]).
+    pub image: Option<String>,
     pub content_html: String,
     /// True when readability extraction failed and raw HTML was used as fallback.
     /// False for both successful extraction and explicit raw mode.
     pub used_raw_fallback: bool,
 }
 
+/// Page-level metadata (OG/meta tags, JSON-LD, canonical link) without the body.
+#[derive(Default)]
+pub(super) struct PageMetadata {
+    pub title: Option<String>,
+    pub byline: Option<String>,
+    pub published_time: Option<String>,
+    pub description: Option<String>,
+    pub site_name: Option<String>,
+    pub canonical_url: Option<String>,
+    pub image: Option<String>,
+}
+
+pub(super) fn extract_metadata(html: &str, url: &str) -> PageMetadata {
+    let canonical_url = extract_canonical_url(html, url);
+    let image = extract_image(html, url);
+    let meta = match Readability::new(html, Some(url), Some(Config::default())) {
+        Ok(r) => r.get_article_metadata(r.parse_json_ld()),
+        Err(e) => {
+            warn!(%e, "readability init failed, falling back to <title> only");
+            return PageMetadata {
+                title: extract_title_from_html(html),
+                byline: None,
+                published_time: None,
+                description: None,
+                site_name: None,
+                canonical_url,
+                image,
+            };
+        }
+    };
+    let non_empty = |s: Option<String>| s.filter(|v| !v.trim().is_empty());
+
+    PageMetadata {
+        title: non_empty(Some(meta.title)),
+        byline: non_empty(meta.byline),
+        published_time: non_empty(meta.published_time),
+        description: non_empty(meta.excerpt),
+        site_name: non_empty(meta.site_name),
+        canonical_url,
+        image,
+    }
+}
+
+/// `<link rel="canonical">`, falling back to `og:url`, resolved against the page URL.
+fn extract_canonical_url(html: &str, url: &str) -> Option<String> {
+    let doc = dom_query::Document::from(html);
+    let href = doc
+        .select(r#"link[rel="canonical"][href]"#)
+        .attr("href")
+        .or_else(|| {
+            doc.select(r#"meta[property="og:url"][content]"#)
+                .attr("content")
+        })?;
+    let base = url::Url::parse(url).ok()?;
+    let resolved = base.join(href.trim()).ok()?;
+    matches!(resolved.scheme(), "http" | "https").then(|| resolved.to_string())
+}
+
+/// The page's preview image: `og:image`, falling back to `twitter:image`,
+/// resolved against the page URL. Only http(s) URLs are returned, so inline
+/// `data:` images are dropped.
+pub(super) fn extract_image(html: &str, url: &str) -> Option<String> {
+    let doc = dom_query::Document::from(html);
+    let base = url::Url::parse(url).ok()?;
+    [
+        r#"meta[property="og:image"][content]"#,
+        r#"meta[property="og:image:url"][content]"#,
+        r#"meta[name="twitter:image"][content]"#,
+        r#"meta[property="twitter:image"][content]"#,
+        r#"meta[name="twitter:image:src"][content]"#,
+    ]
+    .iter()
+    .filter_map(|sel| doc.select(sel).attr("content"))
+    .find_map(|src| {
+        let resolved = base.join(src.trim()).ok()?;
+        matches!(resolved.scheme(), "http" | "https").then(|| resolved.to_string())
+    })
+}
+
+/// A hyperlink found on a page, with its href resolved to an absolute URL.
+#[derive(Debug, PartialEq)]
+pub(super) struct PageLink {
+    pub text: String,
+    pub url: String,
+}
+
+/// Every `<a href>` in `html`, in document order, resolved against `url`.
+/// Only http(s) targets are kept (dropping `javascript:`, `mailto:`, `data:`
+/// and in-page `#fragment` links), and each URL appears once, with the anchor
+/// text of its first occurrence.
+pub(super) fn extract_links(html: &str, url: &str) -> Vec<PageLink> {
+    let Ok(base) = url::Url::parse(url) else {
+        return Vec::new();
+    };
+    let doc = dom_query::Document::from(html);
+    let mut seen = std::collections::HashSet::new();
+    doc.select("a[href]")
+        .iter()
+        .filter_map(|a| {
+            let href = a.attr("href")?;
+            let href = href.trim();
+            if href.is_empty() || href.starts_with('#') {
+                return None;
+            }
+            let resolved = base.join(href).ok()?;
+            if !matches!(resolved.scheme(), "http" | "https") {
+                return None;
+            }
+            let url = resolved.to_string();
+            if !seen.insert(url.clone()) {
+                return None;
+            }
+            let text = a.text().split_whitespace().collect::<Vec<_>>().join(" ");
+            let text = if text.is_empty() {
+                a.attr("title")
+                    .or_else(|| a.select("img[alt]").attr("alt"))
+                    .map(|t| t.trim().to_string())
+                    .filter(|t| !t.is_empty())
+                    .unwrap_or_else(|| url.clone())
+            } else {
+                text
+            };
+            Some(PageLink { text, url })
+        })
+        .collect()
+}
+
+/// `html` with every `<a>` replaced by its contents, so the Markdown keeps
+/// anchor text without `[text](url)` syntax.
+pub(super) fn flatten_links(html: &str) -> String {
+    let doc = dom_query::Document::from(html);
+    for a in doc.select("a").nodes() {
+        a.replace_with_html(a.inner_html());
+    }
+    doc.select("body").inner_html().to_string()
+}
+
 pub(super) fn extract_article(html: &str, url: Option<&str>) -> ExtractedArticle {
-    let mut readability = match Readability::new(html, url, Some(Config::default())) {
+    // Keep class attributes so code blocks retain their `language-*` hints;
+    // html2md ignores them, so the Markdown output is unaffected.
+    let config = Config {
+        keep_classes: true,
+        ..Config::default()
+    };
+    let mut readability = match Readability::new(html, url, Some(config)) {
         Ok(r) => r,
         Err(e) => {
             warn!(%e, "readability init failed, using raw fallback");
@@ -35,6 +180,7 @@ pub(super) fn extract_article(html: &str, url: Option<&str>) -> ExtractedArticle
                     title,
                     byline: article.byline.map(|b| b.to_string()),
                     published_time: article.published_time.map(|t| t.to_string()),
+                    image: None,
                     content_html: article.content.to_string(),
                     used_raw_fallback: false,
                 }
@@ -43,6 +189,7 @@ pub(super) fn extract_article(html: &str, url: Option<&str>) -> ExtractedArticle
                     title,
                     byline: None,
                     published_time: None,
+                    image: None,
                     content_html: html.to_string(),
                     used_raw_fallback: true,
                 }
@@ -55,6 +202,32 @@ pub(super) fn extract_article(html: &str, url: Option<&str>) -> ExtractedArticle
     }
 }
 
+/// The inner HTML of every element matching `selector`, bypassing
+/// Readability. Matches nested inside an earlier match are skipped so their
+/// content is not repeated. `None` when nothing matches.
+pub(super) fn extract_selected(html: &str, selector: &str) -> Option<ExtractedArticle> {
+    let doc = dom_query::Document::from(html);
+    let selection = doc.try_select(selector)?;
+    let nodes = selection.nodes();
+    let mut parts = Vec::new();
+    for node in nodes {
+        let nested = node
+            .ancestors_it(None)
+            .any(|a| nodes.iter().any(|n| n.id == a.id));
+        if !nested {
+            parts.push(node.inner_html().to_string());
+        }
+    }
+    Some(ExtractedArticle {
+        title: extract_title_from_html(html),
+        byline: None,
+        published_time: None,
+        image: None,
+        content_html: parts.join("\n"),
+        used_raw_fallback: false,
+    })
+}
+
 pub(super) fn extract_raw(html: &str) -> ExtractedArticle {
     make_raw(html, false)
 }
@@ -68,19 +241,21 @@ fn make_raw(html: &str, used_raw_fallback: bool) -> ExtractedArticle {
         title: extract_title_from_html(html),
         byline: None,
         published_time: None,
+        image: None,
         content_html: html.to_string(),
         used_raw_fallback,
     }
 }
 
-/// Simple `<title>` tag extraction via string search.
-/// Only used as fallback when dom_smoothie fails to parse the HTML.
-fn extract_title_from_html(html: &str) -> Option<String> {
+/// Simple `<title>` tag extraction via string search. Used when
+/// dom_smoothie fails to parse the HTML, and for link listings.
+pub(super) fn extract_title_from_html(html: &str) -> Option<String> {
     let lower = html.to_ascii_lowercase();
     let tag_start = lower.find("<title")?;
     let content_start = tag_start + lower[tag_start..].find('>')? + 1;
     let content_end = content_start + lower[content_start..].find("</title>")?;
-    let title = html[content_start..content_end].trim();
+    let title = decode_entities(html[content_start..content_end].trim());
+    let title = title.trim();
     if title.is_empty() {
         None
     } else {
@@ -88,6 +263,66 @@ fn extract_title_from_html(html: &str) -> Option<String> {
     }
 }
 
+/// Decode numeric (`&#39;`, `&#x27;`) and common named HTML entities.
+/// Unrecognized sequences are kept verbatim.
+fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| decode_entity(&rest[1..end]).map(|c| (c, end)));
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    if let Some(num) = name.strip_prefix('#') {
+        let code = match num.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => num.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+    let c = match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "ndash" => '–',
+        "mdash" => '—',
+        "hellip" => '…',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "laquo" => '«',
+        "raquo" => '»',
+        "middot" => '·',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        _ => return None,
+    };
+    Some(c)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -118,6 +353,39 @@ mod tests {
 </body>
 </html>"#;
 
+    #[test]
+    fn extracts_links_resolved_and_deduplicated() {
+        let html = r##"<html><body>
+            <a href="/docs/intro">Intro
+               guide</a>
+            <a href="https://other.example/x">Other</a>
+            <a href="javascript:void(0)">JS</a>
+            <a href="mailto:me@example.com">Mail</a>
+            <a href="data:text/plain,hi">Data</a>
+            <a href="#top">Top</a>
+            <a href="../about">About</a>
+            <a href="/docs/intro">Intro again</a>
+            <a href="/img"><img src="i.png" alt="Logo"></a>
+            <a>No href</a>
+        </body></html>"##;
+
+        let links = extract_links(html, "https://example.com/docs/page");
+
+        let pairs: Vec<_> = links
+            .iter()
+            .map(|l| (l.text.as_str(), l.url.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("Intro guide", "https://example.com/docs/intro"),
+                ("Other", "https://other.example/x"),
+                ("About", "https://example.com/about"),
+                ("Logo", "https://example.com/img"),
+            ]
+        );
+    }
+
     #[test]
     fn extracts_article_content() {
         let result = extract_article(BLOG_HTML, None);
@@ -196,4 +464,82 @@ mod tests {
         let html = "<html><head><TITLE>My Title</TITLE></head><body>İİİ</body></html>";
         assert_eq!(extract_title_from_html(html), Some("My Title".to_string()));
     }
+
+    #[test]
+    fn title_extraction_decodes_named_entities() {
+        let html = "<html><head><title>Foo &amp; Bar &lt;3&gt;</title></head></html>";
+        assert_eq!(
+            extract_title_from_html(html),
+            Some("Foo & Bar <3>".to_string())
+        );
+    }
+
+    #[test]
+    fn title_extraction_decodes_numeric_entities() {
+        let html = "<html><head><title>It&#39;s Bob&#x27;s &#X2014; ok</title></head></html>";
+        assert_eq!(
+            extract_title_from_html(html),
+            Some("It's Bob's — ok".to_string())
+        );
+    }
+
+    #[test]
+    fn decode_entities_keeps_unknown_sequences() {
+        assert_eq!(
+            decode_entities("AT&T &bogus; &#xZZ; a&b"),
+            "AT&T &bogus; &#xZZ; a&b"
+        );
+        assert_eq!(decode_entities("trailing &"), "trailing &");
+    }
+
+    #[test]
+    fn extract_metadata_reads_meta_tags_and_canonical() {
+        let html = r#"<html><head>
+            <title>Fallback Title</title>
+            <meta property="og:title" content="OG Title">
+            <meta name="author" content="Jane Doe">
+            <meta name="description" content="A short summary.">
+            <meta property="og:site_name" content="Example Blog">
+            <meta property="article:published_time" content="2026-01-15T00:00:00Z">
+            <link rel="canonical" href="/posts/og-title">
+        </head><body><p>Body</p></body></html>"#;
+        let meta = extract_metadata(html, "https://example.com/posts/og-title?utm=x");
+
+        assert_eq!(meta.title.as_deref(), Some("OG Title"));
+        assert_eq!(meta.byline.as_deref(), Some("Jane Doe"));
+        assert_eq!(meta.description.as_deref(), Some("A short summary."));
+        assert_eq!(meta.site_name.as_deref(), Some("Example Blog"));
+        assert_eq!(meta.published_time.as_deref(), Some("2026-01-15T00:00:00Z"));
+        assert_eq!(
+            meta.canonical_url.as_deref(),
+            Some("https://example.com/posts/og-title")
+        );
+    }
+
+    #[test]
+    fn extract_image_prefers_og_image_and_skips_data_uris() {
+        let html = r#"<html><head>
+            <meta name="twitter:image" content="https://cdn.example.com/card.png">
+            <meta property="og:image" content="../cover.jpg">
+        </head></html>"#;
+        assert_eq!(
+            extract_image(html, "https://example.com/blog/post").as_deref(),
+            Some("https://example.com/cover.jpg")
+        );
+
+        let html = r#"<html><head>
+            <meta property="og:image" content="data:image/png;base64,iVBORw0KGgo=">
+            <meta name="twitter:image" content="https://cdn.example.com/card.png">
+        </head></html>"#;
+        assert_eq!(
+            extract_image(html, "https://example.com/").as_deref(),
+            Some("https://cdn.example.com/card.png")
+        );
+    }
+
+    #[test]
+    fn canonical_url_ignores_non_http_schemes() {
+        let html = r#"<html><head><link rel="canonical" href="javascript:alert(1)"></head></html>"#;
+        assert_eq!(extract_canonical_url(html, "https://example.com/"), None);
+    }
 }
@@ -2,32 +2,93 @@
 //!
 //! URL validation → DNS pre-check → download → post-redirect recheck → content extraction.
 
+mod code_blocks;
 pub(crate) mod converter;
 mod extractor;
+mod openapi;
+mod pagination;
+mod pdf;
+pub(crate) mod robots;
 mod ssrf;
 
-pub(crate) use ssrf::{DnsResolver, TokioDnsResolver};
-use ssrf::{redact_url_credentials, ssrf_check};
-
-use converter::{FetchResult, to_fetch_result};
-use extractor::{extract_article, extract_raw};
+pub use ssrf::BlockReason;
+#[cfg(test)]
+pub(crate) use ssrf::PublicDns;
+pub(crate) use ssrf::{DnsResolver, PublicOnlyResolver, TokioDnsResolver};
+use ssrf::{SsrfOutcome, redact_url_credentials, ssrf_check, ssrf_recheck};
+
+use crate::budget::RequestBudget;
+use crate::retry::{is_transient_network, retry_with};
+use converter::{
+    FetchResult, ResponseHeaders, append_links_section, to_code_result, to_compare_result,
+    to_fetch_result, to_links_result, to_metadata_result, to_not_modified_result, to_pdf_result,
+};
+use extractor::{
+    PageMetadata, extract_article, extract_image, extract_links, extract_metadata, extract_raw,
+    extract_selected, extract_title_from_html,
+};
 use reqwest::Client;
-use std::time::Duration;
+use std::collections::HashSet;
+use std::time::{Duration, Instant, SystemTime};
 use tracing::{debug, info, warn};
 
 /// Options for [`fetch_page`] that control rendering and output.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct FetchOptions {
     /// Force JS rendering via playwright-cli (skip auto-detection).
     pub js: bool,
     /// Skip Readability extraction; return full HTML converted to Markdown.
     pub raw: bool,
+    /// Decode the body with this encoding, ignoring any declared charset.
+    pub charset: Option<&'static encoding_rs::Encoding>,
+    /// Return only the metadata frontmatter, skipping body extraction and conversion.
+    pub metadata_only: bool,
+    /// Follow up to this many same-origin "next page" links and append their content.
+    pub follow_pagination: Option<u8>,
+    /// Return only the fenced code blocks from the extracted content.
+    pub code_only: bool,
+    /// Return only the page's links as a Markdown list, skipping extraction.
+    /// With `flatten_links`, the list follows the flattened body instead.
+    pub links: bool,
+    /// Replace `[text](url)` with plain anchor text in the converted body.
+    pub flatten_links: bool,
+    /// Add the page's `og:image`/`twitter:image` URL to the frontmatter.
+    /// Metadata mode always includes it.
+    pub image: bool,
+    /// Return the Readability extraction and the whole-page conversion side
+    /// by side, to see what extraction dropped.
+    pub compare: bool,
+    /// Convert only the elements matching this CSS selector, bypassing
+    /// Readability. Validate it with [`parse_selector`] first.
+    pub selector: Option<String>,
+    /// Download cap in bytes. Defaults to 10 MB; clamped to [`MAX_RESPONSE_BYTES_CEILING`].
+    pub max_bytes: Option<usize>,
+    /// Send `If-Modified-Since`; a 304 yields a short "not modified" result.
+    pub if_modified_since: Option<SystemTime>,
+    /// Also accept YAML and `+json`/`+yaml` media types, as API specs are
+    /// served. Only the OpenAPI summary sets this.
+    pub accept_specs: bool,
+}
+
+impl FetchOptions {
+    fn response_limit(&self) -> usize {
+        self.max_bytes
+            .unwrap_or(MAX_RESPONSE_BYTES)
+            .min(MAX_RESPONSE_BYTES_CEILING)
+    }
 }
 
 const MAX_RESPONSE_BYTES: usize = 10_000_000;
+/// Hard upper bound for [`FetchOptions::max_bytes`].
+pub const MAX_RESPONSE_BYTES_CEILING: usize = 50_000_000;
 
 const PLAYWRIGHT_TIMEOUT: Duration = Duration::from_secs(60);
 
+/// Upper bound on extra pages fetched by [`FetchOptions::follow_pagination`].
+pub const MAX_PAGINATION_PAGES: u8 = 10;
+/// Combined extracted HTML across all pages; later pages are dropped past this.
+const MAX_PAGINATED_BYTES: usize = 2_000_000;
+
 #[derive(Debug, thiserror::Error)]
 pub enum FetchError {
     #[error("invalid URL: must be HTTP(S)")]
@@ -36,8 +97,8 @@ pub enum FetchError {
     #[error("invalid URL: {0}")]
     InvalidUrl(#[from] url::ParseError),
 
-    #[error("blocked: internal/private host not allowed")]
-    InternalHost,
+    #[error("blocked: {0}")]
+    InternalHost(BlockReason),
 
     #[error("fetch failed: {0}")]
     Http(#[from] reqwest::Error),
@@ -51,45 +112,172 @@ pub enum FetchError {
     #[error("unsupported content type: {0} (expected text/HTML)")]
     UnsupportedContentType(String),
 
-    #[error("response too large (>{} bytes)", MAX_RESPONSE_BYTES)]
-    TooLarge,
+    #[error("response too large (>{0} bytes)")]
+    TooLarge(usize),
 
     #[error("fetch timed out: {0}")]
     Timeout(String),
 
     #[error("playwright rendering failed: {0}")]
     Playwright(String),
+
+    #[error("unknown charset: {0}")]
+    UnknownCharset(String),
+
+    #[error("invalid date: {0} (expected RFC 3339 or an HTTP date)")]
+    InvalidDate(String),
+
+    #[error(
+        "blocked by a bot challenge (e.g. Cloudflare \"Just a moment...\"); the page needs a real browser"
+    )]
+    BotChallenge,
+
+    #[error("PDF text extraction failed: {0}")]
+    Pdf(String),
+
+    #[error("blocked by robots.txt: {0} is disallowed for scout")]
+    BlockedByRobots(String),
+
+    #[error("fetch not allowed: {0}")]
+    NotAllowed(String),
+
+    #[error("request budget for this call is spent (SCOUT_MAX_REQUESTS_PER_CALL)")]
+    BudgetExhausted,
+
+    #[error("not an OpenAPI or Swagger spec: {0}")]
+    NotOpenApi(String),
+
+    #[error("invalid CSS selector: {0}")]
+    InvalidSelector(String),
+
+    #[error("no element matches the selector `{0}`")]
+    SelectorNoMatch(String),
 }
 
+/// Check that `selector` parses as CSS, returning it trimmed.
+pub fn parse_selector(selector: &str) -> Result<String, FetchError> {
+    let selector = selector.trim();
+    if selector.is_empty() || dom_query::Matcher::new(selector).is_err() {
+        return Err(FetchError::InvalidSelector(selector.to_string()));
+    }
+    Ok(selector.to_string())
+}
+
+/// Resolve a user-supplied charset label (e.g. `shift_jis`, `euc-kr`).
+pub fn parse_charset(label: &str) -> Result<&'static encoding_rs::Encoding, FetchError> {
+    encoding_rs::Encoding::for_label(label.trim().as_bytes())
+        .ok_or_else(|| FetchError::UnknownCharset(label.to_string()))
+}
+
+/// Parse a user-supplied `If-Modified-Since` value: an HTTP date
+/// (`Wed, 21 Oct 2026 07:28:00 GMT`) or RFC 3339 (`2026-10-21T07:28:00Z`,
+/// `2026-10-21T16:28:00+09:00`).
+pub fn parse_if_modified_since(value: &str) -> Result<SystemTime, FetchError> {
+    let trimmed = value.trim();
+    httpdate::parse_http_date(trimmed)
+        .ok()
+        .or_else(|| parse_rfc3339(trimmed))
+        .ok_or_else(|| FetchError::InvalidDate(value.to_string()))
+}
+
+fn parse_rfc3339(value: &str) -> Option<SystemTime> {
+    let (date, time) = value.split_once(['T', 't'])?;
+    if let Some(clock) = time.strip_suffix(['Z', 'z']) {
+        return crate::github::dates::parse_date(&format!("{date}T{clock}Z"));
+    }
+    let split = time.rfind(['+', '-'])?;
+    let (clock, offset) = time.split_at(split);
+    let (sign, hhmm) = offset.split_at(1);
+    let (h, m) = hhmm.split_once(':')?;
+    let (h, m) = (h.parse::<u64>().ok()?, m.parse::<u64>().ok()?);
+    if h > 23 || m > 59 {
+        return None;
+    }
+    let local = crate::github::dates::parse_date(&format!("{date}T{clock}Z"))?;
+    let offset = Duration::from_secs(h * 3_600 + m * 60);
+    if sign == "+" {
+        local.checked_sub(offset)
+    } else {
+        local.checked_add(offset)
+    }
+}
+
+/// Challenge pages put their markers near the top; no need to scan further.
+const CHALLENGE_SNIFF_BYTES: usize = 64 * 1024;
+
+const CHALLENGE_MARKERS: &[&str] = &[
+    "/cdn-cgi/challenge-platform/",
+    "window._cf_chl_opt",
+    "id=\"cf-browser-verification\"",
+    "id=\"challenge-form\"",
+];
+
+/// Lowercased `<title>` prefixes used by challenge interstitials.
+const CHALLENGE_TITLES: &[&str] = &[
+    "just a moment...",
+    "attention required! | cloudflare",
+    "checking your browser",
+    "ddos-guard",
+];
+
 /// Minimum extracted text length to consider Readability extraction successful.
 const EXTRACT_TEXT_THRESHOLD: usize = 50;
 
+/// Extracts with less visible text than this may be a paywall stub.
+const PAYWALL_TEXT_THRESHOLD: usize = 1_000;
+
+/// Lowercased subscribe prompts a paywall leaves in the extracted teaser text.
+const PAYWALL_PROMPTS: &[&str] = &[
+    "subscribe to continue reading",
+    "subscribe to read the full",
+    "this article is for subscribers",
+    "available to subscribers only",
+    "subscriber-only content",
+];
+
+/// Elements paywall scripts hang their overlay on.
+const PAYWALL_ELEMENTS: &str =
+    r#"[class^="paywall"], [class*=" paywall"], [id^="paywall"], [data-paywall]"#;
+
 /// Fetch a web page and extract its content.
 ///
 /// Includes SSRF defense (URL validation + DNS check + post-redirect recheck).
 /// Unless [`FetchOptions::js`] is set, automatically falls back to playwright-cli
 /// for JS rendering when the page appears JS-dependent (SPA with empty body)
 /// or when Readability extraction yields too little content.
+/// With [`FetchOptions::metadata_only`], returns only the frontmatter and never
+/// auto-triggers playwright. With [`FetchOptions::code_only`], the body is
+/// reduced to the page's code blocks; with [`FetchOptions::links`], it is
+/// replaced by the page's links. PDF responses bypass extraction and are
+/// returned as their plain-text layer. Every download attempt, pagination hop,
+/// and playwright render takes one request from `budget`.
 pub async fn fetch_page(
     client: &Client,
     url: &str,
     opts: FetchOptions,
+    budget: &RequestBudget,
     resolver: &impl DnsResolver,
 ) -> Result<FetchResult, FetchError> {
-    // SECURITY: Local CLI only. TOCTOU gap between DNS check and reqwest connect
-    // is acceptable here; a network service would need a custom resolver that
-    // enforces the allowlist at connect time. Playwright widens the gap further
-    // (its own DNS resolution) — proxy or disable it in service mode.
+    // SECURITY: Pre-check only. The client built by `Scout::new` re-checks
+    // resolved addresses at connect time (`PublicOnlyResolver`), which closes
+    // the DNS-rebinding gap unless a proxy is configured. Playwright does its
+    // own DNS resolution — proxy or disable it in service mode.
     ssrf_check(url, resolver).await?;
 
-    let (final_url, mut html) = download(client, url).await?;
+    let (final_url, body, headers) = download_body(client, url, &opts, budget).await?;
 
-    ssrf_check(&final_url, resolver).await?;
+    let ssrf = ssrf_recheck(url, &final_url, resolver).await?;
+
+    let mut html = match body {
+        Body::Html(html) => html,
+        Body::Pdf(bytes) => return fetch_pdf(bytes, final_url, &headers, &ssrf, &opts).await,
+        Body::NotModified(since) => return Ok(to_not_modified_result(since, final_url)),
+    };
 
     let need_js = if opts.js {
         info!("--js flag set, using playwright-cli for JS rendering");
         true
-    } else if is_js_dependent(&html) {
+    } else if !opts.metadata_only && is_js_dependent(&html) {
         warn!("JS-dependent page detected, trying playwright-cli fallback");
         true
     } else {
@@ -97,11 +285,14 @@ pub async fn fetch_page(
     };
 
     if need_js {
-        match fetch_with_playwright(&final_url).await {
+        match fetch_with_playwright(&final_url, budget).await {
             Ok(js_html) => {
                 debug!("playwright succeeded");
                 html = js_html;
             }
+            Err(PlaywrightError::BudgetExhausted) if opts.js => {
+                return Err(FetchError::BudgetExhausted);
+            }
             Err(e) if opts.js => {
                 return Err(FetchError::Playwright(e.to_string()));
             }
@@ -111,15 +302,35 @@ pub async fn fetch_page(
         }
     }
 
-    let article = if opts.raw {
+    if opts.metadata_only {
+        debug!(url = %redact_url_credentials(&final_url), "metadata-only fetch");
+        let meta = extract_metadata(&html, &final_url);
+        return Ok(to_metadata_result(meta, final_url, &headers, &ssrf));
+    }
+
+    if opts.links && !opts.flatten_links {
+        let links = extract_links(&html, &final_url);
+        debug!(url = %redact_url_credentials(&final_url), links = links.len(), "links-only fetch");
+        return Ok(to_links_result(
+            extract_title_from_html(&html),
+            &links,
+            final_url,
+        ));
+    }
+
+    let article = if let Some(selector) = &opts.selector {
+        extract_selected(&html, selector)
+            .ok_or_else(|| FetchError::SelectorNoMatch(selector.clone()))?
+    } else if opts.raw {
         extract_raw(&html)
     } else {
         extract_article(&html, Some(&final_url))
     };
 
-    let article = if !opts.raw && !need_js && is_thin_extract(&article) {
+    let bypassed = opts.raw || opts.selector.is_some();
+    let mut article = if !bypassed && !need_js && is_thin_extract(&article) {
         warn!(url = %redact_url_credentials(&final_url), "extraction yielded too little content, trying playwright-cli fallback");
-        match fetch_with_playwright(&final_url).await {
+        match fetch_with_playwright(&final_url, budget).await {
             Ok(js_html) => {
                 let re_extracted = extract_article(&js_html, Some(&final_url));
                 if is_thin_extract(&re_extracted) {
@@ -139,7 +350,167 @@ pub async fn fetch_page(
     };
 
     debug!(url = %redact_url_credentials(&final_url), bytes = html.len(), "page fetched");
-    Ok(to_fetch_result(article, final_url))
+    let paywalled = !bypassed && is_paywalled(&html, &article);
+    if paywalled {
+        warn!(url = %redact_url_credentials(&final_url), "extract looks like a paywall stub");
+    }
+    if opts.compare {
+        return Ok(to_compare_result(article, &extract_raw(&html), final_url));
+    }
+    let page_links = opts.links.then(|| extract_links(&html, &final_url));
+    if opts.image {
+        article.image = extract_image(&html, &final_url);
+    }
+
+    if let Some(limit) = opts.follow_pagination {
+        follow_next_pages(
+            client,
+            &final_url,
+            html,
+            &mut article,
+            limit,
+            &opts,
+            budget,
+            resolver,
+        )
+        .await;
+    }
+
+    if opts.code_only {
+        return Ok(to_code_result(article, final_url));
+    }
+    if opts.flatten_links {
+        article.content_html = extractor::flatten_links(&article.content_html);
+    }
+    let mut result = to_fetch_result(article, final_url);
+    result.paywalled = paywalled;
+    if let Some(links) = page_links {
+        append_links_section(&mut result, &links);
+    }
+    Ok(result)
+}
+
+/// Build the result for a downloaded PDF. Rendering options other than
+/// `metadata_only` don't apply to PDFs.
+async fn fetch_pdf(
+    bytes: Vec<u8>,
+    final_url: String,
+    headers: &ResponseHeaders,
+    ssrf: &SsrfOutcome,
+    opts: &FetchOptions,
+) -> Result<FetchResult, FetchError> {
+    if opts.metadata_only {
+        return Ok(to_metadata_result(
+            PageMetadata::default(),
+            final_url,
+            headers,
+            ssrf,
+        ));
+    }
+    let text = pdf::extract_pdf_text(bytes).await?;
+    if pdf::is_degraded(&text) {
+        warn!(url = %redact_url_credentials(&final_url), "PDF text extraction lost some characters");
+    }
+    debug!(url = %redact_url_credentials(&final_url), chars = text.len(), "PDF extracted");
+    Ok(to_pdf_result(&text, final_url))
+}
+
+/// Append the content of up to `limit` "next" pages to `article`.
+///
+/// Each hop is SSRF-checked like the first page. Pagination is best-effort:
+/// any failure stops the walk and keeps the pages gathered so far.
+async fn follow_next_pages(
+    client: &Client,
+    first_url: &str,
+    mut html: String,
+    article: &mut extractor::ExtractedArticle,
+    limit: u8,
+    opts: &FetchOptions,
+    budget: &RequestBudget,
+    resolver: &impl DnsResolver,
+) {
+    let mut seen = HashSet::from([first_url.to_string()]);
+    let mut current_url = first_url.to_string();
+    let mut total_bytes = article.content_html.len();
+
+    for _ in 0..limit.min(MAX_PAGINATION_PAGES) {
+        let Some(next_url) = pagination::find_next_url(&html, &current_url) else {
+            break;
+        };
+        if !seen.insert(next_url.clone()) {
+            debug!(url = %redact_url_credentials(&next_url), "pagination loop detected");
+            break;
+        }
+
+        let (page_url, page_html) = match fetch_next_page(client, &next_url, opts, budget, resolver)
+            .await
+        {
+            Ok(page) => page,
+            Err(e) => {
+                warn!(url = %redact_url_credentials(&next_url), error = %e, "stopping pagination");
+                break;
+            }
+        };
+        // A redirect back onto an already-collected page ends the walk.
+        if page_url != next_url && !seen.insert(page_url.clone()) {
+            break;
+        }
+
+        let extracted = if let Some(selector) = &opts.selector {
+            match extract_selected(&page_html, selector) {
+                Some(extracted) => extracted,
+                None => {
+                    debug!(url = %redact_url_credentials(&page_url), "selector matched nothing, stopping pagination");
+                    break;
+                }
+            }
+        } else if opts.raw {
+            extract_raw(&page_html)
+        } else {
+            extract_article(&page_html, Some(&page_url))
+        };
+        total_bytes += extracted.content_html.len();
+        if total_bytes > MAX_PAGINATED_BYTES {
+            warn!(url = %redact_url_credentials(&page_url), "pagination size cap reached");
+            break;
+        }
+
+        debug!(url = %redact_url_credentials(&page_url), "appending next page");
+        article.content_html.push_str("\n<hr>\n");
+        article.content_html.push_str(&extracted.content_html);
+        current_url = page_url;
+        html = page_html;
+    }
+}
+
+/// Fetch an OpenAPI/Swagger spec (JSON or YAML) and summarize its endpoints
+/// by tag. SSRF-checked like a page fetch.
+pub async fn fetch_openapi_summary(
+    client: &Client,
+    url: &str,
+    budget: &RequestBudget,
+    resolver: &impl DnsResolver,
+) -> Result<String, FetchError> {
+    let opts = FetchOptions {
+        accept_specs: true,
+        ..FetchOptions::default()
+    };
+    let (final_url, text) = fetch_next_page(client, url, &opts, budget, resolver).await?;
+    debug!(url = %redact_url_credentials(&final_url), bytes = text.len(), "OpenAPI spec fetched");
+    openapi::summarize(&text)
+}
+
+async fn fetch_next_page(
+    client: &Client,
+    url: &str,
+    opts: &FetchOptions,
+    budget: &RequestBudget,
+    resolver: &impl DnsResolver,
+) -> Result<(String, String), FetchError> {
+    ssrf_check(url, resolver).await?;
+    let (final_url, html) = download(client, url, opts, budget).await?;
+    ssrf_recheck(url, &final_url, resolver).await?;
+    Ok((final_url, html))
 }
 
 /// Check whether the extracted article has too little visible text.
@@ -152,6 +523,43 @@ fn is_thin_extract(article: &extractor::ExtractedArticle) -> bool {
         || visible_text_len(&article.content_html, EXTRACT_TEXT_THRESHOLD) < EXTRACT_TEXT_THRESHOLD
 }
 
+/// Heuristic paywall check: a short extract plus a paywall signal — a
+/// subscribe prompt in the extracted text, a `paywall` element, or JSON-LD
+/// `isAccessibleForFree: false`. Scripts and inline config are never matched
+/// as text, and long extracts are never flagged, so an article that merely
+/// mentions subscriptions passes.
+fn is_paywalled(html: &str, article: &extractor::ExtractedArticle) -> bool {
+    if visible_text_len(&article.content_html, PAYWALL_TEXT_THRESHOLD) >= PAYWALL_TEXT_THRESHOLD {
+        return false;
+    }
+    let teaser = dom_query::Document::from(article.content_html.as_str())
+        .select("body")
+        .text()
+        .to_lowercase();
+    if PAYWALL_PROMPTS.iter().any(|p| teaser.contains(p)) {
+        return true;
+    }
+    let doc = dom_query::Document::from(html);
+    doc.select(PAYWALL_ELEMENTS).exists()
+        || doc
+            .select(r#"script[type="application/ld+json"]"#)
+            .iter()
+            .any(|script| declares_not_free(&script.text().to_ascii_lowercase()))
+}
+
+/// Whether lowercased JSON-LD carries `"isAccessibleForFree": false` (or `"false"`).
+fn declares_not_free(lower: &str) -> bool {
+    lower
+        .match_indices("\"isaccessibleforfree\"")
+        .any(|(i, key)| {
+            let value = lower[i + key.len()..].trim_start();
+            value.strip_prefix(':').is_some_and(|v| {
+                let v = v.trim_start();
+                v.starts_with("false") || v.starts_with("\"false\"")
+            })
+        })
+}
+
 /// Count non-whitespace characters outside HTML tags. Short-circuits at `limit`.
 fn visible_text_len(html: &str, limit: usize) -> usize {
     let mut count = 0usize;
@@ -270,6 +678,8 @@ enum PlaywrightError {
     Timeout(u64),
     #[error("playwright-cli failed: {0}")]
     ProcessFailed(String),
+    #[error("request budget for this call is spent")]
+    BudgetExhausted,
 }
 
 async fn drain_pipe<R: tokio::io::AsyncRead + Unpin>(mut pipe: R, limit: usize) -> Vec<u8> {
@@ -309,8 +719,14 @@ async fn resolve_playwright_cli() -> Result<String, PlaywrightError> {
     Err(PlaywrightError::NotInstalled)
 }
 
-async fn fetch_with_playwright(url: &str) -> Result<String, PlaywrightError> {
+async fn fetch_with_playwright(
+    url: &str,
+    budget: &RequestBudget,
+) -> Result<String, PlaywrightError> {
     let cli = resolve_playwright_cli().await?;
+    if !budget.try_acquire() {
+        return Err(PlaywrightError::BudgetExhausted);
+    }
 
     let escaped_url = shell_escape::escape(url.into());
     let cmd = format!(
@@ -391,26 +807,129 @@ fn parse_playwright_output(stdout: &str) -> Result<String, PlaywrightError> {
     ))
 }
 
-async fn download(client: &Client, url: &str) -> Result<(String, String), FetchError> {
-    let response = client
-        .get(url)
-        .header("User-Agent", crate::USER_AGENT)
-        .send()
-        .await?;
+/// A successfully downloaded response body.
+#[derive(Debug)]
+enum Body {
+    Html(String),
+    Pdf(Vec<u8>),
+    /// 304 to a request sent with `If-Modified-Since` this date.
+    NotModified(SystemTime),
+}
+
+/// Download an HTML/text page. PDFs are rejected; only the first page of a
+/// fetch goes through the PDF path, and only it is sent conditionally.
+async fn download(
+    client: &Client,
+    url: &str,
+    opts: &FetchOptions,
+    budget: &RequestBudget,
+) -> Result<(String, String), FetchError> {
+    let opts = FetchOptions {
+        if_modified_since: None,
+        ..opts.clone()
+    };
+    match download_body(client, url, &opts, budget).await? {
+        (final_url, Body::Html(html), _) => Ok((final_url, html)),
+        (_, Body::Pdf(_), _) => Err(FetchError::UnsupportedContentType(
+            "application/pdf".to_string(),
+        )),
+        (_, Body::NotModified(_), _) => Err(FetchError::Status(304)),
+    }
+}
+
+/// Download with retries for dropped connections; statuses and content
+/// problems are returned on the first attempt. Each attempt takes one request
+/// from `budget`.
+async fn download_body(
+    client: &Client,
+    url: &str,
+    opts: &FetchOptions,
+    budget: &RequestBudget,
+) -> Result<(String, Body, ResponseHeaders), FetchError> {
+    retry_with(
+        || download_body_once(client, url, opts, budget),
+        is_retriable_download,
+        || FetchError::Timeout("retries exhausted".into()),
+    )
+    .await
+}
+
+/// Connect failures, timeouts, resets before the response, and bodies cut off
+/// mid-read. No content decoders are enabled, so a decode error can only come
+/// from the connection. Refusals from the connect-time SSRF resolver carry a
+/// [`FetchError`] and are final.
+fn is_retriable_download(e: &FetchError) -> bool {
+    let FetchError::Http(e) = e else {
+        return false;
+    };
+    let mut source = std::error::Error::source(e);
+    while let Some(inner) = source {
+        if inner.is::<FetchError>() {
+            return false;
+        }
+        source = inner.source();
+    }
+    is_transient_network(e) || e.is_request() || e.is_decode()
+}
+
+async fn download_body_once(
+    client: &Client,
+    url: &str,
+    opts: &FetchOptions,
+    budget: &RequestBudget,
+) -> Result<(String, Body, ResponseHeaders), FetchError> {
+    if !budget.try_acquire() {
+        return Err(FetchError::BudgetExhausted);
+    }
+    let started = Instant::now();
+    let mut request = client.get(url).header("User-Agent", crate::USER_AGENT);
+    if let Some(since) = opts.if_modified_since {
+        request = request.header(
+            reqwest::header::IF_MODIFIED_SINCE,
+            httpdate::fmt_http_date(since),
+        );
+    }
+    let response = request.send().await?;
 
     let status = response.status();
+    let cf_mitigated = response
+        .headers()
+        .get("cf-mitigated")
+        .is_some_and(|v| v.as_bytes().eq_ignore_ascii_case(b"challenge"));
+    if cf_mitigated {
+        return Err(FetchError::BotChallenge);
+    }
+    if let Some(since) = opts.if_modified_since
+        && status == reqwest::StatusCode::NOT_MODIFIED
+    {
+        let final_url = response.url().to_string();
+        return Ok((
+            final_url,
+            Body::NotModified(since),
+            ResponseHeaders::default(),
+        ));
+    }
     if !status.is_success() {
+        // Challenge interstitials are served as 403/429/503; peek at the body
+        // so they are reported as gated rather than as a plain HTTP error.
+        if matches!(status.as_u16(), 403 | 429 | 503) {
+            let head = read_prefix(response, CHALLENGE_SNIFF_BYTES).await;
+            if is_bot_challenge(&String::from_utf8_lossy(&head)) {
+                return Err(FetchError::BotChallenge);
+            }
+        }
         return Err(FetchError::Status(status.as_u16()));
     }
 
     let mut charset = None;
+    let mut kind = ContentKind::Text;
     match response.headers().get("content-type") {
         None => {
             debug!(url = %redact_url_credentials(url), "no Content-Type header, proceeding as text")
         }
         Some(ct) => match ct.to_str() {
             Ok(ct_str) => {
-                check_content_type(ct_str)?;
+                kind = check_content_type(ct_str, opts.accept_specs)?;
                 charset = extract_charset(ct_str);
             }
             Err(_) => {
@@ -420,27 +939,88 @@ async fn download(client: &Client, url: &str) -> Result<(String, String), FetchE
     }
 
     let final_url = response.url().to_string();
+    let header_str = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let mut headers = ResponseHeaders {
+        status: Some(status.as_u16()),
+        content_type: header_str(reqwest::header::CONTENT_TYPE),
+        last_modified: header_str(reqwest::header::LAST_MODIFIED),
+        ..ResponseHeaders::default()
+    };
 
+    let limit = opts.response_limit();
     let content_length = response.content_length();
     if let Some(len) = content_length
-        && len as usize > MAX_RESPONSE_BYTES
+        && len as usize > limit
     {
-        return Err(FetchError::TooLarge);
+        return Err(FetchError::TooLarge(limit));
     }
 
     let capacity = content_length
-        .map(|len| (len as usize).min(MAX_RESPONSE_BYTES))
+        .map(|len| (len as usize).min(limit))
         .unwrap_or(8192);
     let mut body = Vec::with_capacity(capacity);
     let mut stream = response;
     while let Some(chunk) = stream.chunk().await? {
         body.extend_from_slice(&chunk);
-        if body.len() > MAX_RESPONSE_BYTES {
-            return Err(FetchError::TooLarge);
+        if body.len() > limit {
+            return Err(FetchError::TooLarge(limit));
         }
     }
-    let html = decode_body(&body, charset.as_deref());
-    Ok((final_url, html))
+    headers.fetch_time_ms = Some(started.elapsed().as_millis() as u64);
+    headers.response_bytes = Some(body.len());
+    if kind == ContentKind::Pdf {
+        return Ok((final_url, Body::Pdf(body), headers));
+    }
+    // Precedence: --charset, then the header, then a <meta> declaration.
+    let sniffed = charset
+        .is_none()
+        .then(|| sniff_meta_charset(&body))
+        .flatten();
+    let label = opts
+        .charset
+        .map(encoding_rs::Encoding::name)
+        .or(charset.as_deref())
+        .or(sniffed.as_deref());
+    let html = decode_body(&body, label);
+    Ok((final_url, Body::Html(html), headers))
+}
+
+/// Read at most `limit` bytes of the body; errors just end the read early.
+async fn read_prefix(mut response: reqwest::Response, limit: usize) -> Vec<u8> {
+    let mut buf = Vec::new();
+    while buf.len() < limit {
+        match response.chunk().await {
+            Ok(Some(chunk)) => buf.extend_from_slice(&chunk),
+            _ => break,
+        }
+    }
+    buf.truncate(limit);
+    buf
+}
+
+/// Detect bot-challenge interstitials (Cloudflare and look-alikes) in the body
+/// of a 403/429/503 response.
+///
+/// Only markers that appear in the challenge page itself are matched, so an
+/// article that merely mentions Cloudflare is not flagged. Successful pages
+/// are never sniffed: Cloudflare injects its challenge-platform script into
+/// ordinary 200 responses too, and a real challenge served as 200 carries
+/// `cf-mitigated: challenge`.
+fn is_bot_challenge(html: &str) -> bool {
+    let head = &html[..html.floor_char_boundary(CHALLENGE_SNIFF_BYTES)];
+    if CHALLENGE_MARKERS.iter().any(|m| head.contains(m)) {
+        return true;
+    }
+    let lower = head.to_ascii_lowercase();
+    CHALLENGE_TITLES
+        .iter()
+        .any(|t| lower.contains(&format!("<title>{t}")))
 }
 
 fn extract_charset(content_type: &str) -> Option<String> {
@@ -457,7 +1037,55 @@ fn extract_charset(content_type: &str) -> Option<String> {
     })
 }
 
+/// How far into the body to look for a `<meta>` charset declaration.
+const META_CHARSET_SNIFF_BYTES: usize = 4096;
+
+/// Find the charset declared by `<meta charset=..>` or
+/// `<meta http-equiv="Content-Type" content="..; charset=..">` near the top
+/// of the document. Bytes are read as Latin-1 so the ASCII markup is legible
+/// whatever the real encoding.
+fn sniff_meta_charset(bytes: &[u8]) -> Option<String> {
+    let head: String = bytes[..bytes.len().min(META_CHARSET_SNIFF_BYTES)]
+        .iter()
+        .map(|&b| char::from(b).to_ascii_lowercase())
+        .collect();
+    let mut rest = head.as_str();
+    while let Some(start) = rest.find("<meta") {
+        let tag = &rest[start..];
+        let tag = &tag[..tag.find('>').unwrap_or(tag.len())];
+        if let Some(pos) = tag.find("charset=") {
+            let value = tag[pos + "charset=".len()..].trim_start_matches(['"', '\'', ' ']);
+            let end = value
+                .find(|c: char| matches!(c, '"' | '\'' | ';' | '/' | '>') || c.is_whitespace())
+                .unwrap_or(value.len());
+            let label = &value[..end];
+            if !label.is_empty() {
+                // A document that can declare itself in ASCII isn't UTF-16.
+                return Some(if label.starts_with("utf-16") {
+                    "utf-8".to_string()
+                } else {
+                    label.to_string()
+                });
+            }
+        }
+        rest = &rest[start + "<meta".len()..];
+    }
+    None
+}
+
+/// A byte-order mark overrides `charset`: it names the encoding (UTF-8,
+/// UTF-16LE, or UTF-16BE) and is stripped from the result.
 fn decode_body(bytes: &[u8], charset: Option<&str>) -> String {
+    if let Some((encoding, bom_len)) = encoding_rs::Encoding::for_bom(bytes) {
+        let (decoded, had_errors) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+        if had_errors {
+            warn!(
+                charset = encoding.name(),
+                "lossy decoding: some bytes could not be decoded"
+            );
+        }
+        return decoded.into_owned();
+    }
     let label = charset.unwrap_or("utf-8");
     let encoding = encoding_rs::Encoding::for_label(label.as_bytes()).unwrap_or(encoding_rs::UTF_8);
     if encoding == encoding_rs::UTF_8 {
@@ -473,17 +1101,33 @@ fn decode_body(bytes: &[u8], charset: Option<&str>) -> String {
     decoded.into_owned()
 }
 
-fn check_content_type(content_type: &str) -> Result<(), FetchError> {
+#[derive(Debug, PartialEq)]
+enum ContentKind {
+    Text,
+    Pdf,
+}
+
+/// `accept_specs` adds the YAML and structured-suffix types API specs use.
+fn check_content_type(content_type: &str, accept_specs: bool) -> Result<ContentKind, FetchError> {
     let mime = content_type.split(';').next().unwrap_or("").trim();
+    if mime.eq_ignore_ascii_case("application/pdf") {
+        return Ok(ContentKind::Pdf);
+    }
+    let spec = accept_specs
+        && (mime == "application/yaml"
+            || mime == "application/x-yaml"
+            || mime.ends_with("+json")
+            || mime.ends_with("+yaml"));
     if !mime.is_empty()
         && !mime.starts_with("text/")
         && mime != "application/xhtml+xml"
         && mime != "application/xml"
         && mime != "application/json"
+        && !spec
     {
         return Err(FetchError::UnsupportedContentType(mime.to_string()));
     }
-    Ok(())
+    Ok(ContentKind::Text)
 }
 
 #[cfg(test)]
@@ -506,6 +1150,38 @@ mod charset_tests {
         );
     }
 
+    #[test]
+    fn sniffs_meta_charset_declarations() {
+        assert_eq!(
+            sniff_meta_charset(b"<html><head><meta charset=\"Shift_JIS\">").as_deref(),
+            Some("shift_jis")
+        );
+        assert_eq!(
+            sniff_meta_charset(
+                b"<META HTTP-EQUIV='Content-Type' CONTENT='text/html; charset=euc-kr'>"
+            )
+            .as_deref(),
+            Some("euc-kr")
+        );
+        assert_eq!(
+            sniff_meta_charset(b"<meta name=viewport><meta charset=utf-16le/>").as_deref(),
+            Some("utf-8")
+        );
+        assert!(sniff_meta_charset(b"<meta name=\"description\" content=\"x\">").is_none());
+        assert!(sniff_meta_charset(b"<p>charset=latin1 is not a meta tag</p>").is_none());
+    }
+
+    #[test]
+    fn decode_uses_meta_charset_for_shift_jis_body() {
+        let (bytes, _, _) = encoding_rs::SHIFT_JIS
+            .encode("<html><head><meta charset=\"shift_jis\"></head><body>日本語</body></html>");
+        let label = sniff_meta_charset(&bytes);
+        assert_eq!(
+            decode_body(&bytes, label.as_deref()),
+            "<html><head><meta charset=\"shift_jis\"></head><body>日本語</body></html>"
+        );
+    }
+
     #[test]
     fn returns_none_when_no_charset() {
         assert!(extract_charset("text/html").is_none());
@@ -538,6 +1214,23 @@ mod charset_tests {
         let bytes = "hello".as_bytes();
         assert_eq!(decode_body(bytes, Some("unknown-encoding")), "hello");
     }
+
+    #[test]
+    fn decode_body_honors_byte_order_mark() {
+        let utf16 = |bom: [u8; 2], unit: fn(u16) -> [u8; 2]| {
+            let mut bytes = bom.to_vec();
+            bytes.extend("日本語".encode_utf16().flat_map(unit));
+            bytes
+        };
+        let le = utf16([0xFF, 0xFE], u16::to_le_bytes);
+        assert_eq!(decode_body(&le, None), "日本語");
+        assert_eq!(decode_body(&le, Some("shift_jis")), "日本語");
+        let be = utf16([0xFE, 0xFF], u16::to_be_bytes);
+        assert_eq!(decode_body(&be, Some("utf-8")), "日本語");
+
+        let utf8 = [&[0xEF, 0xBB, 0xBF][..], "日本語".as_bytes()].concat();
+        assert_eq!(decode_body(&utf8, Some("euc-jp")), "日本語");
+    }
 }
 
 #[cfg(test)]
@@ -546,41 +1239,62 @@ mod content_type_tests {
 
     #[test]
     fn accepts_text_html() {
-        assert!(check_content_type("text/html; charset=utf-8").is_ok());
+        assert!(check_content_type("text/html; charset=utf-8", false).is_ok());
     }
 
     #[test]
     fn accepts_text_plain() {
-        assert!(check_content_type("text/plain").is_ok());
+        assert!(check_content_type("text/plain", false).is_ok());
     }
 
     #[test]
     fn accepts_xhtml() {
-        assert!(check_content_type("application/xhtml+xml").is_ok());
+        assert!(check_content_type("application/xhtml+xml", false).is_ok());
     }
 
     #[test]
     fn accepts_xml() {
-        assert!(check_content_type("application/xml").is_ok());
+        assert!(check_content_type("application/xml", false).is_ok());
     }
 
     #[test]
     fn accepts_json() {
-        assert!(check_content_type("application/json").is_ok());
+        assert!(check_content_type("application/json", false).is_ok());
     }
 
     #[test]
-    fn rejects_pdf() {
-        assert!(matches!(
-            check_content_type("application/pdf"),
-            Err(FetchError::UnsupportedContentType(ref m)) if m == "application/pdf"
-        ));
+    fn accepts_yaml_and_structured_suffixes_only_for_specs() {
+        for mime in [
+            "application/yaml",
+            "application/x-yaml",
+            "application/vnd.oai.openapi+json",
+            "application/vnd.oai.openapi+yaml",
+        ] {
+            assert!(check_content_type(mime, true).is_ok(), "{mime}");
+            assert!(check_content_type(mime, false).is_err(), "{mime}");
+        }
+    }
+
+    #[test]
+    fn detects_pdf() {
+        assert_eq!(
+            check_content_type("application/pdf", false).unwrap(),
+            ContentKind::Pdf
+        );
+        assert_eq!(
+            check_content_type("Application/PDF; qs=0.9", false).unwrap(),
+            ContentKind::Pdf
+        );
+        assert_eq!(
+            check_content_type("text/html", false).unwrap(),
+            ContentKind::Text
+        );
     }
 
     #[test]
     fn rejects_image() {
         assert!(matches!(
-            check_content_type("image/png"),
+            check_content_type("image/png", false),
             Err(FetchError::UnsupportedContentType(_))
         ));
     }
@@ -588,7 +1302,7 @@ mod content_type_tests {
     #[test]
     fn accepts_empty_mime_before_semicolon() {
         // Edge case: "; charset=utf-8" → empty mime → allowed (permissive)
-        assert!(check_content_type("; charset=utf-8").is_ok());
+        assert!(check_content_type("; charset=utf-8", false).is_ok());
     }
 }
 
@@ -611,9 +1325,14 @@ mod download_tests {
             .await;
 
         let client = Client::new();
-        let (final_url, html) = download(&client, &format!("{}/page", server.uri()))
-            .await
-            .unwrap();
+        let (final_url, html) = download(
+            &client,
+            &format!("{}/page", server.uri()),
+            &FetchOptions::default(),
+            &RequestBudget::default(),
+        )
+        .await
+        .unwrap();
 
         assert!(final_url.contains("/page"));
         assert!(html.contains("hello"));
@@ -635,11 +1354,23 @@ mod download_tests {
 
         let client = Client::new();
         assert!(matches!(
-            download(&client, &format!("{}/404", server.uri())).await,
+            download(
+                &client,
+                &format!("{}/404", server.uri()),
+                &FetchOptions::default(),
+                &RequestBudget::default()
+            )
+            .await,
             Err(FetchError::Status(404))
         ));
         assert!(matches!(
-            download(&client, &format!("{}/500", server.uri())).await,
+            download(
+                &client,
+                &format!("{}/500", server.uri()),
+                &FetchOptions::default(),
+                &RequestBudget::default()
+            )
+            .await,
             Err(FetchError::Status(500))
         ));
     }
@@ -655,8 +1386,60 @@ mod download_tests {
             .await;
 
         let client = Client::new();
-        let result = download(&client, &format!("{}/huge", server.uri())).await;
-        assert!(matches!(result, Err(FetchError::TooLarge)));
+        let result = download(
+            &client,
+            &format!("{}/huge", server.uri()),
+            &FetchOptions::default(),
+            &RequestBudget::default(),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(FetchError::TooLarge(MAX_RESPONSE_BYTES))
+        ));
+    }
+
+    #[tokio::test]
+    async fn download_honors_per_request_limit() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/page"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/html")
+                    .set_body_string("x".repeat(2_000)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::new();
+        let url = format!("{}/page", server.uri());
+        let with_limit = |max_bytes| FetchOptions {
+            max_bytes: Some(max_bytes),
+            ..Default::default()
+        };
+
+        let err = download(&client, &url, &with_limit(1_000), &RequestBudget::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, FetchError::TooLarge(1_000)));
+        assert_eq!(err.to_string(), "response too large (>1000 bytes)");
+
+        assert!(
+            download(&client, &url, &with_limit(4_000), &RequestBudget::default())
+                .await
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn response_limit_is_clamped_to_ceiling() {
+        let opts = FetchOptions {
+            max_bytes: Some(usize::MAX),
+            ..Default::default()
+        };
+        assert_eq!(opts.response_limit(), MAX_RESPONSE_BYTES_CEILING);
+        assert_eq!(FetchOptions::default().response_limit(), MAX_RESPONSE_BYTES);
     }
 
     #[tokio::test]
@@ -673,7 +1456,13 @@ mod download_tests {
             .await;
 
         let client = Client::new();
-        let result = download(&client, &format!("{}/binary", server.uri())).await;
+        let result = download(
+            &client,
+            &format!("{}/binary", server.uri()),
+            &FetchOptions::default(),
+            &RequestBudget::default(),
+        )
+        .await;
         assert!(
             matches!(result, Err(FetchError::UnsupportedContentType(ref ct)) if ct == "application/pdf"),
             "got: {result:?}"
@@ -681,56 +1470,248 @@ mod download_tests {
     }
 
     #[tokio::test]
-    async fn download_accepts_text_html_content_type() {
+    async fn download_charset_override_fixes_mislabeled_page() {
+        let (body, _, _) =
+            encoding_rs::SHIFT_JIS.encode("<html><body>日本語のページ</body></html>");
         let server = MockServer::start().await;
         Mock::given(method("GET"))
-            .and(path("/html"))
+            .and(path("/sjis"))
             .respond_with(
                 ResponseTemplate::new(200)
                     .insert_header("content-type", "text/html; charset=utf-8")
-                    .set_body_string("<html><body>ok</body></html>"),
+                    .set_body_bytes(body.into_owned()),
             )
             .mount(&server)
             .await;
 
         let client = Client::new();
-        let (_, html) = download(&client, &format!("{}/html", server.uri()))
-            .await
-            .unwrap();
-        assert!(html.contains("ok"));
-    }
-
-}
-
-#[cfg(test)]
-mod fetch_page_tests {
-    use super::*;
-    use wiremock::matchers::{method, path};
-    use wiremock::{Mock, MockServer, ResponseTemplate};
-
-    #[tokio::test]
-    async fn blocks_ssrf_to_localhost() {
-        let client = Client::new();
-        let result = fetch_page(
+        let url = format!("{}/sjis", server.uri());
+        let (_, mislabeled) = download(
             &client,
-            "http://127.0.0.1/secret",
-            FetchOptions::default(),
-            &TokioDnsResolver,
+            &url,
+            &FetchOptions::default(),
+            &RequestBudget::default(),
         )
-        .await;
-        assert!(matches!(result, Err(FetchError::InternalHost)));
+        .await
+        .unwrap();
+        assert!(!mislabeled.contains("日本語"));
+
+        let opts = FetchOptions {
+            charset: Some(parse_charset("Shift_JIS").unwrap()),
+            ..Default::default()
+        };
+        let (_, html) = download(&client, &url, &opts, &RequestBudget::default())
+            .await
+            .unwrap();
+        assert!(html.contains("日本語のページ"), "got: {html}");
     }
 
     #[tokio::test]
-    async fn js_flag_attempts_playwright_on_rich_body() {
-        // Serve a page with enough visible text that auto-detection would NOT trigger.
-        let content = "x".repeat(200);
+    async fn download_detects_meta_charset_without_header_charset() {
+        let (body, _, _) = encoding_rs::SHIFT_JIS.encode(
+            "<html><head><meta charset=\"Shift_JIS\"></head><body>日本語のページ</body></html>",
+        );
         let server = MockServer::start().await;
         Mock::given(method("GET"))
-            .and(path("/rich"))
+            .and(path("/sjis"))
             .respond_with(
                 ResponseTemplate::new(200)
-                    .set_body_string(format!("<html><body><p>{content}</p></body></html>")),
+                    .insert_header("content-type", "text/html")
+                    .set_body_bytes(body.into_owned()),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::new();
+        let url = format!("{}/sjis", server.uri());
+        let (_, html) = download(
+            &client,
+            &url,
+            &FetchOptions::default(),
+            &RequestBudget::default(),
+        )
+        .await
+        .unwrap();
+        assert!(html.contains("日本語のページ"), "got: {html}");
+    }
+
+    #[tokio::test]
+    async fn download_prefers_header_charset_over_meta() {
+        let body = "<html><head><meta charset=\"Shift_JIS\"></head><body>日本語</body></html>";
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/utf8"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/html; charset=utf-8")
+                    .set_body_bytes(body.as_bytes().to_vec()),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::new();
+        let url = format!("{}/utf8", server.uri());
+        let (_, html) = download(
+            &client,
+            &url,
+            &FetchOptions::default(),
+            &RequestBudget::default(),
+        )
+        .await
+        .unwrap();
+        assert!(html.contains("日本語"), "got: {html}");
+    }
+
+    #[test]
+    fn parse_charset_rejects_unknown_label() {
+        assert!(parse_charset("euc-jp").is_ok());
+        assert!(matches!(
+            parse_charset("klingon-8"),
+            Err(FetchError::UnknownCharset(ref l)) if l == "klingon-8"
+        ));
+    }
+
+    #[test]
+    fn parse_if_modified_since_accepts_http_and_rfc3339_dates() {
+        let expected = httpdate::parse_http_date("Wed, 21 Oct 2026 07:28:00 GMT").unwrap();
+        for value in [
+            "Wed, 21 Oct 2026 07:28:00 GMT",
+            "2026-10-21T07:28:00Z",
+            " 2026-10-21T07:28:00.250Z ",
+            "2026-10-21T16:28:00+09:00",
+            "2026-10-21T02:28:00-05:00",
+        ] {
+            assert_eq!(parse_if_modified_since(value).unwrap(), expected, "{value}");
+        }
+        for value in [
+            "yesterday",
+            "2026-10-21T07:28:00",
+            "2026-10-21T07:28:00+25:00",
+        ] {
+            assert!(
+                matches!(
+                    parse_if_modified_since(value),
+                    Err(FetchError::InvalidDate(_))
+                ),
+                "{value}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn download_accepts_text_html_content_type() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/html"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/html; charset=utf-8")
+                    .set_body_string("<html><body>ok</body></html>"),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::new();
+        let (_, html) = download(
+            &client,
+            &format!("{}/html", server.uri()),
+            &FetchOptions::default(),
+            &RequestBudget::default(),
+        )
+        .await
+        .unwrap();
+        assert!(html.contains("ok"));
+    }
+
+    #[tokio::test]
+    async fn download_retries_after_dropped_connection() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            // First connection: hang up before sending a response.
+            let (first, _) = listener.accept().await.unwrap();
+            drop(first);
+            let (mut second, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = second.read(&mut buf).await;
+            let body = "<html><body>recovered</body></html>";
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: text/html\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            second.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let client = Client::new();
+        let (_, html) = download(
+            &client,
+            &format!("http://{addr}/"),
+            &FetchOptions::default(),
+            &RequestBudget::default(),
+        )
+        .await
+        .unwrap();
+        assert!(html.contains("recovered"));
+    }
+
+    #[tokio::test]
+    async fn download_does_not_retry_client_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::new();
+        let err = download(
+            &client,
+            &server.uri(),
+            &FetchOptions::default(),
+            &RequestBudget::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, FetchError::Status(404)));
+        assert!(!is_retriable_download(&FetchError::UnsupportedContentType(
+            "application/pdf".into()
+        )));
+    }
+
+}
+
+#[cfg(test)]
+mod fetch_page_tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn blocks_ssrf_to_localhost() {
+        let client = Client::new();
+        let result = fetch_page(
+            &client,
+            "http://127.0.0.1/secret",
+            FetchOptions::default(),
+            &RequestBudget::default(),
+            &TokioDnsResolver,
+        )
+        .await;
+        assert!(matches!(result, Err(FetchError::InternalHost(_))));
+    }
+
+    #[tokio::test]
+    async fn js_flag_attempts_playwright_on_rich_body() {
+        // Serve a page with enough visible text that auto-detection would NOT trigger.
+        let content = "x".repeat(200);
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/rich"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(format!("<html><body><p>{content}</p></body></html>")),
             )
             .mount(&server)
             .await;
@@ -741,6 +1722,7 @@ mod fetch_page_tests {
             &client,
             &format!("{}/rich", server.uri()),
             opts,
+            &RequestBudget::default(),
             &TokioDnsResolver,
         )
         .await;
@@ -749,6 +1731,640 @@ mod fetch_page_tests {
         // return an error rather than silently falling back.
         assert!(result.is_err(), "js=true should error when playwright unavailable");
     }
+
+    fn article_page(heading: &str, body: &str, next: Option<&str>) -> String {
+        let next = next
+            .map(|href| format!(r#"<link rel="next" href="{href}">"#))
+            .unwrap_or_default();
+        // Readability only scores paragraphs of 140+ chars; keep them long enough
+        // that extraction succeeds and no playwright fallback is attempted.
+        let filler = "Enough prose to look like a real article body. ".repeat(4);
+        let paragraphs = (0..4)
+            .map(|i| format!("<p>{body} paragraph {i}. {filler}</p>"))
+            .collect::<String>();
+        format!(
+            "<html><head><title>{heading}</title>{next}</head>\
+             <body><article><h1>{heading}</h1>{paragraphs}</article></body></html>"
+        )
+    }
+
+    #[tokio::test]
+    async fn follow_pagination_appends_next_page() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/story"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(article_page(
+                "Story",
+                "First page",
+                Some("/story/2"),
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/story/2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(article_page(
+                "Story",
+                "Second page",
+                Some("/story"),
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+        let url = format!("http://pages.test:{}/story", server.address().port());
+        let opts = FetchOptions {
+            follow_pagination: Some(5),
+            ..Default::default()
+        };
+
+        let result = fetch_page(&client, &url, opts, &RequestBudget::default(), &PublicDns)
+            .await
+            .unwrap();
+
+        assert!(
+            result.markdown.contains("First page"),
+            "got: {}",
+            result.markdown
+        );
+        assert!(
+            result.markdown.contains("Second page"),
+            "got: {}",
+            result.markdown
+        );
+    }
+
+    #[tokio::test]
+    async fn follow_pagination_stops_when_budget_is_spent() {
+        let server = MockServer::start().await;
+        let pages = [
+            ("/story", "Page 1", "/story/2", 1),
+            ("/story/2", "Page 2", "/story/3", 1),
+            ("/story/3", "Page 3", "/story/4", 0),
+        ];
+        for (route, body, next, hits) in pages {
+            Mock::given(method("GET"))
+                .and(path(route))
+                .respond_with(ResponseTemplate::new(200).set_body_string(article_page(
+                    "Story",
+                    body,
+                    Some(next),
+                )))
+                .expect(hits)
+                .mount(&server)
+                .await;
+        }
+
+        let client = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+        let url = format!("http://pages.test:{}/story", server.address().port());
+        let opts = FetchOptions {
+            follow_pagination: Some(5),
+            ..Default::default()
+        };
+        let budget = RequestBudget::new(Some(2));
+
+        let result = fetch_page(&client, &url, opts, &budget, &PublicDns)
+            .await
+            .unwrap();
+
+        let md = &result.markdown;
+        assert!(md.contains("Page 1") && md.contains("Page 2"), "got: {md}");
+        assert!(!md.contains("Page 3"), "got: {md}");
+        assert_eq!(budget.exhausted_at(), Some(2));
+    }
+
+    #[tokio::test]
+    async fn links_mode_lists_absolute_links() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/docs/index"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"<html><head><title>Docs</title></head><body>
+                <a href="guide">Guide</a>
+                <a href="mailto:team@example.com">Mail us</a>
+                <a href="https://crates.io/crates/scout">Crate (latest)</a>
+                <a href="guide">Guide again</a>
+                </body></html>"#,
+                "text/html",
+            ))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+        let base = format!("http://pages.test:{}", server.address().port());
+        let opts = FetchOptions {
+            links: true,
+            ..Default::default()
+        };
+
+        let result = fetch_page(
+            &client,
+            &format!("{base}/docs/index"),
+            opts,
+            &RequestBudget::default(),
+            &PublicDns,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            result.markdown,
+            format!(
+                "---\ntitle: \"Docs\"\n---\n\n\
+                 - [Guide]({base}/docs/guide)\n\
+                 - [Crate \\(latest\\)](https://crates.io/crates/scout)\n"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn flatten_links_keeps_anchor_text_and_lists_links_after_body() {
+        let filler = "Enough prose to look like a real documentation paragraph. ".repeat(4);
+        let html = format!(
+            r#"<html><head><title>Guide</title></head><body><article>
+            <h1>Guide</h1>
+            <p>Read the <a href="/setup">setup notes</a> first. {filler}</p>
+            <p>Then see <a href="https://crates.io/crates/scout">the crate</a>. {filler}</p>
+            </article></body></html>"#
+        );
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/guide"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(html))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+        let base = format!("http://pages.test:{}", server.address().port());
+        let url = format!("{base}/guide");
+
+        let flat = FetchOptions {
+            flatten_links: true,
+            ..Default::default()
+        };
+        let result = fetch_page(&client, &url, flat, &RequestBudget::default(), &PublicDns)
+            .await
+            .unwrap();
+        let md = &result.markdown;
+        assert!(md.contains("Read the setup notes first."), "got: {md}");
+        assert!(md.contains("Then see the crate."), "got: {md}");
+        assert!(!md.contains("]("), "got: {md}");
+
+        let with_list = FetchOptions {
+            flatten_links: true,
+            links: true,
+            ..Default::default()
+        };
+        let result = fetch_page(
+            &client,
+            &url,
+            with_list,
+            &RequestBudget::default(),
+            &PublicDns,
+        )
+        .await
+        .unwrap();
+        let md = &result.markdown;
+        assert!(md.contains("Read the setup notes first."), "got: {md}");
+        let (body, links) = md.split_once("\n## Links\n\n").expect("links section");
+        assert!(!body.contains("]("), "got: {md}");
+        assert_eq!(
+            links,
+            format!(
+                "- [setup notes]({base}/setup)\n- [the crate](https://crates.io/crates/scout)\n"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn image_adds_absolute_og_image_url_to_frontmatter() {
+        let filler = "Enough prose to look like a real documentation paragraph. ".repeat(4);
+        let html = format!(
+            r#"<html><head><title>Launch</title>
+            <meta property="og:image" content="/img/cover.png">
+            </head><body><article>
+            <h1>Launch</h1>
+            <p>Version one is out today. {filler}</p>
+            <p>Here is what changed since the beta. {filler}</p>
+            </article></body></html>"#
+        );
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/post"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(html))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+        let base = format!("http://pages.test:{}", server.address().port());
+        let url = format!("{base}/post");
+
+        let opts = FetchOptions {
+            image: true,
+            ..Default::default()
+        };
+        let result = fetch_page(&client, &url, opts, &RequestBudget::default(), &PublicDns)
+            .await
+            .unwrap();
+        let (frontmatter, _) = result.markdown[4..].split_once("---").unwrap();
+        assert!(
+            frontmatter.contains(&format!("image: \"{base}/img/cover.png\"\n")),
+            "got: {}",
+            result.markdown
+        );
+
+        let result = fetch_page(
+            &client,
+            &url,
+            FetchOptions::default(),
+            &RequestBudget::default(),
+            &PublicDns,
+        )
+        .await
+        .unwrap();
+        assert!(
+            !result.markdown.contains("image:"),
+            "got: {}",
+            result.markdown
+        );
+    }
+
+    #[tokio::test]
+    async fn compare_labels_extracted_and_raw_sections() {
+        let filler = "Enough prose to look like a real documentation paragraph. ".repeat(4);
+        let html = format!(
+            r#"<html><head><title>Guide</title></head><body>
+            <article>
+            <h1>Guide</h1>
+            <p>First paragraph. {filler}</p>
+            <p>Second paragraph. {filler}</p>
+            </article>
+            <div class="comments"><p>Reader comment: thanks</p></div>
+            </body></html>"#
+        );
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/guide"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(html))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+        let url = format!("http://pages.test:{}/guide", server.address().port());
+        let opts = FetchOptions {
+            compare: true,
+            ..Default::default()
+        };
+        let result = fetch_page(&client, &url, opts, &RequestBudget::default(), &PublicDns)
+            .await
+            .unwrap();
+        let md = &result.markdown;
+        let (extracted, raw) = md
+            .split_once("## Raw (whole page)\n\n")
+            .expect("raw section");
+        let (_, extracted) = extracted
+            .split_once("## Extracted (Readability)\n\n")
+            .expect("extracted section");
+        assert!(extracted.contains("First paragraph."), "got: {md}");
+        assert!(!extracted.contains("Reader comment"), "got: {md}");
+        assert!(raw.contains("Reader comment"), "got: {md}");
+        assert!(raw.contains("Second paragraph."), "got: {md}");
+    }
+
+    #[tokio::test]
+    async fn selector_converts_only_the_matching_element() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/post"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<html><head><title>Post</title></head><body>
+                <nav>Home | Archive</nav>
+                <div id="main-content"><h2>Release notes</h2><p>Short but important.</p></div>
+                <aside>Related posts</aside>
+                </body></html>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+        let url = format!("http://pages.test:{}/post", server.address().port());
+        let opts = |selector: &str| FetchOptions {
+            selector: Some(parse_selector(selector).unwrap()),
+            ..Default::default()
+        };
+
+        let result = fetch_page(
+            &client,
+            &url,
+            opts("#main-content"),
+            &RequestBudget::default(),
+            &PublicDns,
+        )
+        .await
+        .unwrap();
+        let md = &result.markdown;
+        assert!(md.contains("Release notes"), "got: {md}");
+        assert!(md.contains("Short but important."), "got: {md}");
+        assert!(
+            !md.contains("Archive") && !md.contains("Related posts"),
+            "got: {md}"
+        );
+
+        let err = fetch_page(
+            &client,
+            &url,
+            opts("article.post"),
+            &RequestBudget::default(),
+            &PublicDns,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, FetchError::SelectorNoMatch(ref s) if s == "article.post"));
+        assert!(matches!(
+            parse_selector("##main"),
+            Err(FetchError::InvalidSelector(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn code_only_returns_just_the_code_blocks() {
+        let filler = "Enough prose to look like a real tutorial paragraph. ".repeat(4);
+        let html = format!(
+            r#"<html><head><title>Tutorial</title></head><body><article>
+            <h1>Tutorial</h1>
+            <p>Start by installing the CLI. {filler}</p>
+            <pre><code class="language-bash">npm install -g tool</code></pre>
+            <p>Then write the config file. {filler}</p>
+            <pre><code class="language-json">{{"name": "demo"}}</code></pre>
+            <p>That is all there is to it. {filler}</p>
+            </article></body></html>"#
+        );
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/tutorial"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(html))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+        let url = format!("http://pages.test:{}/tutorial", server.address().port());
+        let opts = FetchOptions {
+            code_only: true,
+            ..Default::default()
+        };
+
+        let result = fetch_page(&client, &url, opts, &RequestBudget::default(), &PublicDns)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.markdown,
+            "---\ntitle: \"Tutorial\"\n---\n\n\
+             ```bash\nnpm install -g tool\n```\n\n\
+             ```json\n{\"name\": \"demo\"}\n```\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn metadata_only_reports_response_headers_and_ssrf_check() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/doc"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(
+                        article_page("Doc", "Body", None),
+                        "text/html; charset=utf-8",
+                    )
+                    .insert_header("last-modified", "Wed, 21 Oct 2026 07:28:00 GMT"),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+        let url = format!("http://pages.test:{}/doc", server.address().port());
+        let opts = FetchOptions {
+            metadata_only: true,
+            ..Default::default()
+        };
+
+        let result = fetch_page(&client, &url, opts, &RequestBudget::default(), &PublicDns)
+            .await
+            .unwrap();
+
+        assert!(
+            result.markdown.contains(
+                "status: 200\n\
+                 content_type: \"text/html; charset=utf-8\"\n\
+                 last_modified: \"Wed, 21 Oct 2026 07:28:00 GMT\"\n\
+                 ssrf_check: \"dns\"\n\
+                 resolved_ips: [\"93.184.216.34\"]\n"
+            ),
+            "got: {}",
+            result.markdown
+        );
+    }
+
+    #[tokio::test]
+    async fn if_modified_since_returns_not_modified_on_304() {
+        let since = "Wed, 21 Oct 2026 07:28:00 GMT";
+        // `matchers::header` splits values on commas, which HTTP dates contain.
+        let sent_since =
+            move |req: &wiremock::Request| req.headers.get("if-modified-since").is_some_and(|v| v == since);
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/unchanged"))
+            .and(sent_since)
+            .respond_with(ResponseTemplate::new(304))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/changed"))
+            .and(sent_since)
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(article_page("Changed", "Fresh content", None), "text/html"),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+        let base = format!("http://pages.test:{}", server.address().port());
+        let opts = FetchOptions {
+            if_modified_since: Some(parse_if_modified_since("2026-10-21T07:28:00Z").unwrap()),
+            ..Default::default()
+        };
+
+        let result = fetch_page(
+            &client,
+            &format!("{base}/unchanged"),
+            opts.clone(),
+            &RequestBudget::default(),
+            &PublicDns,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            result.markdown,
+            format!("---\nstatus: 304\n---\n\nNot modified since {since}.\n")
+        );
+
+        let result = fetch_page(
+            &client,
+            &format!("{base}/changed"),
+            opts,
+            &RequestBudget::default(),
+            &PublicDns,
+        )
+        .await
+        .unwrap();
+        assert!(
+            result.markdown.contains("Fresh content"),
+            "got: {}",
+            result.markdown
+        );
+    }
+
+    #[tokio::test]
+    async fn metadata_only_reports_fetch_time_and_size() {
+        let body = article_page("Doc", "Body", None);
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/slow"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(body.clone(), "text/html")
+                    .set_delay(Duration::from_millis(50)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+        let url = format!("http://pages.test:{}/slow", server.address().port());
+        let opts = FetchOptions {
+            metadata_only: true,
+            ..Default::default()
+        };
+
+        let result = fetch_page(&client, &url, opts, &RequestBudget::default(), &PublicDns)
+            .await
+            .unwrap();
+
+        let field = |key: &str| -> u64 {
+            let prefix = format!("\n{key}: ");
+            let rest = &result.markdown[result.markdown.find(&prefix).unwrap() + prefix.len()..];
+            rest.lines().next().unwrap().parse().unwrap()
+        };
+        let elapsed = field("fetch_time_ms");
+        assert!((50..10_000).contains(&elapsed), "fetch_time_ms: {elapsed}");
+        assert_eq!(field("response_bytes"), body.len() as u64);
+    }
+
+    #[tokio::test]
+    async fn fetch_page_extracts_pdf_text() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/spec.pdf"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "application/pdf")
+                    .set_body_bytes(pdf::tests::sample_pdf(&["Protocol Specification"])),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+        let url = format!("http://pages.test:{}/spec.pdf", server.address().port());
+
+        let result = fetch_page(
+            &client,
+            &url,
+            FetchOptions::default(),
+            &RequestBudget::default(),
+            &PublicDns,
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            result.markdown.starts_with("---\n---\n\n"),
+            "got: {}",
+            result.markdown
+        );
+        assert!(
+            result.markdown.contains("Protocol Specification"),
+            "got: {}",
+            result.markdown
+        );
+        assert!(!result.used_raw_fallback);
+    }
+
+    #[tokio::test]
+    async fn fetch_page_pdf_respects_size_limit() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/big.pdf"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "application/pdf")
+                    .set_body_bytes(pdf::tests::sample_pdf(&["Too big"])),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+        let url = format!("http://pages.test:{}/big.pdf", server.address().port());
+        let opts = FetchOptions {
+            max_bytes: Some(100),
+            ..Default::default()
+        };
+
+        let result = fetch_page(&client, &url, opts, &RequestBudget::default(), &PublicDns).await;
+
+        assert!(
+            matches!(result, Err(FetchError::TooLarge(100))),
+            "got: {result:?}"
+        );
+    }
 }
 
 #[cfg(test)]
@@ -811,6 +2427,112 @@ mod js_dependent_tests {
     }
 }
 
+#[cfg(test)]
+mod challenge_tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const CLOUDFLARE_CHALLENGE: &str = r#"<!DOCTYPE html><html lang="en-US"><head>
+<title>Just a moment...</title>
+<meta http-equiv="refresh" content="390">
+</head><body><div class="main-wrapper" role="main"><div class="main-content">
+<h1 class="zone-name-title h1">example.com</h1>
+<h2 class="h2" id="challenge-running">Checking if the site connection is secure</h2>
+<noscript>Enable JavaScript and cookies to continue</noscript>
+</div></div>
+<script>(function(){window._cf_chl_opt={cvId: '3',cZone: "example.com",cType: 'managed'};
+var a = document.createElement('script');a.src = '/cdn-cgi/challenge-platform/h/g/orchestrate/chl_page/v1';
+document.getElementsByTagName('head')[0].appendChild(a);}());</script></body></html>"#;
+
+    #[test]
+    fn detects_cloudflare_challenge_fixture() {
+        assert!(is_bot_challenge(CLOUDFLARE_CHALLENGE));
+        assert!(is_bot_challenge(
+            "<html><head><TITLE>Attention Required! | Cloudflare</TITLE></head></html>"
+        ));
+    }
+
+    #[test]
+    fn article_about_cloudflare_is_not_a_challenge() {
+        let html = "<html><head><title>How Cloudflare challenges work</title></head>\
+            <body><p>Sites show a \"Just a moment...\" page while checking your browser.</p></body></html>";
+        assert!(!is_bot_challenge(html));
+    }
+
+    #[tokio::test]
+    async fn download_reports_challenge_instead_of_status() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/gated"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .insert_header("server", "cloudflare")
+                    .set_body_string(CLOUDFLARE_CHALLENGE),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/mitigated"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("cf-mitigated", "challenge")
+                    .set_body_string("<html></html>"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/article"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("server", "cloudflare")
+                    .insert_header("content-type", "text/html")
+                    .set_body_string(
+                        "<html><head><title>Release notes</title></head><body>\
+                         <p>Version 2 is out.</p>\
+                         <script src=\"/cdn-cgi/challenge-platform/scripts/jsd/main.js\"></script>\
+                         </body></html>",
+                    ),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/forbidden"))
+            .respond_with(ResponseTemplate::new(403).set_body_string("Forbidden"))
+            .mount(&server)
+            .await;
+
+        let client = Client::new();
+        let get = |p: &str| {
+            let url = format!("{}{p}", server.uri());
+            let client = client.clone();
+            async move {
+                download(
+                    &client,
+                    &url,
+                    &FetchOptions::default(),
+                    &RequestBudget::default(),
+                )
+                .await
+            }
+        };
+
+        assert!(matches!(get("/gated").await, Err(FetchError::BotChallenge)));
+        assert!(matches!(
+            get("/mitigated").await,
+            Err(FetchError::BotChallenge)
+        ));
+        assert!(matches!(
+            get("/article").await,
+            Ok((_, Body::Html(html), _)) if html.contains("Version 2 is out")
+        ));
+        assert!(matches!(
+            get("/forbidden").await,
+            Err(FetchError::Status(403))
+        ));
+    }
+}
+
 #[cfg(test)]
 mod thin_body_tests {
     use super::*;
@@ -852,7 +2574,7 @@ mod thin_body_tests {
     #[test]
     fn whitespace_only_body_is_thin() {
         let html = "<html><body>   \n\t  \n   </body></html>";
-        assert!(has_thin_body(&html));
+        assert!(has_thin_body(html));
     }
 }
 
@@ -866,6 +2588,7 @@ mod thin_extract_tests {
             title: None,
             byline: None,
             published_time: None,
+            image: None,
             content_html: content_html.to_string(),
             used_raw_fallback,
         }
@@ -894,6 +2617,47 @@ mod thin_extract_tests {
         assert!(!is_thin_extract(&article(&content, false)));
     }
 
+    #[test]
+    fn paywall_stub_is_flagged() {
+        let html = r#"<html><head><script type="application/ld+json">
+            {"@type": "NewsArticle", "isAccessibleForFree": false}
+            </script></head><body><article><h1>Markets rally</h1>
+            <p>Stocks rose sharply on Tuesday as investors weighed new data.</p>
+            <div class="paywall-prompt">Subscribe to continue reading.</div>
+            </article></body></html>"#;
+        let teaser = "<p>Stocks rose sharply on Tuesday as investors weighed new data.</p>";
+        assert!(is_paywalled(html, &article(teaser, false)));
+        assert!(declares_not_free(&html.to_ascii_lowercase()));
+
+        let prompt = "<p>Markets rallied.</p><p>Subscribe to continue reading.</p>";
+        let html = format!("<html><body><article>{prompt}</article></body></html>");
+        assert!(is_paywalled(&html, &article(prompt, false)));
+    }
+
+    #[test]
+    fn short_page_with_metering_scripts_is_not_flagged() {
+        let note = "<p>A short but complete note about the release.</p>";
+        let html = format!(
+            r#"<html><head><script>window.analytics = {{"metered": true,
+            "cta": "Subscribe to continue reading", "paywall": false}};</script>
+            <script>var el = '<div class="paywall-overlay">';</script></head>
+            <body><article>{note}</article>
+            <p>Want more? Subscribe to read our weekly newsletter.</p></body></html>"#
+        );
+        assert!(!is_paywalled(&html, &article(note, false)));
+    }
+
+    #[test]
+    fn full_article_mentioning_subscriptions_is_not_flagged() {
+        let body =
+            "<p>Readers who subscribe to continue supporting us get early access.</p>".repeat(30);
+        let html = format!("<html><body><article>{body}</article></body></html>");
+        assert!(!is_paywalled(&html, &article(&body, false)));
+        let short = "<p>A short but complete note about the release.</p>";
+        let html = format!("<html><body><article>{short}</article></body></html>");
+        assert!(!is_paywalled(&html, &article(short, false)));
+    }
+
     #[test]
     fn exactly_at_threshold_is_not_thin() {
         let content = format!("<p>{}</p>", "x".repeat(EXTRACT_TEXT_THRESHOLD));
@@ -0,0 +1,240 @@
+//! Endpoint summaries of OpenAPI 3.x and Swagger 2.0 specs (JSON or YAML).
+
+use std::fmt::Write;
+
+use serde_yaml_ng::Value;
+
+use super::FetchError;
+
+/// Operation keys of a path item, in the order they are listed.
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Group for operations without tags.
+const UNTAGGED: &str = "untagged";
+
+struct Endpoint {
+    method: String,
+    path: String,
+    summary: Option<String>,
+    deprecated: bool,
+}
+
+/// Parse `text` as an OpenAPI/Swagger spec and list its operations by tag.
+///
+/// JSON is valid YAML, so one parser handles both. Operations with several
+/// tags are listed under the first; tags follow the spec's top-level `tags`
+/// order, then order of first use.
+pub(crate) fn summarize(text: &str) -> Result<String, FetchError> {
+    let spec: Value =
+        serde_yaml_ng::from_str(text).map_err(|e| FetchError::NotOpenApi(e.to_string()))?;
+    let version = spec_version(&spec).ok_or_else(|| {
+        FetchError::NotOpenApi("no `openapi: 3.x` or `swagger: \"2.0\"` field".into())
+    })?;
+
+    let mut groups: Vec<(String, Vec<Endpoint>)> = spec
+        .get("tags")
+        .and_then(Value::as_sequence)
+        .into_iter()
+        .flatten()
+        .filter_map(|t| t.get("name").and_then(scalar))
+        .map(|name| (name, Vec::new()))
+        .collect();
+    let mut total = 0;
+    for (path, item) in spec
+        .get("paths")
+        .and_then(Value::as_mapping)
+        .into_iter()
+        .flatten()
+    {
+        let Some(path) = scalar(path) else { continue };
+        for &method in METHODS {
+            let Some(op) = item.get(method) else { continue };
+            let tag = op
+                .get("tags")
+                .and_then(|t| t.get(0))
+                .and_then(scalar)
+                .unwrap_or_else(|| UNTAGGED.to_string());
+            let endpoint = Endpoint {
+                method: method.to_ascii_uppercase(),
+                path: path.clone(),
+                summary: operation_summary(op),
+                deprecated: op.get("deprecated").and_then(Value::as_bool) == Some(true),
+            };
+            match groups.iter_mut().find(|(name, _)| *name == tag) {
+                Some((_, endpoints)) => endpoints.push(endpoint),
+                None => groups.push((tag, vec![endpoint])),
+            }
+            total += 1;
+        }
+    }
+
+    let info = spec.get("info");
+    let title = info
+        .and_then(|i| i.get("title"))
+        .and_then(scalar)
+        .unwrap_or_else(|| "Untitled API".to_string());
+    let mut out = format!("# {title}");
+    if let Some(api_version) = info.and_then(|i| i.get("version")).and_then(scalar) {
+        let _ = write!(out, " ({api_version})");
+    }
+    let _ = write!(out, "\n\n{version} · {total} endpoints");
+    if let Some(base) = base_url(&spec) {
+        let _ = write!(out, " · base: {base}");
+    }
+    out.push('\n');
+
+    for (tag, endpoints) in groups.iter().filter(|(_, e)| !e.is_empty()) {
+        let _ = write!(out, "\n## {tag}\n\n");
+        for e in endpoints {
+            let _ = write!(out, "- `{} {}`", e.method, e.path);
+            if let Some(summary) = &e.summary {
+                let _ = write!(out, " — {summary}");
+            }
+            if e.deprecated {
+                out.push_str(" (deprecated)");
+            }
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+/// "OpenAPI 3.1.0" or "Swagger 2.0"; `None` for anything else.
+fn spec_version(spec: &Value) -> Option<String> {
+    if let Some(v) = spec.get("openapi").and_then(scalar) {
+        return v.starts_with("3.").then(|| format!("OpenAPI {v}"));
+    }
+    // An unquoted `swagger: 2.0` parses as a float.
+    let v = spec.get("swagger").and_then(scalar)?;
+    (v == "2.0" || v == "2").then(|| "Swagger 2.0".to_string())
+}
+
+/// The first server URL (3.x), or scheme + host + basePath (2.0).
+fn base_url(spec: &Value) -> Option<String> {
+    if let Some(url) = spec
+        .get("servers")
+        .and_then(|s| s.get(0))
+        .and_then(|s| s.get("url"))
+        .and_then(scalar)
+    {
+        return Some(url);
+    }
+    let host = spec.get("host").and_then(scalar)?;
+    let scheme = spec
+        .get("schemes")
+        .and_then(|s| s.get(0))
+        .and_then(scalar)
+        .unwrap_or_else(|| "https".to_string());
+    let base_path = spec.get("basePath").and_then(scalar).unwrap_or_default();
+    Some(format!("{scheme}://{host}{base_path}"))
+}
+
+/// `summary`, else `operationId`, else the first line of `description`.
+fn operation_summary(op: &Value) -> Option<String> {
+    ["summary", "operationId", "description"]
+        .iter()
+        .filter_map(|key| op.get(key).and_then(scalar))
+        .filter_map(|text| text.lines().next().map(|l| l.trim().to_string()))
+        .find(|line| !line.is_empty())
+}
+
+/// String form of a scalar value; numbers and booleans are rendered too.
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PETSTORE: &str = r#"
+openapi: 3.0.3
+info:
+  title: Petstore
+  version: 1.0.0
+servers:
+  - url: https://petstore.example/v1
+tags:
+  - name: pets
+  - name: store
+paths:
+  /pets:
+    get:
+      tags: [pets]
+      summary: List all pets
+    post:
+      tags: [pets]
+      operationId: createPet
+  /pets/{petId}:
+    parameters:
+      - name: petId
+        in: path
+    delete:
+      tags: [pets]
+      summary: Delete a pet
+      deprecated: true
+  /health:
+    get:
+      description: |
+        Liveness probe.
+        Always returns 200.
+"#;
+
+    #[test]
+    fn summarizes_openapi3_yaml_by_tag() {
+        assert_eq!(
+            summarize(PETSTORE).unwrap(),
+            "# Petstore (1.0.0)\n\n\
+             OpenAPI 3.0.3 · 4 endpoints · base: https://petstore.example/v1\n\n\
+             ## pets\n\n\
+             - `GET /pets` — List all pets\n\
+             - `POST /pets` — createPet\n\
+             - `DELETE /pets/{petId}` — Delete a pet (deprecated)\n\n\
+             ## untagged\n\n\
+             - `GET /health` — Liveness probe.\n"
+        );
+    }
+
+    #[test]
+    fn summarizes_swagger2_json() {
+        let spec = r#"{
+            "swagger": "2.0",
+            "info": {"title": "Legacy", "version": "2"},
+            "host": "api.example.com",
+            "basePath": "/v2",
+            "schemes": ["https"],
+            "paths": {"/users": {"get": {"tags": ["users"], "summary": "List users"}}}
+        }"#;
+        let out = summarize(spec).unwrap();
+        assert!(
+            out.starts_with(
+                "# Legacy (2)\n\nSwagger 2.0 · 1 endpoints · base: https://api.example.com/v2\n"
+            ),
+            "got:\n{out}"
+        );
+        assert!(out.contains("## users\n\n- `GET /users` — List users\n"));
+    }
+
+    #[test]
+    fn accepts_unquoted_swagger_version() {
+        let out = summarize("swagger: 2.0\ninfo: {title: T, version: '1'}\npaths: {}\n").unwrap();
+        assert!(out.contains("Swagger 2.0 · 0 endpoints"), "got:\n{out}");
+    }
+
+    #[test]
+    fn rejects_documents_that_are_not_specs() {
+        for text in ["name: not-a-spec\n", "openapi: 2.5\n", "<html></html>: ["] {
+            assert!(
+                matches!(summarize(text), Err(FetchError::NotOpenApi(_))),
+                "{text}"
+            );
+        }
+    }
+}
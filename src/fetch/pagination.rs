@@ -0,0 +1,84 @@
+//! "Next page" link discovery for multi-page articles and threads.
+
+use dom_query::Document;
+
+/// Visible anchor texts treated as a "next page" link when no `rel="next"` exists.
+const NEXT_LINK_TEXTS: &[&str] = &["next", "next page", "次へ", "次のページ"];
+
+/// Find the next page URL in `html`, resolved against `page_url`.
+///
+/// Prefers `<link rel="next">`, then `<a rel="next">`, then an anchor whose
+/// text reads "Next". Only returns URLs on the same origin as `page_url`.
+pub(super) fn find_next_url(html: &str, page_url: &str) -> Option<String> {
+    let base = url::Url::parse(page_url).ok()?;
+    let doc = Document::from(html);
+
+    let by_rel = doc
+        .select(r#"link[rel~="next"][href], a[rel~="next"][href]"#)
+        .iter()
+        .filter_map(|sel| sel.attr("href"))
+        .collect::<Vec<_>>();
+    let by_text = doc
+        .select("a[href]")
+        .iter()
+        .filter(|a| is_next_text(&a.text()))
+        .filter_map(|a| a.attr("href"))
+        .collect::<Vec<_>>();
+
+    by_rel
+        .iter()
+        .chain(&by_text)
+        .filter_map(|href| {
+            let mut next = base.join(href.trim()).ok()?;
+            next.set_fragment(None);
+            Some(next)
+        })
+        .find(|next| next.origin() == base.origin() && next.as_str() != base.as_str())
+        .map(String::from)
+}
+
+fn is_next_text(text: &str) -> bool {
+    let normalized = text
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase();
+    NEXT_LINK_TEXTS.contains(&normalized.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = "https://example.com/thread?page=1";
+
+    #[test]
+    fn prefers_link_rel_next() {
+        let html = r#"<html><head><link rel="next" href="?page=2"></head>
+            <body><a href="/other">Next</a></body></html>"#;
+        assert_eq!(
+            find_next_url(html, PAGE).as_deref(),
+            Some("https://example.com/thread?page=2")
+        );
+    }
+
+    #[test]
+    fn falls_back_to_anchor_text() {
+        let html = r#"<body><a href="/a">Prev</a> <a href="/thread/2#top">Next »</a></body>"#;
+        assert_eq!(
+            find_next_url(html, PAGE).as_deref(),
+            Some("https://example.com/thread/2")
+        );
+    }
+
+    #[test]
+    fn ignores_cross_origin_and_self_links() {
+        let html = r#"<head><link rel="next" href="https://evil.example/page2"></head>
+            <body><a href="?page=1">Next</a></body>"#;
+        assert_eq!(find_next_url(html, PAGE), None);
+    }
+
+    #[test]
+    fn returns_none_without_next_link() {
+        let html = r#"<body><a href="/about">About</a></body>"#;
+        assert_eq!(find_next_url(html, PAGE), None);
+    }
+}
@@ -0,0 +1,135 @@
+//! Plain-text extraction for `application/pdf` responses.
+
+use super::FetchError;
+
+/// Extract the text layer of a PDF, normalized for Markdown output.
+///
+/// Parsing runs on the blocking pool: it is CPU-bound, and a panic inside the
+/// parser on a malformed file surfaces as an error instead of taking the
+/// process down. PDFs without a text layer (scans) are reported as errors
+/// rather than returned as an empty page.
+pub(super) async fn extract_pdf_text(bytes: Vec<u8>) -> Result<String, FetchError> {
+    let text = tokio::task::spawn_blocking(move || pdf_extract::extract_text_from_mem(&bytes))
+        .await
+        .map_err(|_| FetchError::Pdf("parser crashed on this file".to_string()))?
+        .map_err(|e| FetchError::Pdf(e.to_string()))?;
+
+    let text = normalize_text(&text);
+    if text.is_empty() {
+        return Err(FetchError::Pdf(
+            "no text layer (scanned or image-only PDF?)".to_string(),
+        ));
+    }
+    Ok(text)
+}
+
+/// Whether extraction lost characters (unmapped glyphs come out as U+FFFD).
+pub(super) fn is_degraded(text: &str) -> bool {
+    text.contains('\u{FFFD}')
+}
+
+/// Trim trailing whitespace, drop form feeds, and collapse runs of blank
+/// lines so page breaks read as paragraph breaks.
+fn normalize_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut blank_run = 0;
+    for line in text.lines() {
+        let line = line.replace('\u{c}', "");
+        let line = line.trim_end();
+        if line.is_empty() {
+            blank_run += 1;
+            continue;
+        }
+        if !out.is_empty() {
+            out.push_str(if blank_run > 0 { "\n\n" } else { "\n" });
+        }
+        out.push_str(line);
+        blank_run = 0;
+    }
+    out
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    /// Build a minimal single-page PDF showing `lines` in Helvetica.
+    pub(in crate::fetch) fn sample_pdf(lines: &[&str]) -> Vec<u8> {
+        let mut content = String::from("BT /F1 12 Tf 72 720 Td 14 TL\n");
+        for line in lines {
+            content.push_str(&format!("({line}) Tj T*\n"));
+        }
+        content.push_str("ET");
+
+        let objects = [
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] \
+             /Resources << /Font << /F1 4 0 R >> >> /Contents 5 0 R >>"
+                .to_string(),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+            format!(
+                "<< /Length {} >>\nstream\n{content}\nendstream",
+                content.len()
+            ),
+        ];
+
+        let mut pdf = String::from("%PDF-1.4\n");
+        let mut offsets = Vec::new();
+        for (i, obj) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.push_str(&format!("{} 0 obj\n{obj}\nendobj\n", i + 1));
+        }
+        let xref = pdf.len();
+        pdf.push_str(&format!(
+            "xref\n0 {}\n0000000000 65535 f \n",
+            objects.len() + 1
+        ));
+        for offset in offsets {
+            pdf.push_str(&format!("{offset:010} 00000 n \n"));
+        }
+        pdf.push_str(&format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            objects.len() + 1
+        ));
+        pdf.into_bytes()
+    }
+
+    #[tokio::test]
+    async fn extracts_text_from_pdf() {
+        let pdf = sample_pdf(&["Hello PDF", "Second line"]);
+        let text = extract_pdf_text(pdf).await.unwrap();
+        assert!(text.contains("Hello PDF"), "got: {text:?}");
+        assert!(text.contains("Second line"), "got: {text:?}");
+        assert!(!is_degraded(&text));
+    }
+
+    #[tokio::test]
+    async fn garbage_bytes_are_a_clear_error() {
+        let err = extract_pdf_text(b"%PDF-1.4 not really".to_vec())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, FetchError::Pdf(_)), "got: {err:?}");
+    }
+
+    #[tokio::test]
+    async fn pdf_without_text_is_an_error() {
+        let err = extract_pdf_text(sample_pdf(&[])).await.unwrap_err();
+        assert!(
+            matches!(err, FetchError::Pdf(ref m) if m.contains("no text layer")),
+            "got: {err:?}"
+        );
+    }
+
+    #[test]
+    fn normalize_collapses_blank_lines_and_form_feeds() {
+        let text = "\n\nTitle  \n\n\n\n\u{c}Body line\nnext\n\n";
+        assert_eq!(normalize_text(text), "Title\n\nBody line\nnext");
+    }
+
+    #[test]
+    fn replacement_chars_mark_degraded_text() {
+        assert!(is_degraded("caf\u{FFFD}"));
+        assert!(!is_degraded("café"));
+    }
+}
@@ -0,0 +1,336 @@
+//! Opt-in robots.txt compliance for `fetch --respect-robots`.
+//!
+//! Missing, unreachable, or malformed robots.txt files allow everything; only
+//! an explicit `Disallow` for scout (or `*`) blocks a fetch.
+
+use std::time::Duration;
+
+use reqwest::Client;
+use tracing::{debug, warn};
+
+use crate::budget::RequestBudget;
+
+use super::ssrf::{redact_url_credentials, ssrf_check, ssrf_recheck};
+use super::{DnsResolver, FetchError};
+
+/// Product token matched against `User-agent:` lines.
+const ROBOTS_AGENT: &str = "scout";
+const ROBOTS_TIMEOUT: Duration = Duration::from_secs(10);
+/// Google's limit; anything past it is ignored.
+const MAX_ROBOTS_BYTES: usize = 500 * 1024;
+
+#[derive(Debug)]
+struct Rule {
+    allow: bool,
+    pattern: String,
+}
+
+/// Allow/Disallow rules that apply to scout on one origin.
+#[derive(Debug, Default)]
+pub(crate) struct RobotsRules {
+    rules: Vec<Rule>,
+}
+
+impl RobotsRules {
+    /// Keep the rules of the group naming scout, or of the `*` group when no
+    /// group does. Unknown directives and malformed lines are skipped.
+    fn parse(text: &str) -> Self {
+        let mut specific = Vec::new();
+        let mut wildcard = Vec::new();
+        let mut agents: Vec<String> = Vec::new();
+        let mut in_rules = false;
+        let mut has_specific_group = false;
+
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim().to_ascii_lowercase().as_str() {
+                "user-agent" => {
+                    // A user-agent line after rules starts a new group.
+                    if in_rules {
+                        agents.clear();
+                        in_rules = false;
+                    }
+                    let agent = value.to_ascii_lowercase();
+                    has_specific_group |= agent.contains(ROBOTS_AGENT);
+                    agents.push(agent);
+                }
+                directive @ ("allow" | "disallow") => {
+                    in_rules = true;
+                    // An empty Disallow allows everything; it adds no rule.
+                    if value.is_empty() {
+                        continue;
+                    }
+                    let rule = || Rule {
+                        allow: directive == "allow",
+                        pattern: value.to_string(),
+                    };
+                    if agents.iter().any(|a| a.contains(ROBOTS_AGENT)) {
+                        specific.push(rule());
+                    }
+                    if agents.iter().any(|a| a == "*") {
+                        wildcard.push(rule());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            rules: if has_specific_group {
+                specific
+            } else {
+                wildcard
+            },
+        }
+    }
+
+    /// The longest matching pattern decides; on a tie, Allow wins.
+    fn allows(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|r| pattern_matches(&r.pattern, path))
+            .max_by_key(|r| (r.pattern.len(), r.allow))
+            .is_none_or(|r| r.allow)
+    }
+}
+
+/// Match a robots.txt path pattern: a prefix match where `*` spans any
+/// characters and a trailing `$` anchors the end.
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(p) => (p, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = path.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    for (i, part) in parts.iter().enumerate() {
+        let last = i == parts.len() - 1;
+        if last && anchored {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    !anchored || rest.is_empty()
+}
+
+/// Fail with [`FetchError::BlockedByRobots`] if `url`'s robots.txt disallows
+/// it for scout. robots.txt is downloaded on every call, taking one request
+/// from `budget`.
+pub(crate) async fn check(
+    client: &Client,
+    url: &str,
+    budget: &RequestBudget,
+    resolver: &impl DnsResolver,
+) -> Result<(), FetchError> {
+    let parsed = url::Url::parse(url)?;
+    let origin = parsed.origin().ascii_serialization();
+    let path = match parsed.query() {
+        Some(q) => format!("{}?{q}", parsed.path()),
+        None => parsed.path().to_string(),
+    };
+
+    let rules = fetch_rules(client, &origin, budget, resolver).await?;
+    if rules.allows(&path) {
+        Ok(())
+    } else {
+        Err(FetchError::BlockedByRobots(path))
+    }
+}
+
+/// Download and parse `<origin>/robots.txt`. Only SSRF violations and a spent
+/// budget are errors; every other failure means "no rules".
+async fn fetch_rules(
+    client: &Client,
+    origin: &str,
+    budget: &RequestBudget,
+    resolver: &impl DnsResolver,
+) -> Result<RobotsRules, FetchError> {
+    let robots_url = format!("{origin}/robots.txt");
+    ssrf_check(&robots_url, resolver).await?;
+    if !budget.try_acquire() {
+        return Err(FetchError::BudgetExhausted);
+    }
+
+    let response = match client
+        .get(&robots_url)
+        .header("User-Agent", crate::USER_AGENT)
+        .timeout(ROBOTS_TIMEOUT)
+        .send()
+        .await
+    {
+        Ok(r) => r,
+        Err(e) => {
+            warn!(url = %redact_url_credentials(&robots_url), error = %e, "robots.txt unreachable, allowing");
+            return Ok(RobotsRules::default());
+        }
+    };
+    let final_url = response.url().to_string();
+    ssrf_recheck(&robots_url, &final_url, resolver).await?;
+
+    if !response.status().is_success() {
+        debug!(url = %redact_url_credentials(&robots_url), status = response.status().as_u16(), "no robots.txt");
+        return Ok(RobotsRules::default());
+    }
+
+    let mut body = Vec::new();
+    let mut response = response;
+    while let Ok(Some(chunk)) = response.chunk().await {
+        body.extend_from_slice(&chunk);
+        if body.len() >= MAX_ROBOTS_BYTES {
+            body.truncate(MAX_ROBOTS_BYTES);
+            break;
+        }
+    }
+    Ok(RobotsRules::parse(&String::from_utf8_lossy(&body)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::PublicDns;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn wildcard_group_applies_without_specific_group() {
+        let rules = RobotsRules::parse("User-agent: *\nDisallow: /private/\n");
+        assert!(!rules.allows("/private/page"));
+        assert!(rules.allows("/public"));
+    }
+
+    #[test]
+    fn specific_group_overrides_wildcard() {
+        let rules = RobotsRules::parse(
+            "User-agent: *\nDisallow: /\n\nUser-agent: Scout\nDisallow: /drafts\n",
+        );
+        assert!(rules.allows("/docs"));
+        assert!(!rules.allows("/drafts/1"));
+    }
+
+    #[test]
+    fn longest_match_wins_and_allow_breaks_ties() {
+        let rules = RobotsRules::parse(
+            "User-agent: *\nDisallow: /docs\nAllow: /docs/public\nAllow: /a\nDisallow: /a\n",
+        );
+        assert!(!rules.allows("/docs/internal"));
+        assert!(rules.allows("/docs/public/x"));
+        assert!(rules.allows("/a"));
+    }
+
+    #[test]
+    fn empty_disallow_and_garbage_allow_everything() {
+        assert!(RobotsRules::parse("User-agent: *\nDisallow:\n").allows("/x"));
+        assert!(RobotsRules::parse("<html>not robots</html>").allows("/x"));
+    }
+
+    #[test]
+    fn grouped_user_agents_share_rules() {
+        let rules = RobotsRules::parse("User-agent: other\nUser-agent: scout\nDisallow: /x\n");
+        assert!(!rules.allows("/x"));
+    }
+
+    #[test]
+    fn patterns_support_wildcards_and_anchors() {
+        assert!(pattern_matches("/*.pdf$", "/files/spec.pdf"));
+        assert!(!pattern_matches("/*.pdf$", "/files/spec.pdf?x=1"));
+        assert!(pattern_matches("/search*q=", "/search?q=rust"));
+        assert!(pattern_matches("/exact$", "/exact"));
+        assert!(!pattern_matches("/exact$", "/exact/more"));
+        assert!(!pattern_matches("/tmp", "/other"));
+    }
+
+    #[tokio::test]
+    async fn check_blocks_disallowed_path_and_rereads_rules_per_call() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/robots.txt"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string("User-agent: *\nDisallow: /private\n"),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+        let client = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+        let base = format!("http://pages.test:{}", server.address().port());
+        let budget = RequestBudget::new(Some(2));
+
+        let err = check(&client, &format!("{base}/private/a"), &budget, &PublicDns)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, FetchError::BlockedByRobots(ref p) if p == "/private/a"),
+            "got: {err:?}"
+        );
+        check(&client, &format!("{base}/public"), &budget, &PublicDns)
+            .await
+            .unwrap();
+        assert_eq!(budget.exhausted_at(), None);
+    }
+
+    #[tokio::test]
+    async fn spent_budget_skips_robots_download() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/robots.txt"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(0)
+            .mount(&server)
+            .await;
+        let client = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+        let url = format!("http://pages.test:{}/anything", server.address().port());
+
+        let err = check(&client, &url, &RequestBudget::new(Some(0)), &PublicDns)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, FetchError::BudgetExhausted), "got: {err:?}");
+    }
+
+    #[tokio::test]
+    async fn missing_robots_allows() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/robots.txt"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        let client = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+        let url = format!("http://pages.test:{}/anything", server.address().port());
+
+        check(&client, &url, &RequestBudget::default(), &PublicDns)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn robots_fetch_is_ssrf_checked() {
+        let err = check(
+            &Client::new(),
+            "http://127.0.0.1:1/page",
+            &RequestBudget::default(),
+            &crate::fetch::TokioDnsResolver,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, FetchError::InternalHost(_)), "got: {err:?}");
+    }
+}
@@ -1,11 +1,12 @@
 use std::env;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use reqwest::Client;
 use tracing::{debug, warn};
 
+use crate::budget::RequestBudget;
 use crate::redacted::Redacted;
-use crate::retry::{is_transient_network, retry_with};
+use crate::retry::{is_transient_network, parse_retry_after, retry_with_hint};
 
 use super::grounding::extract_grounded_result;
 use super::types::{
@@ -22,8 +23,9 @@ pub enum GeminiError {
     #[error("GEMINI_API_KEY not set. Get one at https://aistudio.google.com/apikey")]
     ApiKeyNotSet,
 
+    /// Carries the server's `Retry-After`, when it sent one.
     #[error("API rate limit exceeded. Please retry later.")]
-    RateLimited,
+    RateLimited(Option<Duration>),
 
     #[error("API quota exhausted: {0}")]
     QuotaExhausted(String),
@@ -33,10 +35,22 @@ pub enum GeminiError {
 
     #[error("Network error: {0}")]
     Network(#[from] reqwest::Error),
+
+    #[error("No answer returned: {0}")]
+    NoAnswer(String),
+
+    #[error("request budget for this call is spent (SCOUT_MAX_REQUESTS_PER_CALL)")]
+    BudgetExhausted,
 }
 
 pub trait SearchClient {
-    async fn search(&self, query: &str) -> Result<GroundedResult, GeminiError>;
+    /// Every HTTP attempt, retries and fallback included, takes one request
+    /// from `budget`.
+    async fn search(
+        &self,
+        query: &str,
+        budget: &RequestBudget,
+    ) -> Result<GroundedResult, GeminiError>;
 }
 
 #[derive(Clone)]
@@ -44,6 +58,8 @@ pub struct GeminiClient {
     http: Client,
     api_key: Redacted,
     model: String,
+    /// Tried when `model` is not found (`GEMINI_FALLBACK_MODEL`).
+    fallback_model: Option<String>,
     base_url: String,
 }
 
@@ -53,15 +69,13 @@ impl GeminiClient {
         if api_key.trim().is_empty() {
             return Err(GeminiError::ApiKeyNotSet);
         }
-        let model = env::var("GEMINI_MODEL")
-            .ok()
-            .map(|m| m.trim().to_string())
-            .filter(|m| !m.is_empty())
-            .unwrap_or_else(|| DEFAULT_MODEL.to_string());
+        let model = model_from_env("GEMINI_MODEL").unwrap_or_else(|| DEFAULT_MODEL.to_string());
+        let fallback_model = model_from_env("GEMINI_FALLBACK_MODEL").filter(|m| *m != model);
         Ok(Self {
             http,
             api_key: Redacted::new(api_key),
             model,
+            fallback_model,
             base_url: API_BASE.to_string(),
         })
     }
@@ -72,26 +86,78 @@ impl GeminiClient {
             http,
             api_key: Redacted::new("test-key".to_string()),
             model: DEFAULT_MODEL.to_string(),
+            fallback_model: None,
             base_url: base_url.to_string(),
         }
     }
 
-    async fn generate_with_search(
+    #[cfg(test)]
+    pub(crate) fn with_models(mut self, model: &str, fallback: Option<&str>) -> Self {
+        self.model = model.to_string();
+        self.fallback_model = fallback.map(String::from);
+        self
+    }
+
+    /// [`Self::generate`] with retries. When the model is not found (retired,
+    /// or not offered in this region), the fallback model gets the request.
+    async fn generate_with_fallback(
         &self,
-        query: &str,
+        prompt: &str,
+        tools: &[Tool],
+        timeout: Duration,
+        budget: &RequestBudget,
     ) -> Result<GenerateContentResponse, GeminiError> {
-        let url = format!("{}/{}:generateContent", self.base_url, self.model);
+        let result = self
+            .generate_retrying(&self.model, prompt, tools, timeout, budget)
+            .await;
+        match (&result, &self.fallback_model) {
+            (Err(GeminiError::Api { code: 404, .. }), Some(fallback)) => {
+                warn!(model = %self.model, %fallback, "Gemini model not found, using fallback model");
+                self.generate_retrying(fallback, prompt, tools, timeout, budget)
+                    .await
+            }
+            _ => result,
+        }
+    }
+
+    async fn generate_retrying(
+        &self,
+        model: &str,
+        prompt: &str,
+        tools: &[Tool],
+        timeout: Duration,
+        budget: &RequestBudget,
+    ) -> Result<GenerateContentResponse, GeminiError> {
+        retry_with_hint(
+            || self.generate(model, prompt, tools, timeout, budget),
+            is_retriable,
+            retry_after,
+            || GeminiError::RateLimited(None),
+        )
+        .await
+    }
+
+    async fn generate(
+        &self,
+        model: &str,
+        prompt: &str,
+        tools: &[Tool],
+        timeout: Duration,
+        budget: &RequestBudget,
+    ) -> Result<GenerateContentResponse, GeminiError> {
+        if !budget.try_acquire() {
+            return Err(GeminiError::BudgetExhausted);
+        }
+        let url = format!("{}/{model}:generateContent", self.base_url);
 
         let request = GenerateContentRequest {
             contents: vec![Content {
                 parts: vec![Part {
-                    text: query.to_string(),
+                    text: prompt.to_string(),
                 }],
                 role: None,
             }],
-            tools: vec![Tool {
-                google_search: GoogleSearch {},
-            }],
+            tools: tools.to_vec(),
         };
 
         assert!(
@@ -105,14 +171,19 @@ impl GeminiClient {
             .header("x-goog-api-key", self.api_key.expose())
             .header("User-Agent", crate::USER_AGENT)
             .json(&request)
-            .timeout(REQUEST_TIMEOUT)
+            .timeout(timeout)
             .send()
             .await?;
 
         let status = response.status();
         if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            warn!("Gemini API rate limited");
-            return Err(GeminiError::RateLimited);
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| parse_retry_after(v, SystemTime::now()));
+            warn!(?retry_after, "Gemini API rate limited");
+            return Err(GeminiError::RateLimited(retry_after));
         }
         if !status.is_success() {
             let text = response
@@ -135,7 +206,7 @@ impl GeminiClient {
         }
 
         let body: GenerateContentResponse = response.json().await?;
-        debug!(model = %self.model, "gemini search complete");
+        debug!(model, "gemini request complete");
 
         if let Some(err) = &body.error {
             let classified = classify_api_error(err);
@@ -145,24 +216,65 @@ impl GeminiClient {
 
         Ok(body)
     }
+
+    /// Ungrounded generation returning the answer text. Empty answers
+    /// (blocked prompts, safety filters) are errors.
+    pub(crate) async fn complete(
+        &self,
+        prompt: &str,
+        timeout: Duration,
+        budget: &RequestBudget,
+    ) -> Result<String, GeminiError> {
+        let response = self
+            .generate_with_fallback(prompt, &[], timeout, budget)
+            .await?;
+        let text: String = response
+            .candidates
+            .iter()
+            .flatten()
+            .take(1)
+            .filter_map(|c| c.content.as_ref())
+            .flat_map(|content| &content.parts)
+            .map(|part| part.text.as_str())
+            .collect();
+        if text.trim().is_empty() {
+            let cause = response
+                .prompt_feedback
+                .and_then(|f| f.block_reason)
+                .map_or_else(|| "empty response".to_string(), |r| format!("blocked: {r}"));
+            return Err(GeminiError::NoAnswer(cause));
+        }
+        Ok(text)
+    }
 }
 
 impl SearchClient for GeminiClient {
-    async fn search(&self, query: &str) -> Result<GroundedResult, GeminiError> {
-        let response = retry_with(
-            || self.generate_with_search(query),
-            is_retriable,
-            || GeminiError::RateLimited,
-        )
-        .await?;
+    async fn search(
+        &self,
+        query: &str,
+        budget: &RequestBudget,
+    ) -> Result<GroundedResult, GeminiError> {
+        let tools = [Tool {
+            google_search: GoogleSearch {},
+        }];
+        let response = self
+            .generate_with_fallback(query, &tools, REQUEST_TIMEOUT, budget)
+            .await?;
         Ok(extract_grounded_result(&response))
     }
 }
 
+fn model_from_env(var: &str) -> Option<String> {
+    env::var(var)
+        .ok()
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+}
+
 fn is_retriable(e: &GeminiError) -> bool {
     matches!(
         e,
-        GeminiError::RateLimited
+        GeminiError::RateLimited(_)
             | GeminiError::Api {
                 code: 500..=599,
                 ..
@@ -170,6 +282,13 @@ fn is_retriable(e: &GeminiError) -> bool {
     ) || matches!(e, GeminiError::Network(e) if is_transient_network(e))
 }
 
+fn retry_after(e: &GeminiError) -> Option<Duration> {
+    match e {
+        GeminiError::RateLimited(wait) => *wait,
+        _ => None,
+    }
+}
+
 fn classify_api_error(err: &ApiError) -> GeminiError {
     let message = err
         .message
@@ -177,7 +296,7 @@ fn classify_api_error(err: &ApiError) -> GeminiError {
         .unwrap_or_else(|| "Unknown error".to_string());
 
     match err.code {
-        Some(429) => GeminiError::RateLimited,
+        Some(429) => GeminiError::RateLimited(None),
         Some(403) => GeminiError::QuotaExhausted(message),
         Some(code) => GeminiError::Api { code, message },
         None => GeminiError::Api {
@@ -197,7 +316,10 @@ mod tests {
             code: Some(429),
             message: Some("Resource exhausted".into()),
         };
-        assert!(matches!(classify_api_error(&err), GeminiError::RateLimited));
+        assert!(matches!(
+            classify_api_error(&err),
+            GeminiError::RateLimited(None)
+        ));
     }
 
     #[test]
@@ -259,7 +381,10 @@ mod http_tests {
             .await;
 
         let client = GeminiClient::with_base_url(Client::new(), &server.uri());
-        let result = client.search("test query").await.unwrap();
+        let result = client
+            .search("test query", &RequestBudget::default())
+            .await
+            .unwrap();
 
         assert_eq!(result.answer.as_deref(), Some("Test answer"));
         assert_eq!(result.sources.len(), 1);
@@ -276,8 +401,140 @@ mod http_tests {
             .await;
 
         let client = GeminiClient::with_base_url(Client::new(), &server.uri());
-        let result = client.search("test").await;
-        assert!(matches!(result, Err(GeminiError::RateLimited)));
+        let result = client.search("test", &RequestBudget::default()).await;
+        assert!(matches!(result, Err(GeminiError::RateLimited(None))));
+    }
+
+    fn grounded_ok() -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "candidates": [{"content": {"parts": [{"text": "Answer"}], "role": "model"}}]
+        }))
+    }
+
+    #[tokio::test]
+    async fn search_429_waits_for_retry_after_instead_of_backoff() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(grounded_ok())
+            .mount(&server)
+            .await;
+
+        let client = GeminiClient::with_base_url(Client::new(), &server.uri());
+        let started = std::time::Instant::now();
+        let result = client
+            .search("test", &RequestBudget::default())
+            .await
+            .unwrap();
+
+        assert_eq!(result.answer.as_deref(), Some("Answer"));
+        // The computed backoff never sleeps less than 500ms.
+        assert!(
+            started.elapsed() < Duration::from_millis(400),
+            "took {:?}",
+            started.elapsed()
+        );
+    }
+
+    #[tokio::test]
+    async fn search_429_with_long_retry_after_fails_without_waiting() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "3600"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = GeminiClient::with_base_url(Client::new(), &server.uri());
+        let result = client.search("test", &RequestBudget::default()).await;
+        assert!(
+            matches!(result, Err(GeminiError::RateLimited(Some(d))) if d.as_secs() == 3600),
+            "got: {result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn search_falls_back_when_model_is_not_found() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"/retired-model:generateContent$"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "error": {
+                    "code": 404,
+                    "message": "models/retired-model is not found for API version v1beta"
+                }
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"/gemini-2.5-flash:generateContent$"))
+            .respond_with(grounded_ok())
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let with_fallback = GeminiClient::with_base_url(Client::new(), &server.uri())
+            .with_models("retired-model", Some(DEFAULT_MODEL));
+        let result = with_fallback
+            .search("test", &RequestBudget::default())
+            .await
+            .unwrap();
+        assert_eq!(result.answer.as_deref(), Some("Answer"));
+
+        let without = GeminiClient::with_base_url(Client::new(), &server.uri())
+            .with_models("retired-model", None);
+        let result = without.search("test", &RequestBudget::default()).await;
+        assert!(
+            matches!(result, Err(GeminiError::Api { code: 404, .. })),
+            "got: {result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn search_spends_budget_on_retries_and_fallback() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"/busy-model:generateContent$"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"/retired-model:generateContent$"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"/gemini-2.5-flash:generateContent$"))
+            .respond_with(grounded_ok())
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let budget = RequestBudget::new(Some(2));
+        let busy = GeminiClient::with_base_url(Client::new(), &server.uri())
+            .with_models("busy-model", None);
+        let result = busy.search("test", &budget).await;
+        assert!(
+            matches!(result, Err(GeminiError::BudgetExhausted)),
+            "got: {result:?}"
+        );
+        assert_eq!(budget.exhausted_at(), Some(2));
+
+        let budget = RequestBudget::new(Some(1));
+        let retired = GeminiClient::with_base_url(Client::new(), &server.uri())
+            .with_models("retired-model", Some(DEFAULT_MODEL));
+        let result = retired.search("test", &budget).await;
+        assert!(
+            matches!(result, Err(GeminiError::BudgetExhausted)),
+            "got: {result:?}"
+        );
     }
 
     #[tokio::test]
@@ -295,7 +552,7 @@ mod http_tests {
             .await;
 
         let client = GeminiClient::with_base_url(Client::new(), &server.uri());
-        let result = client.search("test").await;
+        let result = client.search("test", &RequestBudget::default()).await;
         match &result {
             Err(GeminiError::Api { code: 500, message }) => {
                 assert!(message.contains("Internal server error"));
@@ -314,7 +571,7 @@ mod http_tests {
             .await;
 
         let client = GeminiClient::with_base_url(Client::new(), &server.uri());
-        let result = client.search("test").await;
+        let result = client.search("test", &RequestBudget::default()).await;
         match &result {
             Err(GeminiError::Api { code: 500, message }) => {
                 assert!(message.contains("not json"), "expected body snippet in error, got: {message}");
@@ -338,7 +595,7 @@ mod http_tests {
             .await;
 
         let client = GeminiClient::with_base_url(Client::new(), &server.uri());
-        let result = client.search("test").await;
+        let result = client.search("test", &RequestBudget::default()).await;
         assert!(matches!(result, Err(GeminiError::QuotaExhausted(_))));
     }
 }
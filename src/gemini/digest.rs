@@ -0,0 +1,63 @@
+//! Decision-log digests of issue threads for `repo-issue-digest`.
+
+use std::time::Duration;
+
+use super::client::{GeminiClient, GeminiError};
+use crate::budget::RequestBudget;
+use crate::markdown::truncate_with_note;
+
+/// Long threads are cut here; the opening post and early discussion matter most.
+const MAX_THREAD_BYTES: usize = 200_000;
+const DIGEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Headings every digest is asked to use, in order.
+pub(crate) const DIGEST_SECTIONS: [&str; 4] = [
+    "Problem",
+    "Proposed solutions",
+    "Decisions",
+    "Open questions",
+];
+
+/// Summarize an issue thread (Markdown, oldest post first) into a structured
+/// digest with one `##` section per entry in [`DIGEST_SECTIONS`].
+pub(crate) async fn digest_thread(
+    client: &GeminiClient,
+    title: &str,
+    thread: &str,
+    budget: &RequestBudget,
+) -> Result<String, GeminiError> {
+    let thread = truncate_with_note(thread, MAX_THREAD_BYTES);
+    let reply = client
+        .complete(&digest_prompt(title, &thread), DIGEST_TIMEOUT, budget)
+        .await?;
+    Ok(reply.trim().to_string())
+}
+
+fn digest_prompt(title: &str, thread: &str) -> String {
+    let sections: Vec<String> = DIGEST_SECTIONS.iter().map(|s| format!("## {s}")).collect();
+    format!(
+        "Below is a GitHub issue thread titled \"{title}\", oldest post first. \
+         Write a concise decision log of it in Markdown using exactly these \
+         headings, in order: {}. Under each heading use short bullet points and \
+         name who proposed or decided what (by @handle) where the thread says so. \
+         Write \"- None.\" under a heading with nothing to report. Reply with the \
+         digest only.\n\n{thread}",
+        sections.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prompt_lists_every_section_in_order() {
+        let prompt = digest_prompt("Crash on start", "## Opening post\n\nIt crashes.");
+        let positions: Vec<usize> = DIGEST_SECTIONS
+            .iter()
+            .map(|s| prompt.find(&format!("## {s}")).unwrap())
+            .collect();
+        assert!(positions.is_sorted(), "got: {prompt}");
+        assert!(prompt.ends_with("It crashes."));
+    }
+}
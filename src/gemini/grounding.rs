@@ -1,6 +1,7 @@
 use tracing::warn;
 
-use super::types::{GenerateContentResponse, GroundedResult, Source};
+use super::client::GeminiError;
+use super::types::{Citation, GenerateContentResponse, GroundedResult, GroundingMetadata, Source};
 
 pub fn extract_grounded_result(response: &GenerateContentResponse) -> GroundedResult {
     let candidate = response.candidates.as_ref().and_then(|c| c.first());
@@ -11,7 +12,15 @@ pub fn extract_grounded_result(response: &GenerateContentResponse) -> GroundedRe
         .map(|part| part.text.clone())
         .filter(|text| !text.is_empty());
 
-    if answer.is_none() {
+    let block_reason = response
+        .prompt_feedback
+        .as_ref()
+        .and_then(|f| f.block_reason.clone())
+        .filter(|r| !r.is_empty());
+
+    if let Some(reason) = &block_reason {
+        warn!(%reason, "Gemini blocked the query");
+    } else if answer.is_none() {
         warn!("Gemini returned empty answer (safety filter or empty response)");
     }
 
@@ -34,7 +43,101 @@ pub fn extract_grounded_result(response: &GenerateContentResponse) -> GroundedRe
         })
         .unwrap_or_default();
 
-    GroundedResult { answer, sources }
+    let citations = metadata.map(extract_citations).unwrap_or_default();
+
+    GroundedResult {
+        answer,
+        sources,
+        block_reason,
+        citations,
+    }
+}
+
+/// Map each grounding support to the URLs of the chunks it cites. Supports
+/// without a span end or any web chunk are dropped.
+fn extract_citations(metadata: &GroundingMetadata) -> Vec<Citation> {
+    let chunk_urls: Vec<Option<&str>> = metadata
+        .grounding_chunks
+        .iter()
+        .flatten()
+        .map(|chunk| {
+            chunk
+                .web
+                .as_ref()
+                .and_then(|web| web.uri.as_deref())
+                .filter(|u| !u.is_empty())
+        })
+        .collect();
+    metadata
+        .grounding_supports
+        .iter()
+        .flatten()
+        .filter_map(|support| {
+            let end = support.segment.as_ref()?.end_index?;
+            let urls: Vec<String> = support
+                .grounding_chunk_indices
+                .iter()
+                .flatten()
+                .filter_map(|&i| chunk_urls.get(i).copied().flatten())
+                .map(String::from)
+                .collect();
+            (!urls.is_empty()).then_some(Citation { end, urls })
+        })
+        .collect()
+}
+
+/// Placeholder shown in place of an answer when Gemini returned none.
+pub(crate) fn no_answer_note(result: &GroundedResult) -> String {
+    format!("(No answer returned — {}.)", no_answer_cause(result))
+}
+
+fn no_answer_cause(result: &GroundedResult) -> String {
+    match &result.block_reason {
+        Some(reason) => format!("query blocked: {reason}"),
+        None => "the query may have been filtered by safety settings".into(),
+    }
+}
+
+/// How a query Gemini gave no answer for is reported. `SCOUT_EMPTY_ANSWER=error`
+/// fails the command instead of printing [`no_answer_note`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EmptyAnswerMode {
+    #[default]
+    Placeholder,
+    Error,
+}
+
+impl EmptyAnswerMode {
+    pub fn from_env() -> Self {
+        Self::from_env_with(|var| std::env::var(var).ok())
+    }
+
+    fn from_env_with(env_reader: impl Fn(&str) -> Option<String>) -> Self {
+        match env_reader("SCOUT_EMPTY_ANSWER").as_deref().map(str::trim) {
+            Some(v) if v.eq_ignore_ascii_case("error") => Self::Error,
+            _ => Self::Placeholder,
+        }
+    }
+
+    /// The answer text, or the placeholder / error when there is none.
+    pub(crate) fn answer(self, result: &GroundedResult) -> Result<String, GeminiError> {
+        match (&result.answer, self) {
+            (Some(answer), _) => Ok(answer.clone()),
+            (None, Self::Placeholder) => Ok(no_answer_note(result)),
+            (None, Self::Error) => Err(GeminiError::NoAnswer(no_answer_cause(result))),
+        }
+    }
+
+    /// In error mode, fail when none of `results` has an answer.
+    pub(crate) fn ensure_any_answer(self, results: &[GroundedResult]) -> Result<(), GeminiError> {
+        if self == Self::Placeholder || results.iter().any(|r| r.answer.is_some()) {
+            return Ok(());
+        }
+        match results.first() {
+            Some(first) => Err(GeminiError::NoAnswer(no_answer_cause(first))),
+            None => Ok(()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -53,8 +156,10 @@ mod tests {
                 }),
                 grounding_metadata: Some(GroundingMetadata {
                     grounding_chunks: Some(chunks),
+                    grounding_supports: None,
                 }),
             }]),
+            prompt_feedback: None,
             error: None,
         }
     }
@@ -111,6 +216,7 @@ mod tests {
     fn handles_empty_response() {
         let response = GenerateContentResponse {
             candidates: None,
+            prompt_feedback: None,
             error: None,
         };
 
@@ -132,6 +238,7 @@ mod tests {
                 }),
                 grounding_metadata: None,
             }]),
+            prompt_feedback: None,
             error: None,
         };
 
@@ -173,4 +280,127 @@ mod tests {
         assert_eq!(result.sources.len(), 1);
         assert_eq!(result.sources[0].url, "https://valid.com");
     }
+
+    #[test]
+    fn maps_grounding_supports_to_chunk_urls() {
+        let response: GenerateContentResponse = serde_json::from_str(
+            r#"{"candidates": [{
+                "content": {"parts": [{"text": "Fact one. Fact two."}]},
+                "groundingMetadata": {
+                    "groundingChunks": [
+                        {"web": {"uri": "https://a.com", "title": "A"}},
+                        {"web": {"title": "No URI"}},
+                        {"web": {"uri": "https://b.com", "title": "B"}}
+                    ],
+                    "groundingSupports": [
+                        {"segment": {"endIndex": 9}, "groundingChunkIndices": [0]},
+                        {"segment": {"startIndex": 10, "endIndex": 19}, "groundingChunkIndices": [2, 1, 7]},
+                        {"segment": {"endIndex": 19}, "groundingChunkIndices": [1]}
+                    ]
+                }
+            }]}"#,
+        )
+        .unwrap();
+
+        let result = extract_grounded_result(&response);
+
+        let citations: Vec<_> = result
+            .citations
+            .iter()
+            .map(|c| (c.end, c.urls.clone()))
+            .collect();
+        assert_eq!(
+            citations,
+            vec![
+                (9, vec!["https://a.com".to_string()]),
+                (19, vec!["https://b.com".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn surfaces_prompt_block_reason() {
+        let response: GenerateContentResponse = serde_json::from_str(
+            r#"{"promptFeedback": {"blockReason": "SAFETY", "safetyRatings": []}}"#,
+        )
+        .unwrap();
+
+        let result = extract_grounded_result(&response);
+
+        assert!(result.answer.is_none());
+        assert_eq!(result.block_reason.as_deref(), Some("SAFETY"));
+        assert!(no_answer_note(&result).contains("query blocked: SAFETY"));
+    }
+
+    fn unanswered(block_reason: Option<&str>) -> GroundedResult {
+        GroundedResult {
+            answer: None,
+            sources: vec![],
+            block_reason: block_reason.map(String::from),
+            citations: vec![],
+        }
+    }
+
+    #[test]
+    fn empty_answer_mode_reads_env() {
+        let mode = |v: &'static str| EmptyAnswerMode::from_env_with(|_| Some(v.to_string()));
+        assert_eq!(mode(" Error "), EmptyAnswerMode::Error);
+        assert_eq!(mode("placeholder"), EmptyAnswerMode::Placeholder);
+        assert_eq!(
+            EmptyAnswerMode::from_env_with(|_| None),
+            EmptyAnswerMode::Placeholder
+        );
+    }
+
+    #[test]
+    fn placeholder_mode_returns_note() {
+        let text = EmptyAnswerMode::Placeholder
+            .answer(&unanswered(None))
+            .unwrap();
+        assert_eq!(
+            text,
+            "(No answer returned — the query may have been filtered by safety settings.)"
+        );
+    }
+
+    #[test]
+    fn error_mode_returns_no_answer_error() {
+        let err = EmptyAnswerMode::Error
+            .answer(&unanswered(Some("SAFETY")))
+            .unwrap_err();
+        assert!(matches!(err, GeminiError::NoAnswer(_)));
+        assert_eq!(err.to_string(), "No answer returned: query blocked: SAFETY");
+
+        let mut answered = unanswered(None);
+        answered.answer = Some("ok".into());
+        assert_eq!(EmptyAnswerMode::Error.answer(&answered).unwrap(), "ok");
+    }
+
+    #[test]
+    fn error_mode_requires_at_least_one_answer() {
+        let mut answered = unanswered(None);
+        answered.answer = Some("ok".into());
+        let mode = EmptyAnswerMode::Error;
+        assert!(
+            mode.ensure_any_answer(&[unanswered(None), answered])
+                .is_ok()
+        );
+        assert!(mode.ensure_any_answer(&[unanswered(None)]).is_err());
+        assert!(
+            EmptyAnswerMode::Placeholder
+                .ensure_any_answer(&[unanswered(None)])
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn no_answer_note_without_block_reason_keeps_generic_text() {
+        let result = extract_grounded_result(&GenerateContentResponse {
+            candidates: None,
+            prompt_feedback: None,
+            error: None,
+        });
+
+        assert!(no_answer_note(&result).contains("filtered by safety settings"));
+    }
 }
@@ -1,5 +1,7 @@
 //! Gemini API client with Google Search grounding.
 
 pub(crate) mod client;
+pub(crate) mod digest;
 pub(crate) mod grounding;
+pub(crate) mod translate;
 pub(crate) mod types;
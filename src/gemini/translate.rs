@@ -0,0 +1,151 @@
+//! Markdown translation for `fetch --translate-to`.
+
+use std::time::Duration;
+
+use futures::stream::{self, StreamExt, TryStreamExt};
+use tracing::debug;
+
+use super::client::{GeminiClient, GeminiError};
+use crate::budget::RequestBudget;
+use crate::markdown::split_chunks;
+
+/// Source text per request; the translation must fit in one response.
+const TRANSLATE_CHUNK_CHARS: usize = 8_000;
+/// A full chunk takes far longer to produce than a search answer.
+const TRANSLATE_TIMEOUT: Duration = Duration::from_secs(60);
+const TRANSLATE_CONCURRENCY: usize = 3;
+const SOURCE_LANGUAGE_PREFIX: &str = "SOURCE_LANGUAGE:";
+
+/// Translated Markdown and the source language Gemini detected, if any.
+#[derive(Debug)]
+pub(crate) struct Translation {
+    pub(crate) markdown: String,
+    pub(crate) source_language: Option<String>,
+}
+
+/// Translate `markdown` into `target` (an English language name), splitting
+/// long pages into chunks that are translated in order and rejoined.
+pub(crate) async fn translate_markdown(
+    client: &GeminiClient,
+    markdown: &str,
+    target: &str,
+    budget: &RequestBudget,
+) -> Result<Translation, GeminiError> {
+    let chunks = split_chunks(markdown, TRANSLATE_CHUNK_CHARS);
+    debug!(chunks = chunks.len(), target, "translating page");
+
+    let replies: Vec<String> = stream::iter(chunks)
+        .map(|chunk| async move {
+            client
+                .complete(&translate_prompt(&chunk, target), TRANSLATE_TIMEOUT, budget)
+                .await
+        })
+        .buffered(TRANSLATE_CONCURRENCY)
+        .try_collect()
+        .await?;
+
+    let mut source_language = None;
+    let mut pieces = Vec::with_capacity(replies.len());
+    for reply in &replies {
+        let (language, body) = split_source_language(reply);
+        source_language = source_language.or(language);
+        pieces.push(body.trim());
+    }
+    Ok(Translation {
+        markdown: pieces.join("\n\n"),
+        source_language,
+    })
+}
+
+fn translate_prompt(chunk: &str, target: &str) -> String {
+    format!(
+        "Translate the Markdown below into {target}. Keep the Markdown structure, \
+         links, URLs, and code blocks unchanged; translate only the prose. \
+         Start your reply with one line `{SOURCE_LANGUAGE_PREFIX} <English name of \
+         the original language>`, then give the translation and nothing else.\n\n\
+         {chunk}"
+    )
+}
+
+/// Split the leading `SOURCE_LANGUAGE:` line off a reply.
+fn split_source_language(reply: &str) -> (Option<String>, &str) {
+    let reply = reply.trim_start();
+    let (first, rest) = reply.split_once('\n').unwrap_or((reply, ""));
+    match first.trim().strip_prefix(SOURCE_LANGUAGE_PREFIX) {
+        Some(language) => {
+            let language = language.trim().trim_matches('`').trim();
+            ((!language.is_empty()).then(|| language.to_string()), rest)
+        }
+        None => (None, reply),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::Client;
+    use wiremock::matchers::{body_string_contains, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn source_language_line_is_parsed_and_removed() {
+        assert_eq!(
+            split_source_language("SOURCE_LANGUAGE: German\n# Titel\n"),
+            (Some("German".to_string()), "# Titel\n")
+        );
+        assert_eq!(split_source_language("# Just text"), (None, "# Just text"));
+    }
+
+    fn reply(text: &str) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "candidates": [{"content": {"parts": [{"text": text}], "role": "model"}}]
+        }))
+    }
+
+    #[tokio::test]
+    async fn long_pages_are_translated_in_chunks_and_rejoined_in_order() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("Erster"))
+            .respond_with(reply("SOURCE_LANGUAGE: German\nFirst part"))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("Zweiter"))
+            .respond_with(reply("SOURCE_LANGUAGE: German\nSecond part"))
+            .mount(&server)
+            .await;
+
+        let first = format!("Erster {}", "x".repeat(TRANSLATE_CHUNK_CHARS - 10));
+        let markdown = format!("{first}\n\nZweiter Teil");
+        let client = GeminiClient::with_base_url(Client::new(), &server.uri());
+
+        let translation =
+            translate_markdown(&client, &markdown, "English", &RequestBudget::default())
+                .await
+                .unwrap();
+
+        assert_eq!(translation.markdown, "First part\n\nSecond part");
+        assert_eq!(translation.source_language.as_deref(), Some("German"));
+    }
+
+    #[tokio::test]
+    async fn empty_translation_is_an_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "promptFeedback": {"blockReason": "SAFETY"}
+            })))
+            .mount(&server)
+            .await;
+        let client = GeminiClient::with_base_url(Client::new(), &server.uri());
+
+        let err = translate_markdown(&client, "Text", "Japanese", &RequestBudget::default())
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, GeminiError::NoAnswer(ref m) if m.contains("SAFETY")),
+            "got: {err:?}"
+        );
+    }
+}
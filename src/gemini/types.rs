@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "camelCase")]
 pub(crate) struct GenerateContentRequest {
     pub(crate) contents: Vec<Content>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub(crate) tools: Vec<Tool>,
 }
 
@@ -19,20 +20,29 @@ pub(crate) struct Part {
     pub(crate) text: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub(crate) struct Tool {
     pub(crate) google_search: GoogleSearch,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub(crate) struct GoogleSearch {}
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub(crate) struct GenerateContentResponse {
     pub(crate) candidates: Option<Vec<Candidate>>,
+    pub(crate) prompt_feedback: Option<PromptFeedback>,
     pub(crate) error: Option<ApiError>,
 }
 
+/// Present when the prompt itself was blocked (no candidates are returned).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PromptFeedback {
+    pub(crate) block_reason: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct Candidate {
@@ -44,6 +54,22 @@ pub(crate) struct Candidate {
 #[serde(rename_all = "camelCase")]
 pub(crate) struct GroundingMetadata {
     pub(crate) grounding_chunks: Option<Vec<GroundingChunk>>,
+    pub(crate) grounding_supports: Option<Vec<GroundingSupport>>,
+}
+
+/// A span of the answer and the grounding chunks that back it.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GroundingSupport {
+    pub(crate) segment: Option<Segment>,
+    pub(crate) grounding_chunk_indices: Option<Vec<usize>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Segment {
+    /// Byte offset in the answer text where the span ends.
+    pub(crate) end_index: Option<usize>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -68,6 +94,17 @@ pub(crate) struct ApiError {
 pub(crate) struct GroundedResult {
     pub(crate) answer: Option<String>,
     pub(crate) sources: Vec<Source>,
+    /// `promptFeedback.blockReason` when Gemini refused the query outright.
+    pub(crate) block_reason: Option<String>,
+    /// Which sources back which parts of the answer, when Gemini says.
+    pub(crate) citations: Vec<Citation>,
+}
+
+/// Sources backing the answer text up to byte offset `end`.
+#[derive(Debug, Clone)]
+pub(crate) struct Citation {
+    pub(crate) end: usize,
+    pub(crate) urls: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,187 @@
+//! Timestamp rendering for GitHub output: ISO dates as the API returns them,
+//! or "3 days ago" when `SCOUT_RELATIVE_DATES` is set.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::env::parse_bool_env;
+
+/// Relative forms stop here; older timestamps are clearer as dates.
+const MAX_RELATIVE_AGE: Duration = Duration::from_secs(365 * 86_400);
+
+/// Whether timestamps are shown as dates or relative to now.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum DateStyle {
+    #[default]
+    Absolute,
+    Relative,
+}
+
+impl DateStyle {
+    pub(crate) fn from_env() -> Self {
+        Self::from_env_with(|var| std::env::var(var).ok())
+    }
+
+    fn from_env_with(env_reader: impl Fn(&str) -> Option<String>) -> Self {
+        match parse_bool_env(env_reader, "SCOUT_RELATIVE_DATES") {
+            Some(true) => Self::Relative,
+            _ => Self::Absolute,
+        }
+    }
+
+    /// A formatter for one response, with "now" fixed so every timestamp in
+    /// it is measured from the same instant.
+    pub(crate) fn formatter(self) -> DateFormat {
+        match self {
+            Self::Absolute => DateFormat::Absolute,
+            Self::Relative => DateFormat::RelativeTo(SystemTime::now()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum DateFormat {
+    Absolute,
+    RelativeTo(SystemTime),
+}
+
+impl DateFormat {
+    /// A full timestamp: unchanged, or relative when recent enough.
+    pub(crate) fn format_date(&self, iso: &str) -> String {
+        self.relative(iso).unwrap_or_else(|| iso.to_string())
+    }
+
+    /// Like [`Self::format_date`], but the absolute form is just the day.
+    pub(crate) fn format_day(&self, iso: &str) -> String {
+        self.relative(iso)
+            .unwrap_or_else(|| iso.get(..10).unwrap_or(iso).to_string())
+    }
+
+    fn relative(&self, iso: &str) -> Option<String> {
+        let Self::RelativeTo(now) = self else {
+            return None;
+        };
+        let age = now.duration_since(parse_date(iso)?).ok()?;
+        if age >= MAX_RELATIVE_AGE {
+            return None;
+        }
+        let (count, unit) = match age.as_secs() {
+            s if s < 60 => return Some("just now".into()),
+            s if s < 3_600 => (s / 60, "minute"),
+            s if s < 86_400 => (s / 3_600, "hour"),
+            s if s < 30 * 86_400 => (s / 86_400, "day"),
+            s => (s / (30 * 86_400), "month"),
+        };
+        let plural = if count == 1 { "" } else { "s" };
+        Some(format!("{count} {unit}{plural} ago"))
+    }
+}
+
+/// Parse a GitHub timestamp (`2026-01-15T10:00:00Z`, optionally with
+/// fractional seconds) or a bare `2026-01-15` date, in UTC.
+pub(crate) fn parse_date(iso: &str) -> Option<SystemTime> {
+    let (date, time) = match iso.split_once('T') {
+        Some((date, time)) => (date, Some(time.strip_suffix('Z')?)),
+        None => (iso, None),
+    };
+    let mut ymd = date.splitn(3, '-').map(|p| p.parse::<u32>().ok());
+    let (year, month, day) = (ymd.next()??, ymd.next()??, ymd.next()??);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || year < 1970 {
+        return None;
+    }
+
+    let mut secs = days_from_civil(year, month, day) * 86_400;
+    if let Some(time) = time {
+        let time = time.split_once('.').map_or(time, |(whole, _)| whole);
+        let mut hms = time.splitn(3, ':').map(|p| p.parse::<u64>().ok());
+        let (h, m, s) = (hms.next()??, hms.next()??, hms.next()??);
+        if h > 23 || m > 59 || s > 60 {
+            return None;
+        }
+        secs += h * 3_600 + m * 60 + s;
+    }
+    Some(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date (Howard Hinnant's
+/// `days_from_civil`, restricted to dates after the epoch).
+fn days_from_civil(year: u32, month: u32, day: u32) -> u64 {
+    let y = u64::from(if month <= 2 { year - 1 } else { year });
+    let era = y / 400;
+    let yoe = y - era * 400;
+    let m = u64::from(month);
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + u64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(iso: &str) -> DateFormat {
+        DateFormat::RelativeTo(parse_date(iso).unwrap())
+    }
+
+    #[test]
+    fn parse_date_matches_unix_time() {
+        let secs = |iso| {
+            parse_date(iso)
+                .unwrap()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+        };
+        assert_eq!(secs("1970-01-01T00:00:00Z"), 0);
+        assert_eq!(secs("2024-02-29T12:34:56Z"), 1_709_210_096);
+        assert_eq!(secs("2026-01-15T10:00:00.123Z"), 1_768_471_200);
+        assert_eq!(secs("2026-01-15"), 1_768_435_200);
+        for bad in ["", "2026-13-01", "2026-01-15T10:00:00+09:00", "yesterday"] {
+            assert!(parse_date(bad).is_none(), "should reject {bad:?}");
+        }
+    }
+
+    #[test]
+    fn recent_timestamps_render_relative() {
+        let now = at("2026-03-10T12:00:00Z");
+        assert_eq!(now.format_date("2026-03-10T11:59:30Z"), "just now");
+        assert_eq!(now.format_date("2026-03-10T11:15:00Z"), "45 minutes ago");
+        assert_eq!(now.format_date("2026-03-10T11:00:00Z"), "1 hour ago");
+        assert_eq!(now.format_date("2026-03-08T09:00:00Z"), "2 days ago");
+        assert_eq!(now.format_day("2025-12-01T00:00:00Z"), "3 months ago");
+    }
+
+    #[test]
+    fn old_or_unparseable_timestamps_stay_absolute() {
+        let now = at("2026-03-10T12:00:00Z");
+        assert_eq!(
+            now.format_date("2024-01-15T10:00:00Z"),
+            "2024-01-15T10:00:00Z"
+        );
+        assert_eq!(now.format_day("2024-01-15T10:00:00Z"), "2024-01-15");
+        assert_eq!(now.format_date("not a date"), "not a date");
+        // Clock skew: a timestamp after "now" is shown as is.
+        assert_eq!(
+            now.format_date("2026-03-11T00:00:00Z"),
+            "2026-03-11T00:00:00Z"
+        );
+    }
+
+    #[test]
+    fn absolute_style_keeps_iso_dates() {
+        let dates = DateFormat::Absolute;
+        assert_eq!(
+            dates.format_date("2026-03-10T11:00:00Z"),
+            "2026-03-10T11:00:00Z"
+        );
+        assert_eq!(dates.format_day("2026-03-10T11:00:00Z"), "2026-03-10");
+    }
+
+    #[test]
+    fn style_reads_env_flag() {
+        let env = |value: &'static str| move |_: &str| Some(value.to_string());
+        assert_eq!(DateStyle::from_env_with(|_| None), DateStyle::Absolute);
+        assert_eq!(DateStyle::from_env_with(env("1")), DateStyle::Relative);
+        assert_eq!(DateStyle::from_env_with(env("no")), DateStyle::Absolute);
+        assert_eq!(DateStyle::from_env_with(env("maybe")), DateStyle::Absolute);
+    }
+}
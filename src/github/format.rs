@@ -1,11 +1,40 @@
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Write;
 
-use super::types::{IssueInfo, PullInfo, ReleaseInfo, RepoInfo, TreeEntry};
-use crate::markdown::{escape_md_link, shift_headings};
+use clap::ValueEnum;
+
+use super::dates::DateFormat;
+use super::helpers::encode_path;
+use super::types::{
+    BranchInfo, CodeSearchResponse, CommitInfo, CompareFile, CompareResponse, ContentEntry,
+    IssueComment, IssueDetail, IssueInfo, PullDetail, PullFile, PullInfo, ReleaseInfo, RepoInfo,
+    SearchIssuesResponse, TagInfo, TreeEntry, UserInfo, WorkflowRun,
+};
+use crate::markdown::{escape_md_link, fenced, sanitize_heading, shift_headings};
 
 const MAX_README_LINES: usize = 200;
+/// Text-match fragments shown per code search result.
+const MAX_FRAGMENTS_PER_FILE: usize = 3;
+/// Assets listed under each release before the rest collapse into "(+N more)".
+const MAX_ASSETS_PER_RELEASE: usize = 5;
+/// Languages listed in the metadata table before the rest collapse into "+N more".
+const MAX_LANGUAGES: usize = 5;
+/// Topics/labels listed per line before the rest collapse into "+N more".
+pub const DEFAULT_MAX_LABELS: u8 = 8;
+
+/// How much detail the GitHub tools render.
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Verbosity {
+    /// Paths and titles only: no sizes, README, or release assets.
+    Compact,
+    /// Sizes, labels, and authors.
+    #[default]
+    Normal,
+    /// Also links to files, and comment counts and update dates.
+    Detailed,
+}
 
-fn format_size(bytes: u64) -> String {
+pub(crate) fn format_size(bytes: u64) -> String {
     if bytes < 1024 {
         format!("{bytes} B")
     } else if bytes < 1024 * 1024 {
@@ -15,59 +44,341 @@ fn format_size(bytes: u64) -> String {
     }
 }
 
+/// Filters applied to a tree listing, and how many files they were applied to.
+pub(crate) struct TreeFilter<'a> {
+    pub path: Option<&'a str>,
+    pub pattern: Option<&'a str>,
+    pub total_files: usize,
+}
+
+/// Display settings and inlined file contents for [`format_tree`].
+#[derive(Debug, Default)]
+pub(crate) struct TreeStyle {
+    pub verbosity: Verbosity,
+    /// `…/{owner}/{repo}/blob` on the web host; detailed output links each
+    /// path under it at the listed ref.
+    pub blob_base: String,
+    pub inlined: HashMap<String, String>,
+}
+
+/// Format a file listing. Entries present in `style.inlined` get their
+/// content rendered in a fenced block beneath the path. When a filter was
+/// applied, a summary line shows it with the before/after file counts, so an
+/// empty result reads as "filter too narrow" rather than "no files".
 pub(crate) fn format_tree(
     owner: &str,
     repo: &str,
     ref_: &str,
     entries: &[&TreeEntry],
+    filter: &TreeFilter,
+    truncated: bool,
+    style: &TreeStyle,
+) -> String {
+    let mut out = format_tree_header(owner, repo, ref_, entries.len(), filter, truncated);
+    for entry in entries {
+        if style.verbosity == Verbosity::Detailed {
+            let url = format!(
+                "{}/{}/{}",
+                style.blob_base,
+                encode_path(ref_),
+                encode_path(&entry.path)
+            );
+            let _ = write!(
+                out,
+                "[{}]({})",
+                escape_md_link(&entry.path),
+                escape_md_link(&url)
+            );
+        } else {
+            out.push_str(&entry.path);
+        }
+        if let Some(size) = entry.size.filter(|_| style.verbosity != Verbosity::Compact) {
+            let _ = write!(out, " ({})", format_size(size));
+        }
+        out.push('\n');
+        if let Some(content) = style.inlined.get(&entry.path) {
+            let _ = writeln!(out, "{}\n", fenced(content, ""));
+        }
+    }
+
+    out
+}
+
+/// Same header as [`format_tree`], followed by the entries as an indented
+/// `├──`/`└──` diagram. A directory whose only child is another directory
+/// is shown on one line (`a/b/c/`). Compact output drops the sizes.
+pub(crate) fn format_tree_diagram(
+    owner: &str,
+    repo: &str,
+    ref_: &str,
+    entries: &[&TreeEntry],
+    filter: &TreeFilter,
+    truncated: bool,
+    verbosity: Verbosity,
+) -> String {
+    let mut out = format_tree_header(owner, repo, ref_, entries.len(), filter, truncated);
+    let mut root = Vec::new();
+    for entry in entries {
+        let size = entry.size.filter(|_| verbosity != Verbosity::Compact);
+        insert_tree_node(&mut root, &entry.path, size);
+    }
+    render_tree_nodes(&root, "", &mut out);
+    out
+}
+
+fn format_tree_header(
+    owner: &str,
+    repo: &str,
+    ref_: &str,
+    shown: usize,
+    filter: &TreeFilter,
     truncated: bool,
 ) -> String {
     let mut out = format!("{owner}/{repo} (ref: {ref_})\n");
-    let _ = write!(out, "files: {}", entries.len());
+    let applied: Vec<String> = [("path prefix", filter.path), ("pattern", filter.pattern)]
+        .into_iter()
+        .filter_map(|(label, value)| Some(format!("{label}: {}", value?)))
+        .collect();
+    if !applied.is_empty() {
+        let _ = writeln!(
+            out,
+            "{} → {shown} of {} files",
+            applied.join(", "),
+            filter.total_files
+        );
+    }
+    let _ = write!(out, "files: {shown}");
     if truncated {
         out.push_str(" (tree truncated by GitHub — repository exceeds API limits)");
     }
     out.push_str("\n\n");
+    out
+}
 
-    for entry in entries {
-        out.push_str(&entry.path);
-        if let Some(size) = entry.size {
-            let _ = write!(out, " ({})", format_size(size));
+/// A node of a tree diagram; children keep the listing's order.
+enum TreeNode {
+    Dir(Vec<(String, TreeNode)>),
+    File(Option<u64>),
+}
+
+fn insert_tree_node(children: &mut Vec<(String, TreeNode)>, path: &str, size: Option<u64>) {
+    let Some((dir, rest)) = path.split_once('/') else {
+        children.push((path.to_string(), TreeNode::File(size)));
+        return;
+    };
+    // Tree listings keep each directory's entries together, so the match is
+    // almost always the last child.
+    let index = match children
+        .iter()
+        .rposition(|(name, node)| name == dir && matches!(node, TreeNode::Dir(_)))
+    {
+        Some(i) => i,
+        None => {
+            children.push((dir.to_string(), TreeNode::Dir(Vec::new())));
+            children.len() - 1
+        }
+    };
+    if let TreeNode::Dir(sub) = &mut children[index].1 {
+        insert_tree_node(sub, rest, size);
+    }
+}
+
+fn render_tree_nodes(children: &[(String, TreeNode)], prefix: &str, out: &mut String) {
+    for (i, (name, node)) in children.iter().enumerate() {
+        let (branch, indent) = if i + 1 == children.len() {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        match node {
+            TreeNode::File(size) => {
+                let _ = write!(out, "{prefix}{branch}{name}");
+                if let Some(size) = size {
+                    let _ = write!(out, " ({})", format_size(*size));
+                }
+                out.push('\n');
+            }
+            TreeNode::Dir(sub) => {
+                let mut label = format!("{name}/");
+                let mut sub = sub;
+                while let [(child, TreeNode::Dir(grandchildren))] = sub.as_slice() {
+                    let _ = write!(label, "{child}/");
+                    sub = grandchildren;
+                }
+                let _ = writeln!(out, "{prefix}{branch}{label}");
+                render_tree_nodes(sub, &format!("{prefix}{indent}"), out);
+            }
         }
-        out.push('\n');
     }
+}
 
+/// Format the immediate entries of one directory: subdirectories first with
+/// a trailing `/`, then files with their sizes. Compact output drops the
+/// sizes; detailed output links each entry to GitHub.
+pub(crate) fn format_dir_listing(
+    owner: &str,
+    repo: &str,
+    path: Option<&str>,
+    ref_: Option<&str>,
+    entries: &[ContentEntry],
+    verbosity: Verbosity,
+) -> String {
+    let mut out = format!("{owner}/{repo}");
+    if let Some(path) = path {
+        let _ = write!(out, "/{}", path.trim_end_matches('/'));
+    }
+    if let Some(ref_) = ref_ {
+        let _ = write!(out, " (ref: {ref_})");
+    }
+    let (dirs, others): (Vec<_>, Vec<_>) = entries.iter().partition(|e| e.entry_type == "dir");
+    let _ = writeln!(out, "\ndirs: {}, files: {}\n", dirs.len(), others.len());
+    if entries.is_empty() {
+        out.push_str("_Empty directory._\n");
+        return out;
+    }
+
+    let label = |entry: &ContentEntry, suffix: &str| match &entry.html_url {
+        Some(url) if verbosity == Verbosity::Detailed => {
+            format!("[{}{suffix}]({})", entry.name, escape_md_link(url))
+        }
+        _ => format!("{}{suffix}", entry.name),
+    };
+    for dir in &dirs {
+        let _ = writeln!(out, "{}", label(dir, "/"));
+    }
+    for entry in &others {
+        match entry.entry_type.as_str() {
+            "file" if verbosity == Verbosity::Compact => {
+                let _ = writeln!(out, "{}", label(entry, ""));
+            }
+            "file" => {
+                let _ = writeln!(out, "{} ({})", label(entry, ""), format_size(entry.size));
+            }
+            other => {
+                let _ = writeln!(out, "{} ({other})", label(entry, ""));
+            }
+        }
+    }
     out
 }
 
+/// A matched file's preview: fenced leading lines and the file's total line
+/// count, or the read error.
+pub(crate) struct FilePreview<'a> {
+    pub path: &'a str,
+    pub body: Result<(String, usize), String>,
+}
+
+/// Format pattern-matched file previews. `omitted` lists matches left out by
+/// the file/byte budgets.
+pub(crate) fn format_file_previews(
+    owner: &str,
+    repo: &str,
+    ref_: &str,
+    pattern: &str,
+    previews: &[FilePreview<'_>],
+    omitted: &[&str],
+) -> String {
+    let mut out = format!("{owner}/{repo} (ref: {ref_})\n");
+    let _ = writeln!(
+        out,
+        "pattern: {pattern} — files: {}\n",
+        previews.len() + omitted.len()
+    );
+    if previews.is_empty() && omitted.is_empty() {
+        out.push_str("_No matching files._\n");
+        return out;
+    }
+
+    for preview in previews {
+        match &preview.body {
+            Ok((fenced, total)) => {
+                let unit = if *total == 1 { "line" } else { "lines" };
+                let _ = writeln!(out, "## {} ({total} {unit})\n\n{fenced}\n", preview.path);
+            }
+            Err(e) => {
+                let _ = writeln!(out, "## {}\n\n> Could not read: {e}\n", preview.path);
+            }
+        }
+    }
+
+    if !omitted.is_empty() {
+        let _ = writeln!(out, "## Not previewed ({})\n", omitted.len());
+        for path in omitted {
+            let _ = writeln!(out, "- {path}");
+        }
+    }
+    out
+}
+
+/// Display settings for [`format_overview`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct OverviewStyle {
+    /// Topic and label lists show at most this many entries.
+    pub(crate) max_labels: usize,
+    pub(crate) dates: DateFormat,
+    /// Compact drops the README and release assets; detailed adds comment
+    /// counts and update dates to issues and pull requests.
+    pub(crate) verbosity: Verbosity,
+}
+
+/// Section names accepted by `repo-overview --sections`.
+pub const OVERVIEW_SECTIONS: [&str; 5] = ["meta", "readme", "issues", "pulls", "releases"];
+
 /// Format a comprehensive repository overview with metadata, README, issues, PRs, and releases.
+///
+/// `None` lists mean the fetch failed or was not requested and the section is
+/// omitted (the caller reports failures); an empty list renders an explicit
+/// "none" line. `meta` controls the description and metadata table.
 pub(crate) fn format_overview(
     repo: &RepoInfo,
+    meta: bool,
     readme: Option<&str>,
-    issues: &[IssueInfo],
-    pulls: &[PullInfo],
-    releases: &[ReleaseInfo],
+    issues: Option<&[IssueInfo]>,
+    pulls: Option<&[PullInfo]>,
+    releases: Option<&[ReleaseInfo]>,
+    style: OverviewStyle,
 ) -> String {
     let mut out = format!("# {}\n\n", repo.full_name);
 
-    if let Some(ref desc) = repo.description {
-        let _ = writeln!(out, "{desc}\n");
+    if meta {
+        if let Some(ref desc) = repo.description {
+            let _ = writeln!(out, "{desc}\n");
+        }
+        format_metadata_table(repo, style.max_labels, &mut out);
+    }
+    if style.verbosity != Verbosity::Compact {
+        format_readme_section(readme, &mut out);
+    }
+    if let Some(issues) = issues {
+        format_issues_section(issues, style, &mut out);
+    }
+    if let Some(pulls) = pulls {
+        format_pulls_section(pulls, style, &mut out);
+    }
+    if let Some(releases) = releases {
+        format_releases_section(releases, style, &mut out);
     }
-
-    format_metadata_table(repo, &mut out);
-    format_readme_section(readme, &mut out);
-    format_issues_section(issues, &mut out);
-    format_pulls_section(pulls, &mut out);
-    format_releases_section(releases, &mut out);
 
     out
 }
 
-fn format_metadata_table(repo: &RepoInfo, out: &mut String) {
+/// Join `items` with ", ", listing at most `max` and summarizing the rest.
+fn join_capped(items: &[&str], max: usize) -> String {
+    if items.len() <= max {
+        return items.join(", ");
+    }
+    format!("{}, +{} more", items[..max].join(", "), items.len() - max)
+}
+
+fn format_metadata_table(repo: &RepoInfo, max_labels: usize, out: &mut String) {
     out.push_str("| Attribute | Value |\n|-----------|-------|\n");
     if let Some(ref lang) = repo.language {
         let _ = writeln!(out, "| Language | {lang} |");
     }
+    if !repo.languages.is_empty() {
+        let _ = writeln!(out, "| Languages | {} |", format_languages(&repo.languages));
+    }
     let _ = writeln!(out, "| Stars | {} |", repo.stargazers_count);
     let _ = writeln!(out, "| Forks | {} |", repo.forks_count);
     let _ = writeln!(out, "| Open Issues | {} |", repo.open_issues_count);
@@ -78,11 +389,32 @@ fn format_metadata_table(repo: &RepoInfo, out: &mut String) {
     let _ = writeln!(out, "| Default Branch | {} |", repo.default_branch);
     let topics = repo.topics.as_deref().unwrap_or(&[]);
     if !topics.is_empty() {
-        let _ = writeln!(out, "| Topics | {} |", topics.join(", "));
+        let topics: Vec<_> = topics.iter().map(String::as_str).collect();
+        let _ = writeln!(out, "| Topics | {} |", join_capped(&topics, max_labels));
     }
     let _ = writeln!(out, "| URL | {} |\n", repo.html_url);
 }
 
+/// Languages by share of bytes, largest first: "Rust 72%, Shell 3%, …".
+fn format_languages(languages: &BTreeMap<String, u64>) -> String {
+    let total: u64 = languages.values().sum();
+    let mut by_size: Vec<_> = languages.iter().collect();
+    by_size.sort_by(|a, b| b.1.cmp(a.1));
+    let shares: Vec<String> = by_size
+        .into_iter()
+        .map(|(name, &bytes)| {
+            let percent = (bytes * 100 + total / 2) / total.max(1);
+            if percent == 0 {
+                format!("{name} <1%")
+            } else {
+                format!("{name} {percent}%")
+            }
+        })
+        .collect();
+    let shares: Vec<&str> = shares.iter().map(String::as_str).collect();
+    join_capped(&shares, MAX_LANGUAGES)
+}
+
 fn format_readme_section(readme: Option<&str>, out: &mut String) {
     let Some(content) = readme else { return };
     out.push_str("## README\n\n");
@@ -97,25 +429,38 @@ fn format_readme_section(readme: Option<&str>, out: &mut String) {
     out.push_str("\n\n");
 }
 
-fn format_issues_section(issues: &[IssueInfo], out: &mut String) {
+/// " · 3 comments · updated 2024-05-01" for detailed output, else empty.
+fn activity_suffix(
+    comments: Option<u64>,
+    updated_at: Option<&str>,
+    style: OverviewStyle,
+) -> String {
+    let mut suffix = String::new();
+    if style.verbosity != Verbosity::Detailed {
+        return suffix;
+    }
+    if let Some(n) = comments {
+        let _ = write!(suffix, " · {n} comment{}", if n == 1 { "" } else { "s" });
+    }
+    if let Some(date) = updated_at {
+        let _ = write!(suffix, " · updated {}", style.dates.format_day(date));
+    }
+    suffix
+}
+
+fn format_issues_section(issues: &[IssueInfo], style: OverviewStyle, out: &mut String) {
     let real_issues: Vec<_> = issues.iter().filter(|i| i.pull_request.is_none()).collect();
+    out.push_str("## Recent Issues\n\n");
     if real_issues.is_empty() {
+        out.push_str("_No open issues._\n\n");
         return;
     }
-    out.push_str("## Recent Issues\n\n");
     for issue in &real_issues {
         let labels = if issue.labels.is_empty() {
             String::new()
         } else {
-            format!(
-                " ({})",
-                issue
-                    .labels
-                    .iter()
-                    .map(|l| l.name.as_str())
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            )
+            let names: Vec<_> = issue.labels.iter().map(|l| l.name.as_str()).collect();
+            format!(" ({})", join_capped(&names, style.max_labels))
         };
         let user = issue
             .user
@@ -124,22 +469,24 @@ fn format_issues_section(issues: &[IssueInfo], out: &mut String) {
             .unwrap_or_default();
         let _ = writeln!(
             out,
-            "- [#{}]({}) {}{}{}",
+            "- [#{}]({}) {}{}{}{}",
             issue.number,
             escape_md_link(&issue.html_url),
             issue.title,
             labels,
-            user
+            user,
+            activity_suffix(issue.comments, issue.updated_at.as_deref(), style)
         );
     }
     out.push('\n');
 }
 
-fn format_pulls_section(pulls: &[PullInfo], out: &mut String) {
+fn format_pulls_section(pulls: &[PullInfo], style: OverviewStyle, out: &mut String) {
+    out.push_str("## Recent Pull Requests\n\n");
     if pulls.is_empty() {
+        out.push_str("_No open pull requests._\n\n");
         return;
     }
-    out.push_str("## Recent Pull Requests\n\n");
     for pr in pulls {
         let draft = if pr.draft.unwrap_or(false) {
             " [draft]"
@@ -153,29 +500,30 @@ fn format_pulls_section(pulls: &[PullInfo], out: &mut String) {
             .unwrap_or_default();
         let _ = writeln!(
             out,
-            "- [#{}]({}) {}{}{}",
+            "- [#{}]({}) {}{}{}{}",
             pr.number,
             escape_md_link(&pr.html_url),
             pr.title,
             draft,
-            user
+            user,
+            activity_suffix(pr.comments, pr.updated_at.as_deref(), style)
         );
     }
     out.push('\n');
 }
 
-fn format_releases_section(releases: &[ReleaseInfo], out: &mut String) {
+fn format_releases_section(releases: &[ReleaseInfo], style: OverviewStyle, out: &mut String) {
+    out.push_str("## Recent Releases\n\n");
     if releases.is_empty() {
+        out.push_str("_No releases._\n\n");
         return;
     }
-    out.push_str("## Recent Releases\n\n");
     for release in releases {
         let name = release.name.as_deref().unwrap_or(&release.tag_name);
         let date = release
             .published_at
             .as_deref()
-            .and_then(|d| d.get(..10))
-            .unwrap_or("—");
+            .map_or_else(|| "—".to_string(), |d| style.dates.format_day(d));
         let pre = if release.prerelease {
             " (pre-release)"
         } else {
@@ -189,14 +537,518 @@ fn format_releases_section(releases: &[ReleaseInfo], out: &mut String) {
             date,
             pre
         );
+        if style.verbosity == Verbosity::Compact {
+            continue;
+        }
+        for asset in release.assets.iter().take(MAX_ASSETS_PER_RELEASE) {
+            let _ = writeln!(
+                out,
+                "  - [{}]({}) — {} · {} downloads",
+                escape_md_link(&asset.name),
+                escape_md_link(&asset.browser_download_url),
+                format_size(asset.size),
+                asset.download_count
+            );
+        }
+        if release.assets.len() > MAX_ASSETS_PER_RELEASE {
+            let more = release.assets.len() - MAX_ASSETS_PER_RELEASE;
+            let _ = writeln!(out, "  - (+{more} more)");
+        }
+    }
+    out.push('\n');
+}
+
+/// Title line and status summary for an issue or pull request.
+pub(crate) fn format_issue_header(
+    owner: &str,
+    repo: &str,
+    issue: &IssueDetail,
+    dates: DateFormat,
+) -> String {
+    let kind = if issue.pull_request.is_some() {
+        "Pull request"
+    } else {
+        "Issue"
+    };
+    let mut out = format!("# {owner}/{repo}#{}: {}\n\n", issue.number, issue.title);
+    let _ = writeln!(out, "- **{kind}:** {}", escape_md_link(&issue.html_url));
+    let _ = writeln!(out, "- **State:** {}", issue.state);
+    if let Some(user) = &issue.user {
+        let _ = writeln!(out, "- **Author:** @{}", user.login);
+    }
+    let _ = writeln!(
+        out,
+        "- **Opened:** {}",
+        dates.format_date(&issue.created_at)
+    );
+    let _ = writeln!(out, "- **Comments:** {}", issue.comments);
+    if !issue.labels.is_empty() {
+        let names: Vec<_> = issue.labels.iter().map(|l| l.name.as_str()).collect();
+        let _ = writeln!(out, "- **Labels:** {}", names.join(", "));
     }
     out.push('\n');
+    out
+}
+
+/// The opening post followed by each comment, oldest first. Headings inside
+/// posts are nested below the per-post `##` headings.
+pub(crate) fn format_issue_thread(
+    issue: &IssueDetail,
+    comments: &[IssueComment],
+    dates: DateFormat,
+) -> String {
+    let mut out = String::new();
+    let author = |user: Option<&UserInfo>| {
+        user.map_or_else(|| "(unknown)".to_string(), |u| format!("@{}", u.login))
+    };
+    let mut post = |heading: String, body: Option<&str>| {
+        let body = body.map(str::trim).filter(|b| !b.is_empty());
+        let _ = writeln!(out, "## {heading}\n");
+        match body {
+            Some(body) => out.push_str(&shift_headings(body, 2)),
+            None => out.push_str("_(empty)_"),
+        }
+        out.push_str("\n\n");
+    };
+
+    post(
+        format!(
+            "Opening post — {}, {}",
+            author(issue.user.as_ref()),
+            dates.format_date(&issue.created_at)
+        ),
+        issue.body.as_deref(),
+    );
+    for (i, comment) in comments.iter().enumerate() {
+        post(
+            format!(
+                "Comment {} — {}, {}",
+                i + 1,
+                author(comment.user.as_ref()),
+                dates.format_date(&comment.created_at)
+            ),
+            comment.body.as_deref(),
+        );
+    }
+    out
+}
+
+/// Format the latest GitHub Actions runs for a branch, newest first.
+pub(crate) fn format_workflow_runs(
+    owner: &str,
+    repo: &str,
+    branch: &str,
+    runs: &[WorkflowRun],
+    dates: DateFormat,
+) -> String {
+    let mut out = format!("# {owner}/{repo} CI (branch: {branch})\n\n");
+    if runs.is_empty() {
+        out.push_str("No workflow runs found (GitHub Actions may be disabled or unused).\n");
+        return out;
+    }
+
+    for run in runs {
+        let name = run.name.as_deref().unwrap_or("(unnamed workflow)");
+        // `conclusion` is only set once a run completes; fall back to the live status.
+        let result = run
+            .conclusion
+            .as_deref()
+            .or(run.status.as_deref())
+            .unwrap_or("unknown");
+        let sha = run.head_sha.get(..7).unwrap_or(&run.head_sha);
+        let _ = writeln!(
+            out,
+            "- [{}]({}) — **{}** — {} `{}` — {}",
+            escape_md_link(name),
+            escape_md_link(&run.html_url),
+            result,
+            run.event,
+            sha,
+            dates.format_date(&run.created_at)
+        );
+    }
+    out
+}
+
+pub(crate) fn format_code_search(
+    owner: &str,
+    repo: &str,
+    query: &str,
+    response: &CodeSearchResponse,
+) -> String {
+    let mut out = format!(
+        "# Code search: {} in {owner}/{repo}\n\n",
+        sanitize_heading(query)
+    );
+    if response.items.is_empty() {
+        out.push_str("No matching files found.\n");
+        return out;
+    }
+    let _ = write!(
+        out,
+        "{} matching files (showing {})",
+        response.total_count,
+        response.items.len()
+    );
+    if response.incomplete_results {
+        out.push_str(" — GitHub timed out, so results may be incomplete");
+    }
+    out.push_str("\n\n");
+
+    for item in &response.items {
+        let _ = writeln!(
+            out,
+            "## [{}]({})\n",
+            escape_md_link(&item.path),
+            escape_md_link(&item.html_url)
+        );
+        for m in item.text_matches.iter().take(MAX_FRAGMENTS_PER_FILE) {
+            out.push_str(&fenced(m.fragment.trim_end(), ""));
+            out.push_str("\n\n");
+        }
+    }
+    out
+}
+
+pub(crate) fn format_issue_search(
+    owner: &str,
+    repo: &str,
+    query: &str,
+    response: &SearchIssuesResponse,
+) -> String {
+    let mut out = format!(
+        "# Issue search: {} in {owner}/{repo}\n\n",
+        sanitize_heading(query)
+    );
+    if response.items.is_empty() {
+        out.push_str("No matching issues found.\n");
+        return out;
+    }
+    let _ = write!(
+        out,
+        "{} matching issues (showing {})",
+        response.total_count,
+        response.items.len()
+    );
+    if response.incomplete_results {
+        out.push_str(" — GitHub timed out, so results may be incomplete");
+    }
+    out.push_str("\n\n");
+
+    for item in &response.items {
+        let kind = if item.pull_request.is_some() {
+            "PR, "
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            out,
+            "- [#{}]({}) {} ({kind}{})",
+            item.number,
+            escape_md_link(&item.html_url),
+            item.title,
+            item.state
+        );
+    }
+    out
+}
+
+/// Ahead/behind counts and per-file changes between two refs. Patches are
+/// included only when the whole output fits in `max_bytes`; otherwise the
+/// file list alone is shown, cut off at `max_bytes`.
+pub(crate) fn format_compare(
+    owner: &str,
+    repo: &str,
+    base: &str,
+    head: &str,
+    cmp: &CompareResponse,
+    max_bytes: usize,
+) -> String {
+    let mut out = format!("# {owner}/{repo}: {base}...{head}\n\n");
+    let (additions, deletions) = cmp
+        .files
+        .iter()
+        .fold((0, 0), |(a, d), f| (a + f.additions, d + f.deletions));
+    let _ = writeln!(
+        out,
+        "**{}** — {} commits ahead, {} behind · {} files changed (+{additions} −{deletions}) · [compare]({})\n",
+        cmp.status,
+        cmp.ahead_by,
+        cmp.behind_by,
+        cmp.files.len(),
+        escape_md_link(&cmp.html_url)
+    );
+    if cmp.files.is_empty() {
+        out.push_str("No file changes.\n");
+        return out;
+    }
+
+    let list = format_file_list(&cmp.files);
+    let patches = format_patches(&cmp.files);
+
+    if out.len() + list.len() + patches.len() <= max_bytes {
+        out.push_str(&list);
+        out.push_str(&patches);
+        return out;
+    }
+    out.push_str("_Patches omitted: the diff is too large to show in full._\n\n");
+    let budget = max_bytes.saturating_sub(out.len());
+    if list.len() <= budget {
+        out.push_str(&list);
+        return out;
+    }
+    let mut shown = 0;
+    let mut kept = 0;
+    for line in list.split_inclusive('\n') {
+        if kept + line.len() > budget {
+            break;
+        }
+        kept += line.len();
+        shown += usize::from(line.starts_with("- "));
+    }
+    out.push_str(&list[..kept]);
+    let _ = writeln!(
+        out,
+        "\n_…and {} more files (output limit reached)._",
+        cmp.files.len() - shown
+    );
+    out
+}
+
+/// "## Files" section: one line per file with its status and line counts.
+fn format_file_list(files: &[CompareFile]) -> String {
+    let mut list = String::from("## Files\n\n");
+    for file in files {
+        let status = match &file.previous_filename {
+            Some(prev) if file.status == "renamed" => format!("renamed from `{prev}`"),
+            _ => file.status.clone(),
+        };
+        let _ = writeln!(
+            list,
+            "- `{}` — {status} (+{} −{})",
+            file.filename, file.additions, file.deletions
+        );
+    }
+    list.push('\n');
+    list
+}
+
+/// "## Patches" section with a fenced diff per file that has one.
+fn format_patches(files: &[CompareFile]) -> String {
+    let mut patches = String::from("## Patches\n\n");
+    for file in files {
+        if let Some(patch) = &file.patch {
+            let _ = writeln!(
+                patches,
+                "### `{}`\n\n{}\n",
+                file.filename,
+                fenced(patch, "diff")
+            );
+        }
+    }
+    patches
+}
+
+/// Pull request header with merge status, then its changed files. Patches
+/// are included only when `with_patches` is set.
+pub(crate) fn format_pull_files(
+    owner: &str,
+    repo: &str,
+    pull: &PullDetail,
+    files: &[PullFile],
+    with_patches: bool,
+) -> String {
+    let mut out = format!("# {owner}/{repo}#{}: {}\n\n", pull.number, pull.title);
+    let _ = writeln!(
+        out,
+        "- **Pull request:** {}",
+        escape_md_link(&pull.html_url)
+    );
+    let state = if pull.merged { "merged" } else { &pull.state };
+    let _ = writeln!(out, "- **State:** {state}");
+    if !pull.merged && pull.state == "open" {
+        let mergeable = match (pull.mergeable, &pull.mergeable_state) {
+            (None, _) => "not yet computed".to_string(),
+            (Some(m), state) => {
+                let answer = if m { "yes" } else { "no" };
+                match state {
+                    Some(state) => format!("{answer} ({state})"),
+                    None => answer.to_string(),
+                }
+            }
+        };
+        let _ = writeln!(out, "- **Mergeable:** {mergeable}");
+    }
+    let _ = writeln!(
+        out,
+        "- **Branches:** `{}` → `{}`",
+        pull.head.ref_, pull.base.ref_
+    );
+    let _ = writeln!(
+        out,
+        "- **Changed files:** {} (+{} −{})\n",
+        pull.changed_files, pull.additions, pull.deletions
+    );
+    if files.is_empty() {
+        out.push_str("No file changes.\n");
+        return out;
+    }
+    out.push_str(&format_file_list(files));
+    if with_patches {
+        out.push_str(&format_patches(files));
+    }
+    out
+}
+
+/// Scope of a commit listing, shown in its heading.
+pub(crate) struct CommitScope<'a> {
+    pub ref_: Option<&'a str>,
+    pub path: Option<&'a str>,
+}
+
+pub(crate) fn format_commits(
+    owner: &str,
+    repo: &str,
+    scope: &CommitScope,
+    commits: &[CommitInfo],
+    dates: DateFormat,
+) -> String {
+    let mut out = format!("# {owner}/{repo} commits");
+    let filters: Vec<String> = [("ref", scope.ref_), ("path", scope.path)]
+        .into_iter()
+        .filter_map(|(key, value)| Some(format!("{key}: {}", value?)))
+        .collect();
+    if !filters.is_empty() {
+        let _ = write!(out, " ({})", filters.join(", "));
+    }
+    out.push_str("\n\n");
+    if commits.is_empty() {
+        out.push_str("No commits found.\n");
+        return out;
+    }
+
+    for commit in commits {
+        let sha = commit.sha.get(..7).unwrap_or(&commit.sha);
+        let git_author = commit.commit.author.as_ref();
+        let author = commit
+            .author
+            .as_ref()
+            .map(|u| u.login.as_str())
+            .or_else(|| git_author.and_then(|a| a.name.as_deref()))
+            .unwrap_or("unknown");
+        let date = git_author
+            .and_then(|a| a.date.as_deref())
+            .map(|d| dates.format_date(d))
+            .unwrap_or_default();
+        let subject = commit.commit.message.lines().next().unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "- [`{sha}`]({}) — **{author}** — {date} — {subject}",
+            escape_md_link(&commit.html_url)
+        );
+    }
+    out
+}
+
+pub(crate) fn format_branches(
+    owner: &str,
+    repo: &str,
+    branches: &[BranchInfo],
+    per_page: u8,
+) -> String {
+    let refs: Vec<(&str, &str)> = branches
+        .iter()
+        .map(|b| (b.name.as_str(), b.commit.sha.as_str()))
+        .collect();
+    format_ref_list(owner, repo, "branches", &refs, per_page)
+}
+
+pub(crate) fn format_tags(owner: &str, repo: &str, tags: &[TagInfo], per_page: u8) -> String {
+    let refs: Vec<(&str, &str)> = tags
+        .iter()
+        .map(|t| (t.name.as_str(), t.commit.sha.as_str()))
+        .collect();
+    format_ref_list(owner, repo, "tags", &refs, per_page)
+}
+
+/// Ref names and full SHAs, both verbatim so either can be passed as `--ref`.
+/// A full page means GitHub may have more, which the closing note says.
+fn format_ref_list(
+    owner: &str,
+    repo: &str,
+    kind: &str,
+    refs: &[(&str, &str)],
+    per_page: u8,
+) -> String {
+    let mut out = format!("# {owner}/{repo} {kind}\n\n");
+    if refs.is_empty() {
+        let _ = writeln!(out, "No {kind} found.");
+        return out;
+    }
+    for (name, sha) in refs {
+        let _ = writeln!(out, "- `{name}` — `{sha}`");
+    }
+    if refs.len() >= usize::from(per_page) {
+        let _ = write!(
+            out,
+            "\n> **Note:** Showing the first {} {kind}; raise --per-page (up to 100) to see more.\n",
+            refs.len()
+        );
+    }
+    out
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::github::types::{EntryType, LabelInfo, LicenseInfo, UserInfo};
+    use crate::github::types::{EntryType, LabelInfo, LicenseInfo, ReleaseAsset, UserInfo};
+
+    #[test]
+    fn format_overview_lists_release_assets_with_sizes() {
+        let asset = |i: u64| ReleaseAsset {
+            name: format!("scout-{i}.tar.gz"),
+            size: 1024 * 1024 * i,
+            download_count: 10 * i,
+            browser_download_url: format!(
+                "https://github.com/o/r/releases/download/v1/scout-{i}.tar.gz"
+            ),
+        };
+        let releases = vec![ReleaseInfo {
+            tag_name: "v1".into(),
+            name: None,
+            html_url: "https://github.com/o/r/releases/tag/v1".into(),
+            published_at: None,
+            prerelease: false,
+            assets: (1..=7).map(asset).collect(),
+        }];
+        let output = format_overview(
+            &sample_repo(),
+            false,
+            None,
+            None,
+            None,
+            Some(&releases),
+            style(8),
+        );
+        assert!(
+            output.contains(
+                "- [v1](https://github.com/o/r/releases/tag/v1) — —\n\
+                 \x20 - [scout-1.tar.gz](https://github.com/o/r/releases/download/v1/scout-1.tar.gz) — 1.0 MB · 10 downloads\n"
+            ),
+            "got:\n{output}"
+        );
+        assert!(output.contains("scout-5.tar.gz"), "got:\n{output}");
+        assert!(!output.contains("scout-6.tar.gz"), "got:\n{output}");
+        assert!(output.contains("  - (+2 more)\n"), "got:\n{output}");
+    }
+
+    fn style(max_labels: usize) -> OverviewStyle {
+        OverviewStyle {
+            max_labels,
+            dates: DateFormat::Absolute,
+            verbosity: Verbosity::Normal,
+        }
+    }
 
     #[test]
     fn format_size_bytes() {
@@ -228,19 +1080,252 @@ mod tests {
             },
         ];
         let refs: Vec<&TreeEntry> = entries.iter().collect();
-        let output = format_tree("owner", "repo", "main", &refs, false);
+        let output = format_tree(
+            "owner",
+            "repo",
+            "main",
+            &refs,
+            &unfiltered(2),
+            false,
+            &TreeStyle::default(),
+        );
         assert!(output.contains("owner/repo (ref: main)"));
         assert!(output.contains("files: 2"));
         assert!(output.contains("src/main.rs (1.0 KB)"));
         assert!(output.contains("README.md (256 B)"));
     }
 
+    #[test]
+    fn format_tree_inlines_content_under_entry() {
+        let entries = [TreeEntry {
+            path: "config/app.toml".into(),
+            entry_type: EntryType::Blob,
+            size: Some(12),
+        }];
+        let refs: Vec<&TreeEntry> = entries.iter().collect();
+        let style = TreeStyle {
+            inlined: HashMap::from([("config/app.toml".to_string(), "port = 80\n".to_string())]),
+            ..TreeStyle::default()
+        };
+        let output = format_tree("o", "r", "main", &refs, &unfiltered(1), false, &style);
+        assert!(output.contains("config/app.toml (12 B)\n```\nport = 80\n```\n"));
+    }
+
     #[test]
     fn format_tree_truncated() {
-        let output = format_tree("o", "r", "main", &[], true);
+        let output = format_tree(
+            "o",
+            "r",
+            "main",
+            &[],
+            &unfiltered(0),
+            true,
+            &TreeStyle::default(),
+        );
         assert!(output.contains("truncated"));
     }
 
+    #[test]
+    fn format_tree_diagram_nests_and_collapses_single_child_dirs() {
+        let entries: Vec<TreeEntry> = [
+            ("Cargo.toml", Some(300)),
+            ("crates/core/src/lib.rs", Some(2048)),
+            ("src/main.rs", None),
+            ("src/tools/mod.rs", None),
+            ("src/tools/params.rs", None),
+            ("src/util.rs", None),
+        ]
+        .into_iter()
+        .map(|(path, size)| TreeEntry {
+            path: path.into(),
+            entry_type: EntryType::Blob,
+            size,
+        })
+        .collect();
+        let refs: Vec<&TreeEntry> = entries.iter().collect();
+        let output = format_tree_diagram(
+            "o",
+            "r",
+            "main",
+            &refs,
+            &unfiltered(6),
+            false,
+            Verbosity::Normal,
+        );
+        assert_eq!(
+            output,
+            "o/r (ref: main)\nfiles: 6\n\n\
+             ├── Cargo.toml (300 B)\n\
+             ├── crates/core/src/\n\
+             │   └── lib.rs (2.0 KB)\n\
+             └── src/\n    \
+                 ├── main.rs\n    \
+                 ├── tools/\n    \
+                 │   ├── mod.rs\n    \
+                 │   └── params.rs\n    \
+                 └── util.rs\n"
+        );
+    }
+
+    #[test]
+    fn format_tree_detailed_encodes_link_paths() {
+        let entries = [TreeEntry {
+            path: "docs/a [draft]#1.md".into(),
+            entry_type: EntryType::Blob,
+            size: None,
+        }];
+        let refs: Vec<&TreeEntry> = entries.iter().collect();
+        let style = TreeStyle {
+            verbosity: Verbosity::Detailed,
+            blob_base: "https://github.com/o/r/blob".into(),
+            ..TreeStyle::default()
+        };
+        let output = format_tree(
+            "o",
+            "r",
+            "release/v1#2",
+            &refs,
+            &unfiltered(1),
+            false,
+            &style,
+        );
+        assert!(
+            output.ends_with(
+                "\n\n[docs/a \\[draft\\]#1.md](https://github.com/o/r/blob/release/v1%232/docs/a%20%5Bdraft%5D%231.md)\n"
+            ),
+            "got:\n{output}"
+        );
+    }
+
+    #[test]
+    fn format_tree_compact_and_detailed() {
+        let entries = [TreeEntry {
+            path: "src/main.rs".into(),
+            entry_type: EntryType::Blob,
+            size: Some(1024),
+        }];
+        let refs: Vec<&TreeEntry> = entries.iter().collect();
+        let style = |verbosity| TreeStyle {
+            verbosity,
+            blob_base: "https://github.com/o/r/blob".into(),
+            ..TreeStyle::default()
+        };
+        let compact = format_tree(
+            "o",
+            "r",
+            "main",
+            &refs,
+            &unfiltered(1),
+            false,
+            &style(Verbosity::Compact),
+        );
+        assert!(compact.ends_with("\n\nsrc/main.rs\n"), "got:\n{compact}");
+        let detailed = format_tree(
+            "o",
+            "r",
+            "main",
+            &refs,
+            &unfiltered(1),
+            false,
+            &style(Verbosity::Detailed),
+        );
+        assert!(
+            detailed.ends_with(
+                "\n\n[src/main.rs](https://github.com/o/r/blob/main/src/main.rs) (1.0 KB)\n"
+            ),
+            "got:\n{detailed}"
+        );
+        let diagram = format_tree_diagram(
+            "o",
+            "r",
+            "main",
+            &refs,
+            &unfiltered(1),
+            false,
+            Verbosity::Compact,
+        );
+        assert!(diagram.ends_with("└── main.rs\n"), "got:\n{diagram}");
+    }
+
+    fn unfiltered(total_files: usize) -> TreeFilter<'static> {
+        TreeFilter {
+            path: None,
+            pattern: None,
+            total_files,
+        }
+    }
+
+    #[test]
+    fn format_tree_summarizes_applied_filters() {
+        let entries = [TreeEntry {
+            path: "src/main.rs".into(),
+            entry_type: EntryType::Blob,
+            size: None,
+        }];
+        let refs: Vec<&TreeEntry> = entries.iter().collect();
+        let filter = TreeFilter {
+            path: Some("src/"),
+            pattern: Some("*.rs"),
+            total_files: 3400,
+        };
+        let output = format_tree(
+            "o",
+            "r",
+            "main",
+            &refs,
+            &filter,
+            false,
+            &TreeStyle::default(),
+        );
+        assert!(output.starts_with(
+            "o/r (ref: main)\npath prefix: src/, pattern: *.rs → 1 of 3400 files\nfiles: 1\n"
+        ));
+
+        let filter = TreeFilter {
+            path: None,
+            pattern: Some("*.zig"),
+            total_files: 3400,
+        };
+        let output = format_tree("o", "r", "main", &[], &filter, false, &TreeStyle::default());
+        assert!(output.contains("\npattern: *.zig → 0 of 3400 files\n"));
+
+        let output = format_tree(
+            "o",
+            "r",
+            "main",
+            &refs,
+            &unfiltered(1),
+            false,
+            &TreeStyle::default(),
+        );
+        assert!(!output.contains("→"));
+    }
+
+    #[test]
+    fn format_file_previews_lists_omitted_and_errors() {
+        let previews = [
+            FilePreview {
+                path: "a.test.ts",
+                body: Ok(("```ts\nit()\n```".into(), 40)),
+            },
+            FilePreview {
+                path: "b.test.ts",
+                body: Err("Not found".into()),
+            },
+        ];
+        let output = format_file_previews("o", "r", "main", "*.test.ts", &previews, &["c.test.ts"]);
+        assert!(output.contains("pattern: *.test.ts — files: 3"));
+        assert!(output.contains("## a.test.ts (40 lines)\n\n```ts\nit()\n```"));
+        assert!(output.contains("## b.test.ts\n\n> Could not read: Not found"));
+        assert!(output.contains("## Not previewed (1)\n\n- c.test.ts"));
+    }
+
+    #[test]
+    fn format_file_previews_without_matches() {
+        let output = format_file_previews("o", "r", "main", "*.zig", &[], &[]);
+        assert!(output.contains("_No matching files._"));
+    }
+
     fn sample_repo() -> RepoInfo {
         RepoInfo {
             full_name: "owner/repo".into(),
@@ -256,9 +1341,27 @@ mod tests {
                 spdx_id: Some("MIT".into()),
                 name: "MIT License".into(),
             }),
+            languages: BTreeMap::new(),
         }
     }
 
+    #[test]
+    fn format_overview_shows_language_shares() {
+        let mut repo = sample_repo();
+        repo.languages = BTreeMap::from([
+            ("Dockerfile".to_string(), 30),
+            ("JavaScript".to_string(), 18_000),
+            ("Rust".to_string(), 72_000),
+            ("Shell".to_string(), 9_970),
+        ]);
+        let output = format_overview(&repo, true, None, None, None, None, style(8));
+        assert!(
+            output
+                .contains("| Languages | Rust 72%, JavaScript 18%, Shell 10%, Dockerfile <1% |\n"),
+            "got:\n{output}"
+        );
+    }
+
     #[test]
     fn format_overview_minimal() {
         let repo = RepoInfo {
@@ -272,8 +1375,9 @@ mod tests {
             open_issues_count: 0,
             topics: None,
             license: None,
+            languages: BTreeMap::new(),
         };
-        let output = format_overview(&repo, None, &[], &[], &[]);
+        let output = format_overview(&repo, true, None, None, None, None, style(8));
         assert!(output.contains("# o/r"));
         assert!(output.contains("| Stars | 0 |"));
         assert!(!output.contains("## README"));
@@ -283,7 +1387,7 @@ mod tests {
     #[test]
     fn format_overview_with_metadata() {
         let repo = sample_repo();
-        let output = format_overview(&repo, None, &[], &[], &[]);
+        let output = format_overview(&repo, true, None, None, None, None, style(8));
         assert!(output.contains("| Language | Rust |"));
         assert!(output.contains("| License | MIT |"));
         assert!(output.contains("| Topics | rust, cli |"));
@@ -297,11 +1401,66 @@ mod tests {
             .map(|i| format!("line {i}"))
             .collect::<Vec<_>>()
             .join("\n");
-        let output = format_overview(&repo, Some(&long_readme), &[], &[], &[]);
+        let output = format_overview(&repo, true, Some(&long_readme), None, None, None, style(8));
         assert!(output.contains("## README"));
         assert!(output.contains("truncated, 250 lines total"));
     }
 
+    #[test]
+    fn format_overview_compact_and_detailed() {
+        let repo = sample_repo();
+        let issues = vec![IssueInfo {
+            number: 1,
+            title: "Crash on start".into(),
+            html_url: "https://github.com/o/r/issues/1".into(),
+            labels: vec![],
+            user: None,
+            pull_request: None,
+            comments: Some(3),
+            updated_at: Some("2024-05-01T10:00:00Z".into()),
+        }];
+        let releases = vec![ReleaseInfo {
+            tag_name: "v1.0.0".into(),
+            name: None,
+            html_url: "https://github.com/o/r/releases/tag/v1.0.0".into(),
+            published_at: None,
+            prerelease: false,
+            assets: vec![ReleaseAsset {
+                name: "app.tar.gz".into(),
+                size: 2048,
+                download_count: 7,
+                browser_download_url: "https://github.com/o/r/releases/download/app.tar.gz".into(),
+            }],
+        }];
+        let render = |verbosity| {
+            format_overview(
+                &repo,
+                true,
+                Some("# Readme"),
+                Some(&issues),
+                None,
+                Some(&releases),
+                OverviewStyle {
+                    verbosity,
+                    ..style(8)
+                },
+            )
+        };
+
+        let compact = render(Verbosity::Compact);
+        assert!(!compact.contains("## README"), "got:\n{compact}");
+        assert!(!compact.contains("app.tar.gz"), "got:\n{compact}");
+        assert!(compact.contains("Crash on start\n"), "got:\n{compact}");
+
+        let detailed = render(Verbosity::Detailed);
+        assert!(detailed.contains("## README"), "got:\n{detailed}");
+        assert!(detailed.contains("app.tar.gz"), "got:\n{detailed}");
+        assert!(
+            detailed.contains("Crash on start · 3 comments · updated 2024-05-01\n"),
+            "got:\n{detailed}"
+        );
+    }
+
     #[test]
     fn format_overview_filters_issues_from_prs() {
         let repo = sample_repo();
@@ -313,6 +1472,8 @@ mod tests {
                 labels: vec![],
                 user: None,
                 pull_request: None,
+                comments: None,
+                updated_at: None,
             },
             IssueInfo {
                 number: 2,
@@ -321,9 +1482,11 @@ mod tests {
                 labels: vec![],
                 user: None,
                 pull_request: Some(serde_json::json!({})),
+                comments: None,
+                updated_at: None,
             },
         ];
-        let output = format_overview(&repo, None, &issues, &[], &[]);
+        let output = format_overview(&repo, true, None, Some(&issues), None, None, style(8));
         assert!(output.contains("Real issue"));
         assert!(!output.contains("PR as issue"));
     }
@@ -339,12 +1502,32 @@ mod tests {
             user: Some(UserInfo {
                 login: "dev".into(),
             }),
+            comments: None,
+            updated_at: None,
         }];
-        let output = format_overview(&repo, None, &[], &pulls, &[]);
+        let output = format_overview(&repo, true, None, None, Some(&pulls), None, style(8));
         assert!(output.contains("[draft]"));
         assert!(output.contains("@dev"));
     }
 
+    #[test]
+    fn format_overview_marks_empty_sections_explicitly() {
+        let repo = sample_repo();
+        let output = format_overview(&repo, true, None, Some(&[]), Some(&[]), Some(&[]), style(8));
+        assert!(output.contains("## Recent Issues\n\n_No open issues._"));
+        assert!(output.contains("## Recent Pull Requests\n\n_No open pull requests._"));
+        assert!(output.contains("## Recent Releases\n\n_No releases._"));
+    }
+
+    #[test]
+    fn format_overview_omits_sections_that_failed_to_fetch() {
+        let repo = sample_repo();
+        let output = format_overview(&repo, true, None, Some(&[]), None, None, style(8));
+        assert!(output.contains("## Recent Issues"));
+        assert!(!output.contains("## Recent Pull Requests"));
+        assert!(!output.contains("## Recent Releases"));
+    }
+
     #[test]
     fn format_overview_shows_prerelease() {
         let repo = sample_repo();
@@ -354,12 +1537,48 @@ mod tests {
             html_url: "https://github.com/o/r/releases/tag/v0.1.0-beta".into(),
             published_at: Some("2026-01-15T00:00:00Z".into()),
             prerelease: true,
+            assets: vec![],
         }];
-        let output = format_overview(&repo, None, &[], &[], &releases);
+        let output = format_overview(&repo, true, None, None, None, Some(&releases), style(8));
         assert!(output.contains("(pre-release)"));
         assert!(output.contains("2026-01-15"));
     }
 
+    #[test]
+    fn format_overview_caps_topics() {
+        let mut repo = sample_repo();
+        repo.topics = Some((1..=11).map(|i| format!("t{i}")).collect());
+        let output = format_overview(&repo, true, None, None, None, None, style(8));
+        assert!(
+            output.contains("| Topics | t1, t2, t3, t4, t5, t6, t7, t8, +3 more |"),
+            "got:\n{output}"
+        );
+
+        let output = format_overview(&repo, true, None, None, None, None, style(11));
+        assert!(output.contains("t11 |"), "got:\n{output}");
+        assert!(!output.contains("more"), "got:\n{output}");
+    }
+
+    #[test]
+    fn format_overview_caps_issue_labels() {
+        let repo = sample_repo();
+        let issues = vec![IssueInfo {
+            number: 7,
+            title: "Triage".into(),
+            html_url: "https://github.com/o/r/issues/7".into(),
+            labels: ["a", "b", "c"]
+                .into_iter()
+                .map(|name| LabelInfo { name: name.into() })
+                .collect(),
+            user: None,
+            pull_request: None,
+            comments: None,
+            updated_at: None,
+        }];
+        let output = format_overview(&repo, true, None, Some(&issues), None, None, style(2));
+        assert!(output.contains("Triage (a, b, +1 more)"), "got:\n{output}");
+    }
+
     #[test]
     fn format_overview_shows_issue_labels() {
         let repo = sample_repo();
@@ -377,8 +1596,10 @@ mod tests {
                 login: "reporter".into(),
             }),
             pull_request: None,
+            comments: None,
+            updated_at: None,
         }];
-        let output = format_overview(&repo, None, &issues, &[], &[]);
+        let output = format_overview(&repo, true, None, Some(&issues), None, None, style(8));
         assert!(output.contains("(bug, urgent)"));
         assert!(output.contains("@reporter"));
     }
@@ -387,7 +1608,7 @@ mod tests {
     fn format_overview_shifts_readme_headings() {
         let repo = sample_repo();
         let readme = "# Getting Started\n## Install\nRun `cargo install`\n### Config";
-        let output = format_overview(&repo, Some(readme), &[], &[], &[]);
+        let output = format_overview(&repo, true, Some(readme), None, None, None, style(8));
         assert!(output.contains("### Getting Started"), "h1 should shift to h3");
         assert!(output.contains("#### Install"), "h2 should shift to h4");
         assert!(output.contains("##### Config"), "h3 should shift to h5");
@@ -401,8 +1622,148 @@ mod tests {
             lines.push(format!("line {i}"));
         }
         let readme = lines.join("\n");
-        let output = format_overview(&repo, Some(&readme), &[], &[], &[]);
+        let output = format_overview(&repo, true, Some(&readme), None, None, None, style(8));
         assert!(output.contains("### Title"), "h1 should shift to h3 even when truncated");
         assert!(output.contains("truncated, 251 lines total"));
     }
+
+    fn sample_run(name: &str, status: &str, conclusion: Option<&str>) -> WorkflowRun {
+        WorkflowRun {
+            name: Some(name.into()),
+            html_url: "https://github.com/o/r/actions/runs/1".into(),
+            head_sha: "abcdef0123456789".into(),
+            event: "push".into(),
+            status: Some(status.into()),
+            conclusion: conclusion.map(Into::into),
+            created_at: "2026-01-15T10:00:00Z".into(),
+        }
+    }
+
+    #[test]
+    fn format_workflow_runs_prefers_conclusion_over_status() {
+        let runs = [
+            sample_run("CI", "completed", Some("success")),
+            sample_run("Deploy", "in_progress", None),
+        ];
+        let out = format_workflow_runs("o", "r", "main", &runs, DateFormat::Absolute);
+        assert!(out.starts_with("# o/r CI (branch: main)\n"));
+        assert!(out.contains("[CI](https://github.com/o/r/actions/runs/1) — **success** — push `abcdef0` — 2026-01-15T10:00:00Z"));
+        assert!(out.contains("[Deploy]("));
+        assert!(out.contains("**in_progress**"));
+    }
+
+    #[test]
+    fn format_workflow_runs_empty() {
+        let out = format_workflow_runs("o", "r", "main", &[], DateFormat::Absolute);
+        assert!(out.contains("No workflow runs found"));
+    }
+
+    #[test]
+    fn format_commits_falls_back_to_git_author_name() {
+        use crate::github::types::{CommitAuthor, CommitDetail};
+
+        let commits = [CommitInfo {
+            sha: "abcdef0123456789".into(),
+            html_url: "https://github.com/o/r/commit/abcdef0123456789".into(),
+            commit: CommitDetail {
+                message: "Fix the parser\n\nLonger explanation.".into(),
+                author: Some(CommitAuthor {
+                    name: Some("Jo Dev".into()),
+                    date: Some("2026-01-15T10:00:00Z".into()),
+                }),
+            },
+            author: None,
+        }];
+        let scope = CommitScope {
+            ref_: None,
+            path: Some("src/lib.rs"),
+        };
+        let out = format_commits("o", "r", &scope, &commits, DateFormat::Absolute);
+        assert!(out.starts_with("# o/r commits (path: src/lib.rs)\n"));
+        assert!(out.contains(
+            "- [`abcdef0`](https://github.com/o/r/commit/abcdef0123456789) — **Jo Dev** — 2026-01-15T10:00:00Z — Fix the parser\n"
+        ));
+        assert!(!out.contains("Longer explanation"));
+    }
+
+    fn sample_compare(files: usize, patch: &str) -> CompareResponse {
+        use crate::github::types::CompareFile;
+
+        CompareResponse {
+            status: "ahead".into(),
+            ahead_by: 2,
+            behind_by: 0,
+            html_url: "https://github.com/o/r/compare/v1...main".into(),
+            files: (0..files)
+                .map(|i| CompareFile {
+                    filename: format!("src/file{i}.rs"),
+                    status: "modified".into(),
+                    additions: 3,
+                    deletions: 1,
+                    previous_filename: None,
+                    patch: Some(patch.into()),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn format_compare_includes_patches_when_small() {
+        let cmp = sample_compare(2, "@@ -1 +1 @@\n-old\n+new");
+        let out = format_compare("o", "r", "v1", "main", &cmp, 100_000);
+        assert!(out.starts_with(
+            "# o/r: v1...main\n\n**ahead** — 2 commits ahead, 0 behind · 2 files changed (+6 −2)"
+        ));
+        assert!(out.contains("- `src/file1.rs` — modified (+3 −1)\n"));
+        assert!(out.contains("### `src/file0.rs`\n\n```diff\n@@ -1 +1 @@\n-old\n+new\n```\n"));
+    }
+
+    #[test]
+    fn format_compare_drops_patches_then_files_over_budget() {
+        let cmp = sample_compare(50, &"+line\n".repeat(1_000));
+        let out = format_compare("o", "r", "v1", "main", &cmp, 10_000);
+        assert!(out.contains("_Patches omitted"));
+        assert!(!out.contains("```diff"));
+        assert!(out.contains("- `src/file49.rs`"));
+
+        let out = format_compare("o", "r", "v1", "main", &cmp, 1_000);
+        assert!(out.len() <= 1_100, "len {}", out.len());
+        assert!(out.contains("- `src/file0.rs`"));
+        assert!(!out.contains("- `src/file49.rs`"));
+        assert!(out.contains("more files (output limit reached)"));
+    }
+
+    #[test]
+    fn relative_dates_apply_to_runs_and_releases() {
+        let now = crate::github::dates::parse_date("2026-01-17T10:00:00Z").unwrap();
+        let dates = DateFormat::RelativeTo(now);
+
+        let runs = [sample_run("CI", "completed", Some("success"))];
+        let out = format_workflow_runs("o", "r", "main", &runs, dates);
+        assert!(out.ends_with("— 2 days ago\n"), "got:\n{out}");
+
+        let releases = vec![ReleaseInfo {
+            tag_name: "v1.0.0".into(),
+            name: None,
+            html_url: "https://github.com/o/r/releases/tag/v1.0.0".into(),
+            published_at: Some("2023-05-01T00:00:00Z".into()),
+            prerelease: false,
+            assets: vec![],
+        }];
+        let style = OverviewStyle {
+            max_labels: 8,
+            dates,
+            verbosity: Verbosity::Normal,
+        };
+        let out = format_overview(
+            &sample_repo(),
+            false,
+            None,
+            None,
+            None,
+            Some(&releases),
+            style,
+        );
+        assert!(out.contains("— 2023-05-01\n"), "got:\n{out}");
+    }
 }
@@ -0,0 +1,404 @@
+//! `repo_overview` data in one GraphQL request instead of one REST call per
+//! section. GraphQL requires a token, so callers fall back to REST without one.
+
+use serde::Deserialize;
+use tracing::debug;
+
+use super::types::{
+    IssueInfo, LabelInfo, LicenseInfo, PullInfo, ReleaseAsset, ReleaseInfo, RepoInfo, UserInfo,
+};
+use super::{GitHubClient, GitHubError};
+
+const OVERVIEW_QUERY: &str = r#"
+query Overview(
+  $owner: String!, $name: String!, $items: Int!, $releases: Int!,
+  $meta: Boolean!, $readme: Boolean!, $issues: Boolean!, $pulls: Boolean!, $withReleases: Boolean!
+) {
+  repository(owner: $owner, name: $name) {
+    nameWithOwner
+    description
+    url
+    defaultBranchRef { name }
+    primaryLanguage { name }
+    stargazerCount
+    forkCount
+    openIssues: issues(states: OPEN) { totalCount }
+    openPulls: pullRequests(states: OPEN) { totalCount }
+    repositoryTopics(first: 20) { nodes { topic { name } } }
+    licenseInfo { spdxId name }
+    languages(first: 20, orderBy: {field: SIZE, direction: DESC}) @include(if: $meta) {
+      edges { size node { name } }
+    }
+    readme0: object(expression: "HEAD:README.md") @include(if: $readme) { ... on Blob { text } }
+    readme1: object(expression: "HEAD:README") @include(if: $readme) { ... on Blob { text } }
+    readme2: object(expression: "HEAD:readme.md") @include(if: $readme) { ... on Blob { text } }
+    issues(first: $items, states: OPEN, orderBy: {field: UPDATED_AT, direction: DESC}) @include(if: $issues) {
+      nodes {
+        number title url updatedAt author { login } comments { totalCount }
+        labels(first: 20) { nodes { name } }
+      }
+    }
+    pullRequests(first: $items, states: OPEN, orderBy: {field: UPDATED_AT, direction: DESC}) @include(if: $pulls) {
+      nodes { number title url isDraft updatedAt author { login } comments { totalCount } }
+    }
+    releases(first: $releases, orderBy: {field: CREATED_AT, direction: DESC}) @include(if: $withReleases) {
+      nodes {
+        tagName name url publishedAt isPrerelease
+        releaseAssets(first: 100) { nodes { name size downloadCount downloadUrl } }
+      }
+    }
+  }
+}
+"#;
+
+/// Which `repo_overview` sections to fetch.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct OverviewSections {
+    pub meta: bool,
+    pub readme: bool,
+    pub issues: bool,
+    pub pulls: bool,
+    pub releases: bool,
+}
+
+/// Everything `format_overview` needs. `None` lists were not requested.
+/// `readme` is `None` when not requested or when none of `README.md`,
+/// `README`, `readme.md` exists; the REST `/readme` endpoint finds others.
+#[derive(Debug)]
+pub(crate) struct Overview {
+    pub repo: RepoInfo,
+    pub readme: Option<String>,
+    pub issues: Option<Vec<IssueInfo>>,
+    pub pulls: Option<Vec<PullInfo>>,
+    pub releases: Option<Vec<ReleaseInfo>>,
+}
+
+impl GitHubClient {
+    /// Repository metadata and the requested overview sections in a single
+    /// GraphQL request. Requires a token.
+    pub(crate) async fn get_overview(
+        &self,
+        owner: &str,
+        repo: &str,
+        sections: OverviewSections,
+        items: u8,
+        releases: u8,
+    ) -> Result<Overview, GitHubError> {
+        if !self.has_token().await {
+            return Err(GitHubError::AuthRequired("The GraphQL API"));
+        }
+        let body = serde_json::json!({
+            "query": OVERVIEW_QUERY,
+            "variables": {
+                "owner": owner,
+                "name": repo,
+                "items": items.min(100),
+                "releases": releases.min(100),
+                "meta": sections.meta,
+                "readme": sections.readme,
+                "issues": sections.issues,
+                "pulls": sections.pulls,
+                "withReleases": sections.releases,
+            },
+        });
+        let url = graphql_url(&self.base_url);
+        debug!(owner, repo, "github GraphQL overview request");
+        let response = self
+            .authorize(self.http.post(&url), &url)
+            .await
+            .header("Accept", "application/json")
+            .json(&body)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(GitHubError::Api {
+                code: status.as_u16(),
+                message: response.text().await.unwrap_or_default(),
+            });
+        }
+        let reply: GraphQlReply = response.json().await?;
+        if let Some(error) = reply.errors.first() {
+            return Err(GitHubError::GraphQl(error.message.clone()));
+        }
+        let repository = reply
+            .data
+            .and_then(|d| d.repository)
+            .ok_or_else(|| GitHubError::GraphQl("no repository in response".into()))?;
+        repository.into_overview(sections)
+    }
+}
+
+/// The GraphQL endpoint for a REST base URL: `https://api.github.com/graphql`,
+/// or `…/api/graphql` for GitHub Enterprise's `…/api/v3`.
+fn graphql_url(base_url: &str) -> String {
+    match base_url.strip_suffix("/api/v3") {
+        Some(host) => format!("{host}/api/graphql"),
+        None => format!("{base_url}/graphql"),
+    }
+}
+
+#[derive(Deserialize)]
+struct GraphQlReply {
+    data: Option<ReplyData>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct ReplyData {
+    repository: Option<Repository>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Repository {
+    name_with_owner: String,
+    description: Option<String>,
+    url: String,
+    default_branch_ref: Option<Named>,
+    primary_language: Option<Named>,
+    stargazer_count: u64,
+    fork_count: u64,
+    open_issues: Count,
+    open_pulls: Count,
+    repository_topics: Nodes<TopicNode>,
+    license_info: Option<License>,
+    languages: Option<LanguageEdges>,
+    readme0: Option<Blob>,
+    readme1: Option<Blob>,
+    readme2: Option<Blob>,
+    issues: Option<Nodes<Issue>>,
+    pull_requests: Option<Nodes<Pull>>,
+    releases: Option<Nodes<Release>>,
+}
+
+#[derive(Deserialize)]
+struct Named {
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Count {
+    total_count: u64,
+}
+
+#[derive(Deserialize)]
+struct Nodes<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct TopicNode {
+    topic: Named,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct License {
+    spdx_id: Option<String>,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct LanguageEdges {
+    edges: Vec<LanguageEdge>,
+}
+
+#[derive(Deserialize)]
+struct LanguageEdge {
+    size: u64,
+    node: Named,
+}
+
+/// `object(expression:)` result; `text` is absent for trees and `null` for
+/// binary blobs.
+#[derive(Deserialize)]
+struct Blob {
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Login {
+    login: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Issue {
+    number: u64,
+    title: String,
+    url: String,
+    updated_at: Option<String>,
+    author: Option<Login>,
+    comments: Count,
+    labels: Option<Nodes<Named>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Pull {
+    number: u64,
+    title: String,
+    url: String,
+    is_draft: bool,
+    updated_at: Option<String>,
+    author: Option<Login>,
+    comments: Count,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Release {
+    tag_name: String,
+    name: Option<String>,
+    url: String,
+    published_at: Option<String>,
+    is_prerelease: bool,
+    release_assets: Nodes<Asset>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Asset {
+    name: String,
+    size: u64,
+    download_count: u64,
+    download_url: String,
+}
+
+fn user(author: Option<Login>) -> Option<UserInfo> {
+    author.map(|a| UserInfo { login: a.login })
+}
+
+impl Repository {
+    fn into_overview(self, sections: OverviewSections) -> Result<Overview, GitHubError> {
+        // An empty repository has no default branch ref; REST still reports
+        // the configured name, so let the caller fall back to it.
+        let default_branch = self
+            .default_branch_ref
+            .ok_or_else(|| GitHubError::GraphQl("repository has no default branch".into()))?
+            .name;
+        let readme = [self.readme0, self.readme1, self.readme2]
+            .into_iter()
+            .flatten()
+            .find_map(|blob| blob.text);
+
+        let repo = RepoInfo {
+            full_name: self.name_with_owner,
+            description: self.description,
+            html_url: self.url,
+            default_branch,
+            language: self.primary_language.map(|l| l.name),
+            stargazers_count: self.stargazer_count,
+            forks_count: self.fork_count,
+            // REST counts open pull requests as issues too.
+            open_issues_count: self.open_issues.total_count + self.open_pulls.total_count,
+            topics: Some(
+                self.repository_topics
+                    .nodes
+                    .into_iter()
+                    .map(|t| t.topic.name)
+                    .collect(),
+            ),
+            license: self.license_info.map(|l| LicenseInfo {
+                spdx_id: l.spdx_id,
+                name: l.name,
+            }),
+            languages: self
+                .languages
+                .map(|l| l.edges.into_iter().map(|e| (e.node.name, e.size)).collect())
+                .unwrap_or_default(),
+        };
+
+        let issues = self.issues.map(|n| {
+            n.nodes
+                .into_iter()
+                .map(|i| IssueInfo {
+                    number: i.number,
+                    title: i.title,
+                    html_url: i.url,
+                    labels: i
+                        .labels
+                        .map(|l| l.nodes)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|l| LabelInfo { name: l.name })
+                        .collect(),
+                    user: user(i.author),
+                    pull_request: None,
+                    comments: Some(i.comments.total_count),
+                    updated_at: i.updated_at,
+                })
+                .collect()
+        });
+        let pulls = self.pull_requests.map(|n| {
+            n.nodes
+                .into_iter()
+                .map(|p| PullInfo {
+                    number: p.number,
+                    title: p.title,
+                    html_url: p.url,
+                    draft: Some(p.is_draft),
+                    user: user(p.author),
+                    comments: Some(p.comments.total_count),
+                    updated_at: p.updated_at,
+                })
+                .collect()
+        });
+        let releases = self.releases.map(|n| {
+            n.nodes
+                .into_iter()
+                .map(|r| ReleaseInfo {
+                    tag_name: r.tag_name,
+                    name: r.name,
+                    html_url: r.url,
+                    published_at: r.published_at,
+                    prerelease: r.is_prerelease,
+                    assets: r
+                        .release_assets
+                        .nodes
+                        .into_iter()
+                        .map(|a| ReleaseAsset {
+                            name: a.name,
+                            size: a.size,
+                            download_count: a.download_count,
+                            browser_download_url: a.download_url,
+                        })
+                        .collect(),
+                })
+                .collect()
+        });
+
+        Ok(Overview {
+            repo,
+            readme: readme.filter(|_| sections.readme),
+            issues,
+            pulls,
+            releases,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graphql_url_for_public_and_enterprise_hosts() {
+        assert_eq!(
+            graphql_url("https://api.github.com"),
+            "https://api.github.com/graphql"
+        );
+        assert_eq!(
+            graphql_url("https://ghe.example.com/api/v3"),
+            "https://ghe.example.com/api/graphql"
+        );
+    }
+}
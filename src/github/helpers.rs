@@ -1,14 +1,17 @@
 use base64::{Engine as _, engine::general_purpose::STANDARD};
-use globset::Glob;
+use globset::GlobBuilder;
 use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
 
 use super::GitHubError;
 use super::types::{EntryType, TreeEntry};
+use crate::markdown::fenced;
 
 /// Characters to percent-encode in URL path segments.
 /// Preserves `/` for path structure but encodes query/fragment delimiters and special chars.
+/// `\` is included because URL parsing would otherwise turn it into `/`.
 const PATH_ENCODE_SET: &AsciiSet = &CONTROLS
     .add(b' ')
+    .add(b'\\')
     .add(b'?')
     .add(b'#')
     .add(b'%')
@@ -32,9 +35,41 @@ fn is_valid_github_name(s: &str) -> bool {
         && s != "."
 }
 
+/// First path segments GitHub uses for its own pages. No account can take
+/// these names, so `github.com/<segment>/...` is never a repository.
+const RESERVED_OWNERS: &[&str] = &[
+    "about",
+    "apps",
+    "codespaces",
+    "collections",
+    "customer-stories",
+    "enterprise",
+    "events",
+    "explore",
+    "features",
+    "issues",
+    "login",
+    "marketplace",
+    "new",
+    "notifications",
+    "organizations",
+    "orgs",
+    "pricing",
+    "pulls",
+    "search",
+    "security",
+    "settings",
+    "site",
+    "sponsors",
+    "topics",
+    "trending",
+];
+
 /// Parse a repository identifier into `(owner, repo)`.
 ///
-/// Accepts `"owner/repo"`, full GitHub URLs, and `.git` suffixed URLs.
+/// Accepts `"owner/repo"`, full GitHub URLs, and `.git` suffixed URLs; any
+/// number of trailing slashes is ignored. The original case is kept.
+/// Site pages such as `github.com/orgs/...` are rejected.
 pub fn parse_repo(repository: &str) -> Result<(&str, &str), GitHubError> {
     let stripped = repository
         .strip_prefix("https://github.com/")
@@ -47,14 +82,28 @@ pub fn parse_repo(repository: &str) -> Result<(&str, &str), GitHubError> {
     if parts.len() < 2 || !is_valid_github_name(parts[0]) || !is_valid_github_name(parts[1]) {
         return Err(GitHubError::InvalidRepo(repository.to_string()));
     }
+    if RESERVED_OWNERS
+        .iter()
+        .any(|r| parts[0].eq_ignore_ascii_case(r))
+    {
+        return Err(GitHubError::NotARepository(repository.to_string()));
+    }
     Ok((parts[0], parts[1]))
 }
 
 /// Validate a git ref (branch, tag, or SHA).
 ///
-/// Rejects empty, control characters, and `..` sequences (git-check-ref-format).
+/// Supported forms are what the GitHub API resolves: branch and tag names
+/// (slashes allowed), full or abbreviated commit SHAs, and `HEAD` (the
+/// default branch). Rejects empty, control characters, and `..` sequences
+/// (git-check-ref-format). Reflog syntax such as `main@{yesterday}` is
+/// rejected with its own error, since only a local clone can resolve it.
 pub fn validate_ref(ref_: &str) -> Result<(), GitHubError> {
+    if ref_.contains("@{") {
+        return Err(GitHubError::ReflogRef(ref_.to_string()));
+    }
     if ref_.is_empty()
+        || ref_ == "@"
         || ref_.contains(['\0', '\n', '\r', ' ', '~', '^', ':', '\\', '*', '?', '['])
         || ref_.contains("..")
         || ref_.ends_with('.')
@@ -68,11 +117,13 @@ pub fn validate_ref(ref_: &str) -> Result<(), GitHubError> {
 /// Validate a file path within a repository.
 ///
 /// Rejects empty, absolute paths, control characters, and `..` path traversal.
+/// `.` segments are rejected too: URL parsing drops them, so the request would
+/// name a different path than the one shown.
 pub fn validate_path(path: &str) -> Result<(), GitHubError> {
     if path.is_empty()
         || path.starts_with('/')
         || path.contains(['\0', '\n', '\r'])
-        || path.split('/').any(|s| s == "..")
+        || path.split('/').any(|s| s == ".." || s == ".")
     {
         return Err(GitHubError::InvalidPath(path.to_string()));
     }
@@ -85,10 +136,58 @@ pub fn decode_content(encoded: &str) -> Result<String, GitHubError> {
     let bytes = STANDARD
         .decode(&clean)
         .map_err(|e| GitHubError::Decode(e.to_string()))?;
+    bytes_to_text(bytes)
+}
+
+/// Bytes sampled by [`looks_binary`]; git's own binary check reads the same.
+const BINARY_SNIFF_BYTES: usize = 8000;
+
+/// File bytes as text, rejecting binaries up front so a large blob never
+/// reaches the UTF-8 check.
+pub(crate) fn bytes_to_text(bytes: Vec<u8>) -> Result<String, GitHubError> {
+    if looks_binary(&bytes) {
+        return Err(GitHubError::Decode(format!(
+            "file appears to be binary ({} bytes)",
+            bytes.len()
+        )));
+    }
     String::from_utf8(bytes)
         .map_err(|_| GitHubError::Decode("file appears to be binary (not valid UTF-8)".into()))
 }
 
+/// A NUL byte, or more than 10% control bytes other than whitespace and
+/// escape, in the first [`BINARY_SNIFF_BYTES`].
+fn looks_binary(bytes: &[u8]) -> bool {
+    let sample = &bytes[..bytes.len().min(BINARY_SNIFF_BYTES)];
+    if sample.contains(&0) {
+        return true;
+    }
+    let control = sample
+        .iter()
+        .filter(|&&b| (b < 0x20 && !matches!(b, b'\t' | b'\n' | b'\r' | 0x0c | 0x1b)) || b == 0x7f)
+        .count();
+    control * 10 > sample.len()
+}
+
+/// Declared size of the real file when `content` is a Git LFS pointer
+/// (`version https://git-lfs.github.com/spec/v1`, then `oid` and `size` lines).
+pub fn lfs_pointer_size(content: &str) -> Option<u64> {
+    let mut lines = content.lines();
+    if lines.next()?.trim_end() != "version https://git-lfs.github.com/spec/v1" {
+        return None;
+    }
+    let mut has_oid = false;
+    let mut size = None;
+    for line in lines {
+        if let Some(oid) = line.strip_prefix("oid ") {
+            has_oid = oid.contains(':');
+        } else if let Some(n) = line.strip_prefix("size ") {
+            size = n.trim().parse().ok();
+        }
+    }
+    size.filter(|_| has_oid)
+}
+
 /// Parse a line range string: `"1-80"` (range), `"50-"` (open end), `"100"` (first N lines).
 pub fn parse_line_range(range: &str) -> Result<(usize, Option<usize>), GitHubError> {
     let range = range.trim();
@@ -140,7 +239,143 @@ pub fn apply_line_range(content: &str, start: usize, end: Option<usize>) -> Stri
         .join("\n")
 }
 
+/// Extract a line range from content as a code fence tagged with the
+/// language inferred from `path`, without line numbers.
+pub fn fence_line_range(content: &str, path: &str, start: usize, end: Option<usize>) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let total = lines.len();
+    let start_idx = start.saturating_sub(1);
+    let end_idx = end.map(|e| e.min(total)).unwrap_or(total);
+
+    if start_idx >= total {
+        return format!("(file has {total} lines, requested start at {start})");
+    }
+
+    fenced(&lines[start_idx..end_idx].join("\n"), fence_language(path))
+}
+
+/// Code-fence info string for a file path, or `""` when unknown.
+fn fence_language(path: &str) -> &'static str {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    match name {
+        "Dockerfile" | "Containerfile" => return "dockerfile",
+        "Makefile" | "GNUmakefile" => return "makefile",
+        _ => {}
+    }
+    let Some((_, ext)) = name.rsplit_once('.') else {
+        return "";
+    };
+    match ext.to_ascii_lowercase().as_str() {
+        "rs" => "rust",
+        "py" | "pyi" => "python",
+        "js" | "mjs" | "cjs" => "javascript",
+        "jsx" => "jsx",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "tsx",
+        "go" => "go",
+        "java" => "java",
+        "kt" | "kts" => "kotlin",
+        "swift" => "swift",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" => "cpp",
+        "cs" => "csharp",
+        "rb" => "ruby",
+        "php" => "php",
+        "sh" | "bash" | "zsh" => "bash",
+        "toml" => "toml",
+        "yaml" | "yml" => "yaml",
+        "json" => "json",
+        "md" | "markdown" => "markdown",
+        "html" | "htm" => "html",
+        "css" => "css",
+        "scss" => "scss",
+        "sql" => "sql",
+        "vue" => "vue",
+        "svelte" => "svelte",
+        "lua" => "lua",
+        "zig" => "zig",
+        "ex" | "exs" => "elixir",
+        "hs" => "haskell",
+        "xml" => "xml",
+        _ => "",
+    }
+}
+
+/// Locate a marker-delimited block as a 1-based line range.
+///
+/// Starts at the first line containing `start` and ends at the first later
+/// line containing `end`. Returns `None` when `start` is absent; the end is
+/// `None` when no later line contains `end`.
+pub fn find_marker_range(content: &str, start: &str, end: &str) -> Option<(usize, Option<usize>)> {
+    let mut lines = content.lines().enumerate();
+    let (start_idx, _) = lines.find(|(_, line)| line.contains(start))?;
+    let end_line = lines
+        .find(|(_, line)| line.contains(end))
+        .map(|(i, _)| i + 1);
+    Some((start_idx + 1, end_line))
+}
+
+/// Pick the tag that best matches a partial version such as `v1.2`.
+///
+/// A tag matches when its numeric components start with the requested ones
+/// (`v1.2` matches `v1.2.0` and `1.2.3`, not `v1.20.0`); a leading `v` is
+/// ignored on both sides. The highest match wins, preferring releases over
+/// pre-releases. Returns `None` when `requested` is not a version.
+pub fn resolve_version_tag<'a>(
+    requested: &str,
+    tags: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let (want, _) = parse_version(requested)?;
+    tags.into_iter()
+        .filter(|tag| *tag != requested)
+        .filter_map(|tag| {
+            let (parts, prerelease) = parse_version(tag)?;
+            parts
+                .starts_with(&want)
+                .then_some(((!prerelease, parts), tag))
+        })
+        // Ties (e.g. two pre-releases of one version) go to the first listed.
+        .min_by(|a, b| b.0.cmp(&a.0))
+        .map(|(_, tag)| tag)
+}
+
+/// Whether `ref_` reads as a version number (`v1.2`, `2.0.0-rc.1`).
+pub fn looks_like_version(ref_: &str) -> bool {
+    parse_version(ref_).is_some()
+}
+
+/// Split `v1.2.3-rc.1` into `([1, 2, 3], true)`. Up to four numeric parts.
+fn parse_version(s: &str) -> Option<(Vec<u64>, bool)> {
+    let s = s.strip_prefix(['v', 'V']).unwrap_or(s);
+    let (core, suffix) = match s.find(['-', '+']) {
+        Some(i) => (&s[..i], Some(&s[i..])),
+        None => (s, None),
+    };
+    let parts = core
+        .split('.')
+        .map(|p| p.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    if parts.len() > 4 {
+        return None;
+    }
+    Some((parts, suffix.is_some_and(|s| s.starts_with('-'))))
+}
+
+/// Whether `path` is the entry `prefix` names or lies under it as a directory:
+/// `src` and `src/` match `src` and `src/main.rs`, not `src2/main.rs`.
+fn is_under_prefix(path: &str, prefix: &str) -> bool {
+    let dir = prefix.trim_end_matches('/');
+    dir.is_empty()
+        || path
+            .strip_prefix(dir)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
 /// Filter tree entries to blobs matching an optional path prefix and glob pattern.
+///
+/// A pattern containing `/` is matched against the full path, with `*` kept
+/// within one segment and `**` spanning directories (`src/**/*.rs`); any other
+/// pattern is matched against the filename alone (`*.rs`).
 pub fn filter_tree_entries<'a>(
     entries: &'a [TreeEntry],
     path: Option<&str>,
@@ -148,18 +383,23 @@ pub fn filter_tree_entries<'a>(
 ) -> Result<Vec<&'a TreeEntry>, GitHubError> {
     let matcher = pattern
         .map(|p| {
-            Glob::new(p)
+            GlobBuilder::new(p)
+                .literal_separator(true)
+                .build()
                 .map_err(|e| GitHubError::InvalidPattern(e.to_string()))
-                .map(|g| g.compile_matcher())
+                .map(|g| (g.compile_matcher(), p.contains('/')))
         })
         .transpose()?;
 
     Ok(entries
         .iter()
         .filter(|e| e.entry_type == EntryType::Blob)
-        .filter(|e| path.is_none_or(|prefix| e.path.starts_with(prefix)))
+        .filter(|e| path.is_none_or(|prefix| is_under_prefix(&e.path, prefix)))
         .filter(|e| {
-            matcher.as_ref().is_none_or(|m| {
+            matcher.as_ref().is_none_or(|(m, full_path)| {
+                if *full_path {
+                    return m.is_match(&e.path);
+                }
                 let filename = e.path.rsplit('/').next().unwrap_or(&e.path);
                 m.is_match(filename)
             })
@@ -172,6 +412,19 @@ mod tests {
     use super::*;
     use base64::engine::general_purpose::STANDARD;
 
+    #[test]
+    fn lfs_pointer_size_reads_canonical_pointer() {
+        let pointer = "version https://git-lfs.github.com/spec/v1\n\
+                       oid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\n\
+                       size 12345\n";
+        assert_eq!(lfs_pointer_size(pointer), Some(12345));
+        assert_eq!(
+            lfs_pointer_size("version https://git-lfs.github.com/spec/v1\nsize 1\n"),
+            None
+        );
+        assert_eq!(lfs_pointer_size("fn main() {}\n"), None);
+    }
+
     #[test]
     fn parse_repo_valid_formats() {
         for (input, owner, repo) in [
@@ -191,6 +444,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_repo_ignores_repeated_trailing_slashes() {
+        let canonical = parse_repo("owner/repo").unwrap();
+        for input in [
+            "owner/repo//",
+            "Owner/Repo",
+            "https://github.com/OWNER/repo.git///",
+        ] {
+            let (owner, repo) =
+                parse_repo(input).unwrap_or_else(|_| panic!("should parse: {input}"));
+            assert!(
+                !owner.ends_with('/') && !repo.ends_with('/'),
+                "input: {input}"
+            );
+            assert_eq!(
+                (owner.to_ascii_lowercase(), repo.to_ascii_lowercase()),
+                (canonical.0.to_string(), canonical.1.to_string()),
+                "input: {input}"
+            );
+        }
+        assert_eq!(parse_repo("Owner/Repo//").unwrap(), ("Owner", "Repo"));
+    }
+
     #[test]
     fn parse_repo_rejects_invalid() {
         for input in [
@@ -206,9 +482,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_repo_rejects_reserved_paths() {
+        for input in [
+            "https://github.com/orgs/rust-lang/repositories",
+            "https://github.com/features/copilot",
+            "https://github.com/settings/tokens",
+            "https://github.com/sponsors/someone",
+            "https://github.com/marketplace/actions/checkout",
+            "https://github.com/About/careers",
+        ] {
+            assert!(
+                matches!(parse_repo(input), Err(GitHubError::NotARepository(_))),
+                "should reject: {input}"
+            );
+        }
+        // Reserved words are fine in the repo position.
+        assert_eq!(
+            parse_repo("https://github.com/acme/features").unwrap(),
+            ("acme", "features")
+        );
+    }
+
     #[test]
     fn validate_ref_valid() {
-        assert!(validate_ref("feature/my-branch").is_ok());
+        for input in ["feature/my-branch", "main", "HEAD", "v1.2.3", "a1b2c3d"] {
+            assert!(validate_ref(input).is_ok(), "should accept ref: {input}");
+        }
+    }
+
+    #[test]
+    fn validate_ref_rejects_reflog_syntax_with_guidance() {
+        for input in ["main@{1.day.ago}", "main@{yesterday}", "@{-1}", "HEAD@{2}"] {
+            let err = validate_ref(input).unwrap_err();
+            assert!(
+                matches!(err, GitHubError::ReflogRef(ref r) if r == input),
+                "got: {err:?}"
+            );
+            assert!(err.to_string().contains("commit SHA"), "got: {err}");
+        }
     }
 
     #[test]
@@ -228,6 +540,7 @@ mod tests {
             "ref[bracket",
             "branch.",
             "refs/heads/main.lock",
+            "@",
         ] {
             assert!(validate_ref(input).is_err(), "should reject ref: {input}");
         }
@@ -239,6 +552,8 @@ mod tests {
             "src/lib.rs",
             ".github/workflows/ci.yml",
             "path/to/file..name",
+            "docs/日本語.md",
+            ".github/.keep",
         ] {
             assert!(validate_path(input).is_ok(), "should accept path: {input}");
         }
@@ -252,6 +567,8 @@ mod tests {
             "../etc/passwd",
             "src/../../secret",
             "a/..",
+            "./README.md",
+            "docs/./guide.md",
         ] {
             assert!(validate_path(input).is_err(), "should reject path: {input}");
         }
@@ -276,6 +593,7 @@ mod tests {
         let encoded = encode_path("docs/日本語.md");
         assert!(encoded.starts_with("docs/"));
         assert!(!encoded.contains("日本語"));
+        assert_eq!(encode_path(r"dir\file.md"), "dir%5Cfile.md");
     }
 
     #[test]
@@ -292,6 +610,31 @@ mod tests {
         }
     }
 
+    const MARKED: &str =
+        "use a;\n// BEGIN helpers\nfn one() {}\nfn two() {}\n// END helpers\nfn main() {}";
+
+    #[test]
+    fn find_marker_range_found() {
+        assert_eq!(
+            find_marker_range(MARKED, "BEGIN helpers", "END helpers"),
+            Some((2, Some(5)))
+        );
+        // The end marker must be on a later line than the start.
+        assert_eq!(
+            find_marker_range(MARKED, "fn one", "fn"),
+            Some((3, Some(4)))
+        );
+    }
+
+    #[test]
+    fn find_marker_range_missing_markers() {
+        assert_eq!(find_marker_range(MARKED, "BEGIN other", "END other"), None);
+        assert_eq!(
+            find_marker_range(MARKED, "BEGIN helpers", "END other"),
+            Some((2, None))
+        );
+    }
+
     #[test]
     fn apply_line_range_subset() {
         let result = apply_line_range("line1\nline2\nline3\nline4\nline5", 2, Some(4));
@@ -322,6 +665,73 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decode_content_rejects_binary_with_size() {
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\0\x10\0\0\0\x10\x08\x06\0\0\0";
+        let err = decode_content(&STANDARD.encode(png)).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains(&format!("appears to be binary ({} bytes)", png.len())),
+            "got: {err}"
+        );
+    }
+
+    #[test]
+    fn decode_content_keeps_text_with_occasional_control_chars() {
+        let text = "\x1b[31mred\x1b[0m\n\x0cpage two\tcol\r\n\x07done\n";
+        assert_eq!(decode_content(&STANDARD.encode(text)).unwrap(), text);
+    }
+
+    #[test]
+    fn fence_line_range_tags_language_without_numbers() {
+        let content = "fn a() {}\nfn b() {}\nfn c() {}";
+        assert_eq!(
+            fence_line_range(content, "src/lib.rs", 2, Some(3)),
+            "```rust\nfn b() {}\nfn c() {}\n```"
+        );
+        assert_eq!(
+            fence_line_range("FROM rust", "docker/Dockerfile", 1, None),
+            "```dockerfile\nFROM rust\n```"
+        );
+        assert_eq!(fence_line_range("x", "LICENSE", 1, None), "```\nx\n```");
+        assert_eq!(
+            fence_line_range("x", "a.rs", 5, None),
+            "(file has 1 lines, requested start at 5)"
+        );
+    }
+
+    #[test]
+    fn resolve_version_tag_picks_highest_matching_release() {
+        let tags = [
+            "v1.10.0",
+            "v1.2.4-rc.1",
+            "v1.2.3",
+            "v1.2.1",
+            "v1.3.0",
+            "nightly",
+        ];
+        assert_eq!(resolve_version_tag("v1.2", tags), Some("v1.2.3"));
+        assert_eq!(resolve_version_tag("1.2", tags), Some("v1.2.3"));
+        assert_eq!(resolve_version_tag("v1", tags), Some("v1.10.0"));
+        assert_eq!(resolve_version_tag("v1.4", tags), None);
+    }
+
+    #[test]
+    fn resolve_version_tag_falls_back_to_prerelease() {
+        assert_eq!(
+            resolve_version_tag("v2.0", ["v2.0.0-beta.2", "v2.0.0-beta.1", "v1.9.0"]),
+            Some("v2.0.0-beta.2")
+        );
+    }
+
+    #[test]
+    fn resolve_version_tag_ignores_non_versions() {
+        assert_eq!(resolve_version_tag("main", ["v1.0.0"]), None);
+        assert_eq!(resolve_version_tag("release-1.2", ["v1.2.0"]), None);
+        assert!(looks_like_version("v1.2"));
+        assert!(!looks_like_version("main"));
+    }
+
     fn blob(path: &str) -> TreeEntry {
         TreeEntry {
             path: path.into(),
@@ -346,6 +756,27 @@ mod tests {
         assert_eq!(filtered[0].path, "src/main.rs");
     }
 
+    #[test]
+    fn filter_by_path_prefix_stops_at_directory_boundary() {
+        let entries = vec![
+            blob("src/main.rs"),
+            blob("src2/main.rs"),
+            blob("srcfile.txt"),
+            blob("docs/guide.md"),
+        ];
+        let paths = |prefix| -> Vec<&str> {
+            filter_tree_entries(&entries, Some(prefix), None)
+                .unwrap()
+                .into_iter()
+                .map(|e| e.path.as_str())
+                .collect()
+        };
+        assert_eq!(paths("src"), ["src/main.rs"]);
+        assert_eq!(paths("src/"), ["src/main.rs"]);
+        assert_eq!(paths("srcfile.txt"), ["srcfile.txt"]);
+        assert_eq!(paths("docs/guide.md"), ["docs/guide.md"]);
+    }
+
     #[test]
     fn filter_by_glob_pattern() {
         let entries = vec![blob("src/main.rs"), blob("src/lib.ts"), blob("README.md")];
@@ -354,6 +785,39 @@ mod tests {
         assert_eq!(filtered[0].path, "src/main.rs");
     }
 
+    #[test]
+    fn filter_by_path_glob_spans_directories() {
+        let entries = vec![
+            blob("src/main.rs"),
+            blob("src/github/helpers.rs"),
+            blob("tests/test_api.rs"),
+            blob("test_root.rs"),
+            blob("src/lib.ts"),
+        ];
+        let paths = |pattern| -> Vec<&str> {
+            filter_tree_entries(&entries, None, Some(pattern))
+                .unwrap()
+                .into_iter()
+                .map(|e| e.path.as_str())
+                .collect()
+        };
+        assert_eq!(
+            paths("src/**/*.rs"),
+            ["src/main.rs", "src/github/helpers.rs"]
+        );
+        assert_eq!(paths("src/*.rs"), ["src/main.rs"]);
+        assert_eq!(paths("**/test_*.rs"), ["tests/test_api.rs", "test_root.rs"]);
+        assert_eq!(
+            paths("*.rs"),
+            [
+                "src/main.rs",
+                "src/github/helpers.rs",
+                "tests/test_api.rs",
+                "test_root.rs"
+            ]
+        );
+    }
+
     #[test]
     fn filter_excludes_tree_entries() {
         let entries = vec![tree("src"), blob("src/main.rs")];
@@ -1,29 +1,58 @@
+pub(crate) mod dates;
 pub(crate) mod format;
+mod graphql;
 mod helpers;
 pub(crate) mod types;
 
-use helpers::encode_path;
+pub(crate) use graphql::{Overview, OverviewSections};
 pub use helpers::{
-    apply_line_range, decode_content, filter_tree_entries, parse_line_range, parse_repo,
+    apply_line_range, decode_content, fence_line_range, filter_tree_entries, find_marker_range,
+    lfs_pointer_size, looks_like_version, parse_line_range, parse_repo, resolve_version_tag,
     validate_path, validate_ref,
 };
+use helpers::{bytes_to_text, encode_path};
 
+use std::collections::BTreeMap;
 use std::env;
-use std::time::Duration;
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use futures::future::join_all;
 use reqwest::Client;
-use tracing::{debug, info};
+use tokio::sync::{OnceCell, Semaphore};
+use tracing::{debug, info, warn};
 
+use crate::env::parse_bool_env;
 use crate::redacted::Redacted;
 
 use types::{
-    BlobResponse, ContentsResponse, IssueInfo, PullInfo, ReleaseInfo, RepoInfo, TreeResponse,
+    BlobResponse, BranchInfo, CodeSearchResponse, CommitInfo, CompareResponse, ContentEntry,
+    ContentsListing, ContentsResponse, IssueComment, IssueDetail, IssueInfo, PullDetail, PullFile,
+    PullInfo, ReleaseInfo, RepoInfo, SearchIssuesResponse, TagInfo, TreeEntry, TreeResponse,
+    WorkflowRunsResponse,
 };
 
 const API_BASE: &str = "https://api.github.com";
 const TOKEN_RESOLVE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Default pause before the single retry of a failed `gh auth token`;
+/// `SCOUT_GH_TOKEN_RETRY_MS` overrides it, and `0` disables the retry.
+const DEFAULT_GH_TOKEN_RETRY_DELAY: Duration = Duration::from_millis(300);
+const JSON_MEDIA_TYPE: &str = "application/vnd.github+json";
+/// Contents API media type that returns file bodies directly instead of base64 JSON.
+const RAW_MEDIA_TYPE: &str = "application/vnd.github.raw+json";
+/// Raw file body only, for files too large for the JSON representation.
+const RAW_ONLY_MEDIA_TYPE: &str = "application/vnd.github.raw";
+/// Largest file body read through the raw media type (GitHub's own limit).
+const MAX_RAW_CONTENT_BYTES: usize = 100 * 1024 * 1024;
+/// Search media type that adds `text_matches` fragments to each result.
+const TEXT_MATCH_MEDIA_TYPE: &str = "application/vnd.github.text-match+json";
+/// Concurrent file reads per client. Bulk reads above this tend to trip
+/// GitHub's secondary rate limit; override with `SCOUT_GITHUB_CONCURRENCY`.
+const DEFAULT_READ_CONCURRENCY: usize = 4;
+const MAX_READ_CONCURRENCY: usize = 16;
 
-use crate::retry::{is_transient_network, retry_with};
+use crate::retry::{is_transient_network, retry_with_hint};
 
 #[derive(Debug, thiserror::Error)]
 pub enum GitHubError {
@@ -33,7 +62,10 @@ pub enum GitHubError {
     #[error(
         "GitHub API rate limit exceeded. Set GITHUB_TOKEN or run `gh auth login` for higher limits."
     )]
-    RateLimited,
+    RateLimited {
+        /// Unix time at which the quota resets, from `x-ratelimit-reset`.
+        reset_epoch: Option<u64>,
+    },
 
     #[error("Access denied: {0}")]
     Forbidden(String),
@@ -41,15 +73,31 @@ pub enum GitHubError {
     #[error("GitHub API error ({code}): {message}")]
     Api { code: u16, message: String },
 
+    /// A 2xx response with nothing in it, typically a proxy or CDN hiccup.
+    #[error("GitHub returned an empty response body")]
+    EmptyResponse,
+
+    /// The GraphQL API answered, but with `errors` or without the data asked for.
+    #[error("GitHub GraphQL error: {0}")]
+    GraphQl(String),
+
     #[error("Network error: {0}")]
     Network(#[from] reqwest::Error),
 
     #[error("Invalid repository format: expected 'owner/repo', got '{0}'")]
     InvalidRepo(String),
 
+    #[error("Not a repository URL: '{0}' is a GitHub site page, not 'owner/repo'")]
+    NotARepository(String),
+
     #[error("Invalid ref: {0}")]
     InvalidRef(String),
 
+    #[error(
+        "Unsupported ref '{0}': reflog/date syntax (`@{{...}}`) only exists in a local clone and the GitHub API cannot resolve it. Pass a branch, tag, or commit SHA instead (e.g. find the SHA with `git log --before=<date> -1` or on the repository's commits page)."
+    )]
+    ReflogRef(String),
+
     #[error("Invalid path: {0}")]
     InvalidPath(String),
 
@@ -61,34 +109,60 @@ pub enum GitHubError {
 
     #[error("Content decode error: {0}")]
     Decode(String),
+
+    #[error("File too large: over {0} bytes")]
+    TooLarge(usize),
+
+    #[error("Not a directory: '{path}' is a {kind}")]
+    NotADirectory { path: String, kind: String },
+
+    #[error("{0} requires authentication. Set GITHUB_TOKEN or run `gh auth login`.")]
+    AuthRequired(&'static str),
+
+    #[error(
+        "This organization requires SAML SSO: authorize your token for it at {0} (or re-run `gh auth login`), then retry."
+    )]
+    SsoRequired(String),
+}
+
+impl GitHubError {
+    /// How long until a rate-limited quota resets, when GitHub said.
+    pub(crate) fn rate_limit_reset_in(&self, now: SystemTime) -> Option<Duration> {
+        let Self::RateLimited {
+            reset_epoch: Some(reset),
+        } = self
+        else {
+            return None;
+        };
+        let reset = UNIX_EPOCH + Duration::from_secs(*reset);
+        Some(reset.duration_since(now).unwrap_or(Duration::ZERO))
+    }
 }
 
 /// HTTP client for the GitHub REST API v3.
 ///
 /// Auth resolution order: `GITHUB_TOKEN` env → `GH_TOKEN` env → `gh auth token` CLI → unauthenticated.
+/// An Enterprise base URL uses `GITHUB_ENTERPRISE_TOKEN` → `gh auth token --hostname` instead,
+/// then the generic variables only if `SCOUT_GITHUB_ENTERPRISE_GENERIC_TOKEN` is set.
 /// Owner/repo parameters are safe for direct URL interpolation because `parse_repo`
 /// restricts them to `[a-zA-Z0-9._-]`.
 #[derive(Clone)]
 pub struct GitHubClient {
     http: Client,
-    token: Option<Redacted>,
+    /// Resolved on the first request, so commands that never reach GitHub do
+    /// not pay for a `gh` subprocess.
+    token: Arc<OnceCell<Option<Redacted>>>,
     base_url: String,
+    read_limiter: Arc<Semaphore>,
 }
 
 impl GitHubClient {
-    pub async fn from_env(http: Client) -> Self {
-        let token = resolve_token().await;
-        if token.is_some() {
-            debug!("GitHub token configured");
-        } else {
-            info!(
-                "No GitHub token found. Rate limit: 60 req/hour. Set GITHUB_TOKEN or run `gh auth login`."
-            );
-        }
+    pub fn from_env(http: Client) -> Self {
         Self {
             http,
-            token,
-            base_url: API_BASE.to_string(),
+            token: Arc::new(OnceCell::new()),
+            base_url: api_base_with(|var| env::var(var).ok()),
+            read_limiter: Arc::new(Semaphore::new(read_concurrency())),
         }
     }
 
@@ -96,20 +170,68 @@ impl GitHubClient {
     pub(crate) fn with_base_url(http: Client, base_url: &str) -> Self {
         Self {
             http,
-            token: None,
-            base_url: base_url.to_string(),
+            token: Arc::new(OnceCell::new_with(Some(None))),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            read_limiter: Arc::new(Semaphore::new(DEFAULT_READ_CONCURRENCY)),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_token(mut self, token: &str) -> Self {
+        let token = Some(Redacted::new(token.to_string()));
+        self.token = Arc::new(OnceCell::new_with(Some(token)));
+        self
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_read_concurrency(mut self, limit: usize) -> Self {
+        self.read_limiter = Arc::new(Semaphore::new(limit));
+        self
+    }
+
+    pub(crate) async fn has_token(&self) -> bool {
+        self.token().await.is_some()
+    }
+
+    async fn token(&self) -> Option<&Redacted> {
+        self.token
+            .get_or_init(|| async {
+                let token = resolve_token(&self.base_url).await;
+                if token.is_some() {
+                    debug!("GitHub token configured");
+                } else {
+                    info!(
+                        "No GitHub token found. Rate limit: 60 req/hour. Set GITHUB_TOKEN or run `gh auth login`."
+                    );
+                }
+                token
+            })
+            .await
+            .as_ref()
+    }
+
+    /// Web origin matching the API host: `https://github.com`, or the
+    /// Enterprise host for an Enterprise `base_url`.
+    pub(crate) fn web_base(&self) -> String {
+        match enterprise_host(&self.base_url) {
+            Some(host) => format!("https://{host}"),
+            None => "https://github.com".to_string(),
         }
     }
 
-    fn request(&self, path: &str) -> reqwest::RequestBuilder {
+    async fn request(&self, path: &str, accept: &str) -> reqwest::RequestBuilder {
         let url = format!("{}{path}", self.base_url);
-        let mut req = self
-            .http
-            .get(&url)
-            .header("Accept", "application/vnd.github+json")
+        self.authorize(self.http.get(&url), &url)
+            .await
+            .header("Accept", accept)
+    }
+
+    /// Adds the headers every GitHub API request carries, plus the token if any.
+    async fn authorize(&self, req: reqwest::RequestBuilder, url: &str) -> reqwest::RequestBuilder {
+        let mut req = req
             .header("User-Agent", crate::USER_AGENT)
             .header("X-GitHub-Api-Version", "2022-11-28");
-        if let Some(ref token) = self.token {
+        if let Some(token) = self.token().await {
             assert!(
                 url.starts_with("https://") || cfg!(test),
                 "Bearer token must only be sent over HTTPS"
@@ -120,31 +242,47 @@ impl GitHubClient {
     }
 
     async fn get_json<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T, GitHubError> {
-        retry_with(
-            || self.get_json_once(path),
+        self.get_json_as(path, JSON_MEDIA_TYPE).await
+    }
+
+    async fn get_json_as<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        accept: &str,
+    ) -> Result<T, GitHubError> {
+        retry_with_hint(
+            || self.get_json_once(path, accept),
             is_retriable,
-            || GitHubError::RateLimited,
+            |e| e.rate_limit_reset_in(SystemTime::now()),
+            || GitHubError::RateLimited { reset_epoch: None },
         )
         .await
     }
 
-    async fn get_json_once<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T, GitHubError> {
+    async fn get_json_once<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        accept: &str,
+    ) -> Result<T, GitHubError> {
+        let body = self.send_once(path, accept).await?.bytes().await?;
+        parse_json_body(&body)
+    }
+
+    /// Single GET with GitHub status → error mapping; 2xx responses are returned as-is.
+    async fn send_once(&self, path: &str, accept: &str) -> Result<reqwest::Response, GitHubError> {
         debug!(path, "github API request");
-        let response = self.request(path).send().await?;
+        let response = self.request(path, accept).await.send().await?;
         let status = response.status();
         debug!(path, status = %status, "github API response");
         match status.as_u16() {
-            200..=299 => Ok(response.json().await?),
+            200..=299 => Ok(response),
             404 => Err(GitHubError::NotFound(path.to_string())),
-            429 => Err(GitHubError::RateLimited),
+            429 => Err(rate_limited(&response)),
             403 => {
-                let remaining = response
-                    .headers()
-                    .get("x-ratelimit-remaining")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|v| v.parse::<u64>().ok());
-                if remaining == Some(0) {
-                    Err(GitHubError::RateLimited)
+                if rate_limit_header(&response, "x-ratelimit-remaining") == Some(0) {
+                    Err(rate_limited(&response))
+                } else if let Some(url) = sso_authorization_url(&response) {
+                    Err(GitHubError::SsoRequired(url))
                 } else {
                     let message = extract_error_message(&response.text().await.unwrap_or_default());
                     Err(GitHubError::Forbidden(message))
@@ -182,6 +320,62 @@ impl GitHubClient {
         .await
     }
 
+    /// Fetch the recursive tree of directory `dir` at `ref_`, with paths
+    /// relative to the repository root, for when the root tree is truncated.
+    ///
+    /// Takes two requests: a non-recursive listing of the parent directory to
+    /// find the subtree SHA, then the subtree itself. Returns `None` when
+    /// `dir` is not a directory.
+    pub async fn get_subtree(
+        &self,
+        owner: &str,
+        repo: &str,
+        ref_: &str,
+        dir: &str,
+    ) -> Result<Option<TreeResponse>, GitHubError> {
+        let parent = dir.rsplit_once('/').map_or("", |(parent, _)| parent);
+        let listing = self.list_dir(owner, repo, parent, Some(ref_)).await?;
+        let Some(entry) = listing
+            .into_iter()
+            .find(|e| e.path == dir && e.entry_type == "dir")
+        else {
+            return Ok(None);
+        };
+
+        let subtree = self.get_tree(owner, repo, &entry.sha).await?;
+        Ok(Some(TreeResponse {
+            tree: subtree
+                .tree
+                .iter()
+                .map(|e| TreeEntry {
+                    path: format!("{dir}/{}", e.path),
+                    ..e.clone()
+                })
+                .collect(),
+            truncated: subtree.truncated,
+        }))
+    }
+
+    /// Immediate entries of directory `path` (`""` for the repository root).
+    pub async fn list_dir(
+        &self,
+        owner: &str,
+        repo: &str,
+        path: &str,
+        ref_: Option<&str>,
+    ) -> Result<Vec<ContentEntry>, GitHubError> {
+        match self
+            .get_json(&contents_path(owner, repo, path, ref_))
+            .await?
+        {
+            ContentsListing::Directory(entries) => Ok(entries),
+            ContentsListing::NotDirectory(entry) => Err(GitHubError::NotADirectory {
+                path: path.to_string(),
+                kind: entry.entry_type,
+            }),
+        }
+    }
+
     pub async fn get_contents(
         &self,
         owner: &str,
@@ -189,12 +383,7 @@ impl GitHubClient {
         path: &str,
         ref_: Option<&str>,
     ) -> Result<ContentsResponse, GitHubError> {
-        let path = encode_path(path);
-        let query = ref_
-            .map(|r| format!("?ref={}", encode_path(r)))
-            .unwrap_or_default();
-        self.get_json(&format!("/repos/{owner}/{repo}/contents/{path}{query}"))
-            .await
+        self.get_json(&contents_path(owner, repo, path, ref_)).await
     }
 
     pub async fn get_blob(
@@ -207,6 +396,120 @@ impl GitHubClient {
             .await
     }
 
+    /// Read a text file.
+    ///
+    /// Asks the Contents API for the raw body first, which skips the base64
+    /// round-trip. If the raw media type is refused (415) or ignored (a JSON
+    /// body comes back), falls back to base64 decoding. Files over 1 MB have
+    /// no base64 body; those are fetched with [`Self::get_raw_content`], and
+    /// only from the Blob API when that is refused too.
+    pub async fn read_file(
+        &self,
+        owner: &str,
+        repo: &str,
+        path: &str,
+        ref_: Option<&str>,
+    ) -> Result<String, GitHubError> {
+        let api_path = contents_path(owner, repo, path, ref_);
+        let contents = match self.get_raw(&api_path, RAW_MEDIA_TYPE).await {
+            Ok(RawContents::Text(text)) => return Ok(text),
+            Ok(RawContents::Json(contents)) => contents,
+            Err(GitHubError::Api { code: 415, .. }) => {
+                debug!(path, "raw media type not supported, using base64 contents");
+                self.get_contents(owner, repo, path, ref_).await?
+            }
+            Err(e) => return Err(e),
+        };
+        if let Some(ref encoded) = contents.content {
+            return decode_content(encoded);
+        }
+        match self.get_raw_content(owner, repo, path, ref_).await {
+            Err(GitHubError::Api { code: 415, .. }) => {
+                debug!(path, "raw download refused, using the Blob API");
+                let blob = self.get_blob(owner, repo, &contents.sha).await?;
+                decode_content(&blob.content)
+            }
+            result => result,
+        }
+    }
+
+    /// Download a file's bytes with the raw media type, up to
+    /// [`MAX_RAW_CONTENT_BYTES`], as UTF-8 text. A server that ignores the
+    /// media type is reported as a 415, like one that refuses it.
+    pub async fn get_raw_content(
+        &self,
+        owner: &str,
+        repo: &str,
+        path: &str,
+        ref_: Option<&str>,
+    ) -> Result<String, GitHubError> {
+        let api_path = contents_path(owner, repo, path, ref_);
+        match self.get_raw(&api_path, RAW_ONLY_MEDIA_TYPE).await? {
+            RawContents::Text(text) => Ok(text),
+            RawContents::Json(_) => Err(GitHubError::Api {
+                code: 415,
+                message: "raw media type not honored".into(),
+            }),
+        }
+    }
+
+    async fn get_raw(&self, path: &str, accept: &str) -> Result<RawContents, GitHubError> {
+        retry_with_hint(
+            || self.get_raw_once(path, accept),
+            is_retriable,
+            |e| e.rate_limit_reset_in(SystemTime::now()),
+            || GitHubError::RateLimited { reset_epoch: None },
+        )
+        .await
+    }
+
+    async fn get_raw_once(&self, path: &str, accept: &str) -> Result<RawContents, GitHubError> {
+        let mut response = self.send_once(path, accept).await?;
+        let is_json = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|ct| ct.contains("json"));
+        if is_json {
+            return Ok(RawContents::Json(response.json().await?));
+        }
+        if response
+            .content_length()
+            .is_some_and(|len| len > MAX_RAW_CONTENT_BYTES as u64)
+        {
+            return Err(GitHubError::TooLarge(MAX_RAW_CONTENT_BYTES));
+        }
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            bytes.extend_from_slice(&chunk);
+            if bytes.len() > MAX_RAW_CONTENT_BYTES {
+                return Err(GitHubError::TooLarge(MAX_RAW_CONTENT_BYTES));
+            }
+        }
+        bytes_to_text(bytes).map(RawContents::Text)
+    }
+
+    /// Read several files, at most `SCOUT_GITHUB_CONCURRENCY` at a time across
+    /// all callers sharing this client. Results keep the order of `paths`; a
+    /// failed read is reported in place without aborting the others.
+    pub async fn read_files<'a>(
+        &self,
+        owner: &str,
+        repo: &str,
+        paths: &[&'a str],
+        ref_: Option<&str>,
+    ) -> Vec<(&'a str, Result<String, GitHubError>)> {
+        join_all(paths.iter().map(|&path| async move {
+            let _permit = self
+                .read_limiter
+                .acquire()
+                .await
+                .expect("read limiter is never closed");
+            (path, self.read_file(owner, repo, path, ref_).await)
+        }))
+        .await
+    }
+
     pub async fn get_readme(
         &self,
         owner: &str,
@@ -216,6 +519,51 @@ impl GitHubClient {
             .await
     }
 
+    /// `text` rendered to HTML by `POST /markdown` in GFM mode, with issue
+    /// references and mentions resolved against `owner/repo`.
+    pub async fn render_markdown(
+        &self,
+        owner: &str,
+        repo: &str,
+        text: &str,
+    ) -> Result<String, GitHubError> {
+        let url = format!("{}/markdown", self.base_url);
+        debug!(owner, repo, "github markdown render request");
+        let response = self
+            .authorize(self.http.post(&url), &url)
+            .await
+            .header("Accept", "text/html")
+            .json(&serde_json::json!({
+                "text": text,
+                "mode": "gfm",
+                "context": format!("{owner}/{repo}"),
+            }))
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(GitHubError::Api {
+                code: status.as_u16(),
+                message: extract_error_message(&response.text().await.unwrap_or_default()),
+            });
+        }
+        let html = response.text().await?;
+        if html.trim().is_empty() {
+            return Err(GitHubError::EmptyResponse);
+        }
+        Ok(html)
+    }
+
+    /// Bytes of code per language, as detected by GitHub.
+    pub async fn get_languages(
+        &self,
+        owner: &str,
+        repo: &str,
+    ) -> Result<BTreeMap<String, u64>, GitHubError> {
+        self.get_json(&format!("/repos/{owner}/{repo}/languages"))
+            .await
+    }
+
     pub async fn get_issues(
         &self,
         owner: &str,
@@ -229,6 +577,31 @@ impl GitHubClient {
         .await
     }
 
+    pub async fn get_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<IssueDetail, GitHubError> {
+        self.get_json(&format!("/repos/{owner}/{repo}/issues/{number}"))
+            .await
+    }
+
+    /// The first `per_page` comments on an issue, oldest first.
+    pub async fn get_issue_comments(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        per_page: u8,
+    ) -> Result<Vec<IssueComment>, GitHubError> {
+        let per_page = per_page.min(100);
+        self.get_json(&format!(
+            "/repos/{owner}/{repo}/issues/{number}/comments?per_page={per_page}"
+        ))
+        .await
+    }
+
     pub async fn get_pulls(
         &self,
         owner: &str,
@@ -242,6 +615,31 @@ impl GitHubClient {
         .await
     }
 
+    pub async fn get_pull(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<PullDetail, GitHubError> {
+        self.get_json(&format!("/repos/{owner}/{repo}/pulls/{number}"))
+            .await
+    }
+
+    /// The first `per_page` files changed by a pull request.
+    pub async fn get_pull_files(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        per_page: u8,
+    ) -> Result<Vec<PullFile>, GitHubError> {
+        let per_page = per_page.min(100);
+        self.get_json(&format!(
+            "/repos/{owner}/{repo}/pulls/{number}/files?per_page={per_page}"
+        ))
+        .await
+    }
+
     pub async fn get_releases(
         &self,
         owner: &str,
@@ -254,6 +652,198 @@ impl GitHubClient {
         ))
         .await
     }
+
+    pub async fn get_branches(
+        &self,
+        owner: &str,
+        repo: &str,
+        per_page: u8,
+    ) -> Result<Vec<BranchInfo>, GitHubError> {
+        let per_page = per_page.min(100);
+        self.get_json(&format!(
+            "/repos/{owner}/{repo}/branches?per_page={per_page}"
+        ))
+        .await
+    }
+
+    pub async fn get_tags(
+        &self,
+        owner: &str,
+        repo: &str,
+        per_page: u8,
+    ) -> Result<Vec<TagInfo>, GitHubError> {
+        let per_page = per_page.min(100);
+        self.get_json(&format!("/repos/{owner}/{repo}/tags?per_page={per_page}"))
+            .await
+    }
+
+    pub async fn get_workflow_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        per_page: u8,
+    ) -> Result<WorkflowRunsResponse, GitHubError> {
+        let per_page = per_page.min(100);
+        let branch = encode_path(branch);
+        self.get_json(&format!(
+            "/repos/{owner}/{repo}/actions/runs?branch={branch}&per_page={per_page}"
+        ))
+        .await
+    }
+
+    /// Code search scoped to one repository, with text-match fragments.
+    /// GitHub only allows this endpoint for authenticated requests.
+    pub async fn search_code(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &str,
+        per_page: u8,
+    ) -> Result<CodeSearchResponse, GitHubError> {
+        if !self.has_token().await {
+            return Err(GitHubError::AuthRequired("GitHub code search"));
+        }
+        let per_page = per_page.min(100);
+        let q = encode_path(&format!("{query} repo:{owner}/{repo}"));
+        let path = format!("/search/code?q={q}&per_page={per_page}");
+        secondary_rate_limited(self.get_json_as(&path, TEXT_MATCH_MEDIA_TYPE).await)
+    }
+
+    /// Issues and pull requests in `owner/repo` matching `query`, which may
+    /// carry qualifiers such as `is:open` or `label:bug`.
+    pub async fn search_issues(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &str,
+        per_page: u8,
+    ) -> Result<SearchIssuesResponse, GitHubError> {
+        let per_page = per_page.min(100);
+        let q = encode_path(&format!("{query} repo:{owner}/{repo}"));
+        let path = format!("/search/issues?q={q}&per_page={per_page}");
+        secondary_rate_limited(self.get_json(&path).await)
+    }
+
+    /// Commits and changed files between `base` and `head`.
+    pub async fn compare_refs(
+        &self,
+        owner: &str,
+        repo: &str,
+        base: &str,
+        head: &str,
+    ) -> Result<CompareResponse, GitHubError> {
+        let (base, head) = (encode_path(base), encode_path(head));
+        self.get_json(&format!("/repos/{owner}/{repo}/compare/{base}...{head}"))
+            .await
+    }
+
+    /// Most recent commits on `ref_` (the default branch when `None`),
+    /// optionally only those touching `path`.
+    pub async fn get_commits(
+        &self,
+        owner: &str,
+        repo: &str,
+        ref_: Option<&str>,
+        path: Option<&str>,
+        per_page: u8,
+    ) -> Result<Vec<CommitInfo>, GitHubError> {
+        let per_page = per_page.min(100);
+        let mut query = format!("per_page={per_page}");
+        if let Some(r) = ref_ {
+            let _ = write!(query, "&sha={}", encode_path(r));
+        }
+        if let Some(p) = path {
+            let _ = write!(query, "&path={}", encode_path(p));
+        }
+        self.get_json(&format!("/repos/{owner}/{repo}/commits?{query}"))
+            .await
+    }
+
+    /// Whether `ref_` (branch, tag, or SHA) resolves to a commit. GitHub
+    /// answers an unknown ref with 404 or 422, both reported as `false`.
+    pub async fn ref_exists(
+        &self,
+        owner: &str,
+        repo: &str,
+        ref_: &str,
+    ) -> Result<bool, GitHubError> {
+        let ref_ = encode_path(ref_);
+        match self
+            .get_json::<serde::de::IgnoredAny>(&format!("/repos/{owner}/{repo}/commits/{ref_}"))
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(GitHubError::NotFound(_) | GitHubError::Api { code: 422, .. }) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Body of a raw-media-type contents request: the file itself, or the regular
+/// JSON representation when GitHub did not honor the raw media type.
+enum RawContents {
+    Text(String),
+    Json(ContentsResponse),
+}
+
+fn contents_path(owner: &str, repo: &str, path: &str, ref_: Option<&str>) -> String {
+    let path = encode_path(path);
+    let query = ref_
+        .map(|r| format!("?ref={}", encode_path(r)))
+        .unwrap_or_default();
+    format!("/repos/{owner}/{repo}/contents/{path}{query}")
+}
+
+fn parse_json_body<T: serde::de::DeserializeOwned>(body: &[u8]) -> Result<T, GitHubError> {
+    if body.trim_ascii().is_empty() {
+        return Err(GitHubError::EmptyResponse);
+    }
+    serde_json::from_slice(body).map_err(|e| GitHubError::Decode(e.to_string()))
+}
+
+/// API root from `GITHUB_API_URL` (as set by `gh` and GitHub Actions, e.g.
+/// `https://ghe.example.com/api/v3` on Enterprise Server), without a trailing
+/// slash. Tokens are only sent over HTTPS, so other schemes fall back to the
+/// public API.
+fn api_base_with(env_reader: impl Fn(&str) -> Option<String>) -> String {
+    let Some(raw) = env_reader("GITHUB_API_URL") else {
+        return API_BASE.to_string();
+    };
+    let url = raw.trim().trim_end_matches('/');
+    if url.is_empty() {
+        return API_BASE.to_string();
+    }
+    if !url.starts_with("https://") {
+        warn!(value = %raw, "GITHUB_API_URL must be an https:// URL, using the public API");
+        return API_BASE.to_string();
+    }
+    url.to_string()
+}
+
+/// Host of an Enterprise Server API root; `None` for the public API.
+fn enterprise_host(base_url: &str) -> Option<String> {
+    if base_url == API_BASE {
+        return None;
+    }
+    url::Url::parse(base_url).ok()?.host_str().map(String::from)
+}
+
+fn read_concurrency() -> usize {
+    read_concurrency_with(|var| env::var(var).ok())
+}
+
+fn read_concurrency_with(env_reader: impl Fn(&str) -> Option<String>) -> usize {
+    let Some(raw) = env_reader("SCOUT_GITHUB_CONCURRENCY") else {
+        return DEFAULT_READ_CONCURRENCY;
+    };
+    match raw.trim().parse::<usize>() {
+        Ok(n) if n > 0 => n.min(MAX_READ_CONCURRENCY),
+        _ => {
+            warn!(value = %raw, "invalid SCOUT_GITHUB_CONCURRENCY, using default");
+            DEFAULT_READ_CONCURRENCY
+        }
+    }
 }
 
 fn extract_error_message(body: &str) -> String {
@@ -263,10 +853,53 @@ fn extract_error_message(body: &str) -> String {
         .unwrap_or_else(|| body.chars().take(200).collect())
 }
 
+fn rate_limit_header(response: &reqwest::Response, name: &str) -> Option<u64> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+}
+
+/// Where to authorize the token when GitHub answers
+/// `X-GitHub-SSO: required; url=<url>`. The token settings page stands in if
+/// the header omits the URL.
+fn sso_authorization_url(response: &reqwest::Response) -> Option<String> {
+    let value = response.headers().get("x-github-sso")?.to_str().ok()?;
+    let mut parts = value.split(';').map(str::trim);
+    if !parts.next()?.eq_ignore_ascii_case("required") {
+        return None;
+    }
+    let url = parts
+        .find_map(|p| p.strip_prefix("url="))
+        .unwrap_or("https://github.com/settings/tokens");
+    Some(url.to_string())
+}
+
+fn rate_limited(response: &reqwest::Response) -> GitHubError {
+    GitHubError::RateLimited {
+        reset_epoch: rate_limit_header(response, "x-ratelimit-reset"),
+    }
+}
+
+/// The search endpoints have their own, stricter secondary limit, reported
+/// as a 403 whose quota headers still show requests remaining.
+fn secondary_rate_limited<T>(result: Result<T, GitHubError>) -> Result<T, GitHubError> {
+    match result {
+        Err(GitHubError::Forbidden(message))
+            if message.to_ascii_lowercase().contains("rate limit") =>
+        {
+            Err(GitHubError::RateLimited { reset_epoch: None })
+        }
+        result => result,
+    }
+}
+
 fn is_retriable(e: &GitHubError) -> bool {
     matches!(
         e,
-        GitHubError::RateLimited
+        GitHubError::RateLimited { .. }
+            | GitHubError::EmptyResponse
             | GitHubError::Api {
                 code: 500..=599,
                 ..
@@ -274,56 +907,204 @@ fn is_retriable(e: &GitHubError) -> bool {
     ) || matches!(e, GitHubError::Network(e) if is_transient_network(e))
 }
 
-async fn resolve_token() -> Option<Redacted> {
-    resolve_token_with(|var| env::var(var).ok()).await
+async fn resolve_token(base_url: &str) -> Option<Redacted> {
+    resolve_token_with(|var| env::var(var).ok(), base_url, run_gh).await
 }
 
-async fn resolve_token_with(env_reader: impl Fn(&str) -> Option<String>) -> Option<Redacted> {
-    let from_env = ["GITHUB_TOKEN", "GH_TOKEN"]
-        .iter()
-        .filter_map(|var| env_reader(var))
-        .map(|t| t.trim().to_string())
-        .find(|t| !t.is_empty());
-
-    if let Some(token) = from_env {
-        return Some(Redacted::new(token));
-    }
-
-    let output = tokio::time::timeout(
-        TOKEN_RESOLVE_TIMEOUT,
-        tokio::process::Command::new("gh")
-            .args(["auth", "token"])
-            .kill_on_drop(true)
-            .output(),
-    )
-    .await
-    .inspect_err(|_| info!("gh auth token timed out after {}s", TOKEN_RESOLVE_TIMEOUT.as_secs()))
-    .ok()?
-    .inspect_err(|e| info!("gh auth token command failed: {e}"))
-    .ok()?;
-
-    if !output.status.success() {
-        info!(
-            stderr = %String::from_utf8_lossy(&output.stderr).trim(),
-            "gh auth token failed"
-        );
-        return None;
-    }
+/// A token is only sent to the host it was issued for. `GITHUB_TOKEN` and
+/// `GH_TOKEN` are github.com tokens; an Enterprise `base_url` uses
+/// `GITHUB_ENTERPRISE_TOKEN`, then `gh`'s token for that host, and falls back
+/// to the generic variables only when `SCOUT_GITHUB_ENTERPRISE_GENERIC_TOKEN`
+/// opts in (e.g. Actions on Enterprise Server, where `GITHUB_TOKEN` is issued
+/// by that host).
+async fn resolve_token_with<F, Fut>(
+    env_reader: impl Fn(&str) -> Option<String>,
+    base_url: &str,
+    gh: F,
+) -> Option<Redacted>
+where
+    F: Fn(Vec<String>) -> Fut,
+    Fut: std::future::Future<Output = GhRun>,
+{
+    const GENERIC_VARS: &[&str] = &["GITHUB_TOKEN", "GH_TOKEN"];
+    let from_env = |vars: &[&str]| {
+        vars.iter()
+            .filter_map(|var| env_reader(var))
+            .map(|t| t.trim().to_string())
+            .find(|t| !t.is_empty())
+            .map(Redacted::new)
+    };
 
-    let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    if token.is_empty() {
-        None
+    let Some(host) = enterprise_host(base_url) else {
+        return match from_env(GENERIC_VARS) {
+            Some(token) => Some(token),
+            None => gh_token(&["auth", "token"], gh_retry_delay_with(&env_reader), gh).await,
+        };
+    };
+    if let Some(token) = from_env(&["GITHUB_ENTERPRISE_TOKEN"]) {
+        return Some(token);
+    }
+    let args = ["auth", "token", "--hostname", host.as_str()];
+    if let Some(token) = gh_token(&args, gh_retry_delay_with(&env_reader), gh).await {
+        return Some(token);
+    }
+    let generic_allowed =
+        parse_bool_env(&env_reader, "SCOUT_GITHUB_ENTERPRISE_GENERIC_TOKEN").unwrap_or(false);
+    if generic_allowed {
+        from_env(GENERIC_VARS)
     } else {
-        Some(Redacted::new(token))
+        None
     }
 }
 
-#[cfg(test)]
+/// Pause before retrying `gh auth token` from `SCOUT_GH_TOKEN_RETRY_MS`;
+/// `None` when the retry is disabled.
+fn gh_retry_delay_with(env_reader: impl Fn(&str) -> Option<String>) -> Option<Duration> {
+    let Some(raw) = env_reader("SCOUT_GH_TOKEN_RETRY_MS") else {
+        return Some(DEFAULT_GH_TOKEN_RETRY_DELAY);
+    };
+    match raw.trim().parse::<u64>() {
+        Ok(0) => None,
+        Ok(ms) => Some(Duration::from_millis(ms)),
+        Err(_) => {
+            warn!(value = %raw, "invalid SCOUT_GH_TOKEN_RETRY_MS, using default");
+            Some(DEFAULT_GH_TOKEN_RETRY_DELAY)
+        }
+    }
+}
+
+/// Outcome of one `gh` invocation.
+#[derive(Debug)]
+enum GhRun {
+    /// Exited successfully; trimmed stdout.
+    Output(String),
+    /// Could not be started (usually not installed). Not retried.
+    Unavailable(String),
+    /// Did not exit within [`TOKEN_RESOLVE_TIMEOUT`]. Not retried: a hung
+    /// `gh` rarely recovers within a second attempt.
+    TimedOut,
+    /// Non-zero exit; stderr.
+    Failed(String),
+}
+
+/// Whether `gh` failed only because no account is logged in, which is the
+/// normal state for an unauthenticated user rather than a transient fault.
+fn gh_not_logged_in(stderr: &str) -> bool {
+    let stderr = stderr.to_ascii_lowercase();
+    stderr.contains("no oauth token") || stderr.contains("not logged in")
+}
+
+/// `gh auth token`, retried once after `retry_delay` when it fails: a locked
+/// keychain makes it fail briefly, and a miss here leaves the whole session
+/// unauthenticated. A missing login or a timeout is not retried.
+async fn gh_token<F, Fut>(args: &[&str], retry_delay: Option<Duration>, run: F) -> Option<Redacted>
+where
+    F: Fn(Vec<String>) -> Fut,
+    Fut: std::future::Future<Output = GhRun>,
+{
+    let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+    let mut outcome = run(args.clone()).await;
+    if let (GhRun::Failed(reason), Some(delay)) = (&outcome, retry_delay)
+        && !gh_not_logged_in(reason)
+    {
+        debug!(%reason, "gh auth token failed, retrying");
+        tokio::time::sleep(delay).await;
+        outcome = run(args).await;
+    }
+    match outcome {
+        GhRun::Output(token) if !token.is_empty() => Some(Redacted::new(token)),
+        GhRun::Output(_) => None,
+        GhRun::Unavailable(e) => {
+            info!("gh auth token command failed: {e}");
+            None
+        }
+        GhRun::Failed(stderr) if gh_not_logged_in(&stderr) => {
+            debug!(%stderr, "gh has no token configured");
+            None
+        }
+        GhRun::TimedOut => {
+            warn!(
+                "gh auth token timed out after {}s; continuing without a GitHub token",
+                TOKEN_RESOLVE_TIMEOUT.as_secs()
+            );
+            None
+        }
+        GhRun::Failed(stderr) => {
+            warn!(%stderr, "gh auth token failed; continuing without a GitHub token");
+            None
+        }
+    }
+}
+
+async fn run_gh(args: Vec<String>) -> GhRun {
+    let mut gh = tokio::process::Command::new("gh");
+    gh.args(&args);
+    let output =
+        match tokio::time::timeout(TOKEN_RESOLVE_TIMEOUT, gh.kill_on_drop(true).output()).await {
+            Ok(Ok(output)) => output,
+            Ok(Err(e)) => return GhRun::Unavailable(e.to_string()),
+            Err(_) => return GhRun::TimedOut,
+        };
+    if output.status.success() {
+        GhRun::Output(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        GhRun::Failed(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+#[cfg(test)]
 mod http_tests {
     use super::*;
+    use std::sync::Mutex;
     use wiremock::matchers::{method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
+    #[test]
+    fn api_base_reads_enterprise_url_from_env() {
+        let env = |value: &'static str| move |_: &str| Some(value.to_string());
+        assert_eq!(api_base_with(|_| None), API_BASE);
+        assert_eq!(
+            api_base_with(env(" https://ghe.example.com/api/v3/ ")),
+            "https://ghe.example.com/api/v3"
+        );
+        assert_eq!(api_base_with(env("")), API_BASE);
+        assert_eq!(
+            api_base_with(env("http://ghe.example.com/api/v3")),
+            API_BASE
+        );
+    }
+
+    #[tokio::test]
+    async fn enterprise_base_url_prefixes_api_paths() {
+        use wiremock::matchers::header;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/repos/owner/repo"))
+            .and(header("Accept", JSON_MEDIA_TYPE))
+            .and(header("X-GitHub-Api-Version", "2022-11-28"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "full_name": "owner/repo",
+                "description": null,
+                "html_url": "https://ghe.example.com/owner/repo",
+                "default_branch": "trunk",
+                "language": null,
+                "stargazers_count": 0,
+                "forks_count": 0,
+                "open_issues_count": 0,
+                "topics": null,
+                "license": null
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let base = format!("{}/api/v3/", server.uri());
+        let client = GitHubClient::with_base_url(Client::new(), &base);
+        let repo = client.get_repo("owner", "repo").await.unwrap();
+        assert_eq!(repo.default_branch, "trunk");
+    }
+
     #[tokio::test]
     async fn get_json_404_returns_not_found() {
         let server = MockServer::start().await;
@@ -349,7 +1130,7 @@ mod http_tests {
 
         let client = GitHubClient::with_base_url(Client::new(), &server.uri());
         let result: Result<RepoInfo, _> = client.get_json("/repos/owner/repo").await;
-        assert!(matches!(result, Err(GitHubError::RateLimited)));
+        assert!(matches!(result, Err(GitHubError::RateLimited { .. })));
     }
 
     #[tokio::test]
@@ -367,7 +1148,65 @@ mod http_tests {
 
         let client = GitHubClient::with_base_url(Client::new(), &server.uri());
         let result: Result<RepoInfo, _> = client.get_json("/repos/owner/repo").await;
-        assert!(matches!(result, Err(GitHubError::RateLimited)));
+        assert!(matches!(result, Err(GitHubError::RateLimited { .. })));
+    }
+
+    #[tokio::test]
+    async fn rate_limited_error_carries_reset_time() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .append_header("x-ratelimit-remaining", "0")
+                    .append_header("x-ratelimit-reset", "4102444800"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::with_base_url(Client::new(), &server.uri());
+        let err = client
+            .get_json::<RepoInfo>("/repos/owner/repo")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            GitHubError::RateLimited {
+                reset_epoch: Some(4_102_444_800)
+            }
+        ));
+        let now = UNIX_EPOCH + Duration::from_secs(4_102_444_000);
+        assert_eq!(err.rate_limit_reset_in(now), Some(Duration::from_secs(800)));
+    }
+
+    #[tokio::test]
+    async fn get_json_403_with_sso_header_points_to_authorization() {
+        let sso_url = "https://github.com/orgs/acme/sso?authorization_request=abc123";
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/repo"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .append_header("x-ratelimit-remaining", "4999")
+                    .append_header("x-github-sso", format!("required; url={sso_url}").as_str())
+                    .set_body_json(serde_json::json!({
+                        "message": "Resource protected by organization SAML enforcement."
+                    })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::with_base_url(Client::new(), &server.uri());
+        let err = client
+            .get_json::<RepoInfo>("/repos/acme/repo")
+            .await
+            .unwrap_err();
+        assert!(matches!(&err, GitHubError::SsoRequired(url) if url == sso_url));
+        let message = err.to_string();
+        assert!(message.contains("SSO"), "got: {message}");
+        assert!(message.contains(&format!("authorize your token for it at {sso_url}")));
     }
 
     #[tokio::test]
@@ -388,15 +1227,24 @@ mod http_tests {
         assert!(matches!(result, Err(GitHubError::Forbidden(ref msg)) if msg == "access denied"));
     }
 
+    /// A `gh` runner for machines without `gh` installed.
+    fn no_gh(_: Vec<String>) -> std::future::Ready<GhRun> {
+        std::future::ready(GhRun::Unavailable("not installed".into()))
+    }
+
     #[tokio::test]
     async fn resolve_token_reads_env_var() {
-        let token = resolve_token_with(|key| {
-            if key == "GITHUB_TOKEN" {
-                Some("test-token-from-env".into())
-            } else {
-                None
-            }
-        })
+        let token = resolve_token_with(
+            |key| {
+                if key == "GITHUB_TOKEN" {
+                    Some("test-token-from-env".into())
+                } else {
+                    None
+                }
+            },
+            API_BASE,
+            no_gh,
+        )
         .await;
         assert_eq!(
             token.as_ref().map(|t| t.expose()),
@@ -404,6 +1252,554 @@ mod http_tests {
         );
     }
 
+    #[tokio::test]
+    async fn resolve_token_prefers_enterprise_token_for_enterprise_host() {
+        let env = |key: &str| match key {
+            "GITHUB_ENTERPRISE_TOKEN" => Some("ghe-token".to_string()),
+            "GITHUB_TOKEN" => Some("public-token".to_string()),
+            _ => None,
+        };
+        let token = resolve_token_with(env, "https://ghe.example.com/api/v3", no_gh).await;
+        assert_eq!(token.as_ref().map(|t| t.expose()), Some("ghe-token"));
+
+        let token = resolve_token_with(env, API_BASE, no_gh).await;
+        assert_eq!(token.as_ref().map(|t| t.expose()), Some("public-token"));
+    }
+
+    #[tokio::test]
+    async fn resolve_token_keeps_public_tokens_off_enterprise_hosts() {
+        let public_only = |key: &str| match key {
+            "GITHUB_TOKEN" => Some("public-token".to_string()),
+            "GH_TOKEN" => Some("gh-token".to_string()),
+            _ => None,
+        };
+        let token = resolve_token_with(public_only, "https://ghe.example.com/api/v3", no_gh).await;
+        assert!(token.is_none());
+
+        let host_gh = |args: Vec<String>| {
+            assert_eq!(args, ["auth", "token", "--hostname", "ghe.example.com"]);
+            std::future::ready(GhRun::Output("ghe-from-gh".into()))
+        };
+        let token =
+            resolve_token_with(public_only, "https://ghe.example.com/api/v3", host_gh).await;
+        assert_eq!(token.as_ref().map(|t| t.expose()), Some("ghe-from-gh"));
+    }
+
+    #[tokio::test]
+    async fn resolve_token_sends_generic_token_to_enterprise_only_on_opt_in() {
+        let opted_in = |key: &str| match key {
+            "GITHUB_TOKEN" => Some("actions-token".to_string()),
+            "SCOUT_GITHUB_ENTERPRISE_GENERIC_TOKEN" => Some("1".to_string()),
+            _ => None,
+        };
+        let token = resolve_token_with(opted_in, "https://ghe.example.com/api/v3", no_gh).await;
+        assert_eq!(token.as_ref().map(|t| t.expose()), Some("actions-token"));
+
+        // gh's token for the host still wins over the opted-in generic one.
+        let host_gh = |_: Vec<String>| std::future::ready(GhRun::Output("ghe-from-gh".into()));
+        let token = resolve_token_with(opted_in, "https://ghe.example.com/api/v3", host_gh).await;
+        assert_eq!(token.as_ref().map(|t| t.expose()), Some("ghe-from-gh"));
+    }
+
+    /// A `gh` runner that replays `outcomes` in order and counts its calls.
+    fn scripted_gh(
+        outcomes: Vec<GhRun>,
+    ) -> (
+        Arc<Mutex<usize>>,
+        impl Fn(Vec<String>) -> std::future::Ready<GhRun>,
+    ) {
+        let calls = Arc::new(Mutex::new(0));
+        let outcomes = Mutex::new(outcomes.into_iter());
+        let counter = Arc::clone(&calls);
+        let run = move |args: Vec<String>| {
+            assert_eq!(args, ["auth", "token"]);
+            *counter.lock().unwrap() += 1;
+            std::future::ready(outcomes.lock().unwrap().next().expect("unexpected gh call"))
+        };
+        (calls, run)
+    }
+
+    #[tokio::test]
+    async fn gh_token_retries_once_then_succeeds() {
+        let (calls, run) = scripted_gh(vec![
+            GhRun::Failed("keychain locked".into()),
+            GhRun::Output("gho_retry".into()),
+        ]);
+        let token = gh_token(&["auth", "token"], Some(Duration::ZERO), run).await;
+        assert_eq!(token.as_ref().map(|t| t.expose()), Some("gho_retry"));
+        assert_eq!(*calls.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn gh_token_gives_up_after_second_failure() {
+        let (calls, run) = scripted_gh(vec![
+            GhRun::Failed("keychain locked".into()),
+            GhRun::Failed("keychain locked".into()),
+        ]);
+        assert!(
+            gh_token(&["auth", "token"], Some(Duration::ZERO), run)
+                .await
+                .is_none()
+        );
+        assert_eq!(*calls.lock().unwrap(), 2);
+
+        let (calls, run) = scripted_gh(vec![GhRun::Unavailable("not found".into())]);
+        assert!(
+            gh_token(&["auth", "token"], Some(Duration::ZERO), run)
+                .await
+                .is_none()
+        );
+        assert_eq!(*calls.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn gh_token_does_not_retry_missing_login_or_timeout() {
+        for outcome in [
+            GhRun::Failed("no oauth token found for github.com".into()),
+            GhRun::Failed("You are not logged into any GitHub hosts.".into()),
+            GhRun::TimedOut,
+        ] {
+            let (calls, run) = scripted_gh(vec![outcome]);
+            assert!(
+                gh_token(&["auth", "token"], Some(Duration::ZERO), run)
+                    .await
+                    .is_none()
+            );
+            assert_eq!(*calls.lock().unwrap(), 1);
+        }
+
+        let (calls, run) = scripted_gh(vec![GhRun::Failed("keychain locked".into())]);
+        assert!(gh_token(&["auth", "token"], None, run).await.is_none());
+        assert_eq!(*calls.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn gh_retry_delay_parses_env_with_fallback() {
+        let env = |value: &'static str| move |_: &str| Some(value.to_string());
+        assert_eq!(
+            gh_retry_delay_with(|_| None),
+            Some(DEFAULT_GH_TOKEN_RETRY_DELAY)
+        );
+        assert_eq!(
+            gh_retry_delay_with(env("50")),
+            Some(Duration::from_millis(50))
+        );
+        assert_eq!(gh_retry_delay_with(env("0")), None);
+        assert_eq!(
+            gh_retry_delay_with(env("soon")),
+            Some(DEFAULT_GH_TOKEN_RETRY_DELAY)
+        );
+    }
+
+    #[test]
+    fn enterprise_host_is_none_for_public_api() {
+        assert_eq!(enterprise_host(API_BASE), None);
+        assert_eq!(
+            enterprise_host("https://ghe.example.com/api/v3").as_deref(),
+            Some("ghe.example.com")
+        );
+    }
+
+    #[test]
+    fn read_concurrency_parses_env_with_fallback() {
+        let env = |value: &'static str| move |_: &str| Some(value.to_string());
+        assert_eq!(read_concurrency_with(|_| None), DEFAULT_READ_CONCURRENCY);
+        assert_eq!(read_concurrency_with(env("8")), 8);
+        assert_eq!(read_concurrency_with(env("1000")), MAX_READ_CONCURRENCY);
+        assert_eq!(read_concurrency_with(env("0")), DEFAULT_READ_CONCURRENCY);
+        assert_eq!(read_concurrency_with(env("many")), DEFAULT_READ_CONCURRENCY);
+    }
+
+    #[tokio::test]
+    async fn search_code_requires_a_token() {
+        let client = GitHubClient::with_base_url(Client::new(), "http://localhost:0");
+        let result = client.search_code("owner", "repo", "parse_repo", 10).await;
+        assert!(matches!(result, Err(GitHubError::AuthRequired(_))));
+    }
+
+    #[tokio::test]
+    async fn search_code_scopes_query_and_requests_text_matches() {
+        use wiremock::matchers::{header, query_param};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/search/code"))
+            .and(query_param("q", "fn parse_repo repo:owner/repo"))
+            .and(query_param("per_page", "5"))
+            .and(header("Accept", TEXT_MATCH_MEDIA_TYPE))
+            .and(header("Authorization", "Bearer test-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "total_count": 1,
+                "incomplete_results": false,
+                "items": [{
+                    "path": "src/github/helpers.rs",
+                    "html_url": "https://github.com/owner/repo/blob/main/src/github/helpers.rs",
+                    "text_matches": [{"fragment": "pub fn parse_repo(repository: &str)"}]
+                }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client =
+            GitHubClient::with_base_url(Client::new(), &server.uri()).with_token("test-token");
+        let response = client
+            .search_code("owner", "repo", "fn parse_repo", 5)
+            .await
+            .unwrap();
+        assert_eq!(response.total_count, 1);
+        assert_eq!(response.items[0].path, "src/github/helpers.rs");
+        assert_eq!(
+            response.items[0].text_matches[0].fragment,
+            "pub fn parse_repo(repository: &str)"
+        );
+    }
+
+    #[tokio::test]
+    async fn search_code_secondary_limit_is_rate_limited() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/search/code"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .append_header("x-ratelimit-remaining", "9")
+                    .set_body_json(serde_json::json!({
+                        "message": "You have exceeded a secondary rate limit. Please wait a few minutes before you try again."
+                    })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client =
+            GitHubClient::with_base_url(Client::new(), &server.uri()).with_token("test-token");
+        let result = client.search_code("owner", "repo", "x", 10).await;
+        assert!(matches!(result, Err(GitHubError::RateLimited { .. })));
+    }
+
+    #[tokio::test]
+    async fn search_issues_secondary_limit_is_rate_limited() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/search/issues"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .append_header("x-ratelimit-remaining", "9")
+                    .set_body_json(serde_json::json!({
+                        "message": "You have exceeded a secondary rate limit."
+                    })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::with_base_url(Client::new(), &server.uri());
+        let result = client.search_issues("owner", "repo", "x", 10).await;
+        assert!(matches!(result, Err(GitHubError::RateLimited { .. })));
+    }
+
+    #[tokio::test]
+    async fn get_commits_passes_ref_and_path_filters() {
+        use wiremock::matchers::query_param;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/commits"))
+            .and(query_param("sha", "release/v1"))
+            .and(query_param("path", "src/main.rs"))
+            .and(query_param("per_page", "5"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::with_base_url(Client::new(), &server.uri());
+        let commits = client
+            .get_commits("owner", "repo", Some("release/v1"), Some("src/main.rs"), 5)
+            .await
+            .unwrap();
+        assert!(commits.is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_workflow_runs_filters_by_branch() {
+        use wiremock::matchers::query_param;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/actions/runs"))
+            .and(query_param("branch", "release/v1"))
+            .and(query_param("per_page", "5"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "total_count": 1,
+                "workflow_runs": [{
+                    "name": "CI",
+                    "html_url": "https://github.com/owner/repo/actions/runs/1",
+                    "head_sha": "0123456789abcdef0123456789abcdef01234567",
+                    "event": "push",
+                    "status": "completed",
+                    "conclusion": "failure",
+                    "created_at": "2026-01-15T10:00:00Z"
+                }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::with_base_url(Client::new(), &server.uri());
+        let runs = client
+            .get_workflow_runs("owner", "repo", "release/v1", 5)
+            .await
+            .unwrap();
+        assert_eq!(runs.workflow_runs.len(), 1);
+        assert_eq!(runs.workflow_runs[0].conclusion.as_deref(), Some("failure"));
+    }
+
+    fn contents_body(text: &str) -> serde_json::Value {
+        use base64::Engine as _;
+        serde_json::json!({
+            "sha": "abc",
+            "content": base64::engine::general_purpose::STANDARD.encode(text)
+        })
+    }
+
+    #[tokio::test]
+    async fn read_files_bounds_concurrency_and_isolates_errors() {
+        let delay = Duration::from_millis(150);
+        let server = MockServer::start().await;
+        for name in ["a", "b", "c"] {
+            Mock::given(method("GET"))
+                .and(path(format!("/repos/owner/repo/contents/{name}.txt")))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(contents_body(name))
+                        .set_delay(delay),
+                )
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/contents/missing.txt"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let client =
+            GitHubClient::with_base_url(Client::new(), &server.uri()).with_read_concurrency(1);
+        let start = std::time::Instant::now();
+        let results = client
+            .read_files(
+                "owner",
+                "repo",
+                &["a.txt", "missing.txt", "b.txt", "c.txt"],
+                None,
+            )
+            .await;
+
+        assert!(
+            start.elapsed() >= delay * 3,
+            "reads should be serialized with a limit of 1, took {:?}",
+            start.elapsed()
+        );
+        let paths: Vec<_> = results.iter().map(|(p, _)| *p).collect();
+        assert_eq!(paths, ["a.txt", "missing.txt", "b.txt", "c.txt"]);
+        assert_eq!(results[0].1.as_deref().unwrap(), "a");
+        assert!(matches!(results[1].1, Err(GitHubError::NotFound(_))));
+        assert_eq!(results[2].1.as_deref().unwrap(), "b");
+        assert_eq!(results[3].1.as_deref().unwrap(), "c");
+    }
+
+    #[tokio::test]
+    async fn read_file_prefers_raw_media_type() {
+        use wiremock::matchers::header;
+
+        let text = "fn main() {}\n// 日本語\n";
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/contents/src/main.rs"))
+            .and(header("accept", RAW_MEDIA_TYPE))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "application/vnd.github.raw")
+                    .set_body_string(text),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/contents/src/main.rs"))
+            .and(header("accept", JSON_MEDIA_TYPE))
+            .respond_with(ResponseTemplate::new(200).set_body_json(contents_body(text)))
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::with_base_url(Client::new(), &server.uri());
+        let raw = client
+            .read_file("owner", "repo", "src/main.rs", None)
+            .await
+            .unwrap();
+        let base64 = client
+            .get_contents("owner", "repo", "src/main.rs", None)
+            .await
+            .unwrap();
+
+        assert_eq!(raw, text);
+        assert_eq!(
+            raw,
+            decode_content(base64.content.as_deref().unwrap()).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn read_file_falls_back_to_base64_when_raw_unsupported() {
+        use wiremock::matchers::header;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/contents/a.txt"))
+            .and(header("accept", RAW_MEDIA_TYPE))
+            .respond_with(ResponseTemplate::new(415))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/contents/a.txt"))
+            .and(header("accept", JSON_MEDIA_TYPE))
+            .respond_with(ResponseTemplate::new(200).set_body_json(contents_body("hello")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::with_base_url(Client::new(), &server.uri());
+        let text = client
+            .read_file("owner", "repo", "a.txt", None)
+            .await
+            .unwrap();
+        assert_eq!(text, "hello");
+    }
+
+    /// Contents API for a file over 1 MB that ignores the raw media type:
+    /// JSON without a base64 body.
+    async fn mount_large_file_json(server: &MockServer) {
+        use wiremock::matchers::header;
+
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/contents/big.log"))
+            .and(header("accept", RAW_MEDIA_TYPE))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"sha": "bigsha", "content": null})),
+            )
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn read_file_downloads_large_files_raw() {
+        use wiremock::matchers::header;
+
+        let server = MockServer::start().await;
+        mount_large_file_json(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/contents/big.log"))
+            .and(header("accept", RAW_ONLY_MEDIA_TYPE))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "application/vnd.github.raw")
+                    .set_body_string("line 1\nline 2\n"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/git/blobs/bigsha"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::with_base_url(Client::new(), &server.uri());
+        let text = client
+            .read_file("owner", "repo", "big.log", None)
+            .await
+            .unwrap();
+        assert_eq!(text, "line 1\nline 2\n");
+    }
+
+    #[tokio::test]
+    async fn get_raw_content_rejects_binary() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/contents/logo.png"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "application/vnd.github.raw")
+                    .set_body_bytes(vec![0x89, b'P', b'N', b'G', 0xff, 0xfe]),
+            )
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::with_base_url(Client::new(), &server.uri());
+        let err = client
+            .get_raw_content("owner", "repo", "logo.png", None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, GitHubError::Decode(_)), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn read_file_uses_blob_api_when_raw_download_is_refused() {
+        use base64::Engine as _;
+        use wiremock::matchers::header;
+
+        let server = MockServer::start().await;
+        mount_large_file_json(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/contents/big.log"))
+            .and(header("accept", RAW_ONLY_MEDIA_TYPE))
+            .respond_with(ResponseTemplate::new(415))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/git/blobs/bigsha"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "content": base64::engine::general_purpose::STANDARD.encode("from blob")
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::with_base_url(Client::new(), &server.uri());
+        let text = client
+            .read_file("owner", "repo", "big.log", None)
+            .await
+            .unwrap();
+        assert_eq!(text, "from blob");
+    }
+
+    #[tokio::test]
+    async fn get_json_retries_an_empty_200() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(" \n"))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::with_base_url(Client::new(), &server.uri());
+        let value: serde_json::Value = client.get_json("/test").await.unwrap();
+        assert_eq!(value, serde_json::json!({"ok": true}));
+    }
+
     #[tokio::test]
     async fn get_json_500_returns_api_error() {
         let server = MockServer::start().await;
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::Deserialize;
 
 /// Repository metadata from `GET /repos/{owner}/{repo}`.
@@ -13,6 +15,10 @@ pub struct RepoInfo {
     pub open_issues_count: u64,
     pub topics: Option<Vec<String>>,
     pub license: Option<LicenseInfo>,
+    /// Bytes of code per language from `GET /repos/{owner}/{repo}/languages`,
+    /// filled in separately; empty when not fetched.
+    #[serde(skip)]
+    pub languages: BTreeMap<String, u64>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -29,7 +35,7 @@ pub struct TreeResponse {
 }
 
 /// Git object type. `Other` captures unknown types via `#[serde(other)]` for forward compat.
-#[derive(Deserialize, Debug, PartialEq, Eq)]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum EntryType {
     Blob,
@@ -40,7 +46,7 @@ pub enum EntryType {
 }
 
 /// A single entry in a git tree (file, directory, or submodule).
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct TreeEntry {
     pub path: String,
     #[serde(rename = "type")]
@@ -55,6 +61,29 @@ pub struct ContentsResponse {
     pub content: Option<String>,
 }
 
+/// One entry of a directory listing from `GET /repos/{owner}/{repo}/contents/{dir}`.
+#[derive(Deserialize, Debug)]
+pub struct ContentEntry {
+    pub name: String,
+    pub path: String,
+    pub sha: String,
+    /// `file`, `dir`, `symlink`, or `submodule`.
+    #[serde(rename = "type")]
+    pub entry_type: String,
+    #[serde(default)]
+    pub size: u64,
+    pub html_url: Option<String>,
+}
+
+/// The Contents API answers with an array for a directory and an object for
+/// anything else.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum ContentsListing {
+    Directory(Vec<ContentEntry>),
+    NotDirectory(ContentEntry),
+}
+
 /// Response from `GET /repos/{owner}/{repo}/git/blobs/{sha}`.
 #[derive(Deserialize, Debug)]
 pub struct BlobResponse {
@@ -69,6 +98,32 @@ pub struct IssueInfo {
     pub labels: Vec<LabelInfo>,
     pub user: Option<UserInfo>,
     pub pull_request: Option<serde_json::Value>,
+    pub comments: Option<u64>,
+    pub updated_at: Option<String>,
+}
+
+/// Response from `GET /repos/{owner}/{repo}/issues/{number}`.
+#[derive(Deserialize, Debug)]
+pub struct IssueDetail {
+    pub number: u64,
+    pub title: String,
+    pub html_url: String,
+    /// `open` or `closed`.
+    pub state: String,
+    pub body: Option<String>,
+    pub user: Option<UserInfo>,
+    pub labels: Vec<LabelInfo>,
+    pub comments: u64,
+    pub created_at: String,
+    pub pull_request: Option<serde_json::Value>,
+}
+
+/// Entry from `GET /repos/{owner}/{repo}/issues/{number}/comments`.
+#[derive(Deserialize, Debug)]
+pub struct IssueComment {
+    pub user: Option<UserInfo>,
+    pub body: Option<String>,
+    pub created_at: String,
 }
 
 #[derive(Deserialize, Debug)]
@@ -83,8 +138,51 @@ pub struct PullInfo {
     pub html_url: String,
     pub draft: Option<bool>,
     pub user: Option<UserInfo>,
+    /// Absent from the REST list endpoint; GraphQL reports it.
+    pub comments: Option<u64>,
+    pub updated_at: Option<String>,
+}
+
+/// Response from `GET /repos/{owner}/{repo}/pulls/{number}`.
+#[derive(Deserialize, Debug)]
+pub struct PullDetail {
+    pub number: u64,
+    pub title: String,
+    pub html_url: String,
+    /// `open` or `closed`.
+    pub state: String,
+    #[serde(default)]
+    pub merged: bool,
+    /// `None` while GitHub is still computing it.
+    pub mergeable: Option<bool>,
+    /// `clean`, `dirty`, `blocked`, `behind`, `unstable`, `unknown`, …
+    pub mergeable_state: Option<String>,
+    pub changed_files: u64,
+    pub additions: u64,
+    pub deletions: u64,
+    pub base: PullBranch,
+    pub head: PullBranch,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PullBranch {
+    #[serde(rename = "ref")]
+    pub ref_: String,
+    pub sha: String,
+    /// Repository the branch lives in; `None` once a fork has been deleted.
+    pub repo: Option<PullRepo>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PullRepo {
+    /// `owner/repo`.
+    pub full_name: String,
 }
 
+/// Entry from `GET /repos/{owner}/{repo}/pulls/{number}/files`, which has
+/// the same shape as a compare file.
+pub type PullFile = CompareFile;
+
 #[derive(Deserialize, Debug)]
 pub struct UserInfo {
     pub login: String,
@@ -97,4 +195,145 @@ pub struct ReleaseInfo {
     pub html_url: String,
     pub published_at: Option<String>,
     pub prerelease: bool,
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+/// Downloadable file attached to a release.
+#[derive(Deserialize, Debug)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub size: u64,
+    pub download_count: u64,
+    pub browser_download_url: String,
+}
+
+/// Entry from `GET /repos/{owner}/{repo}/branches`.
+#[derive(Deserialize, Debug)]
+pub struct BranchInfo {
+    pub name: String,
+    pub commit: RefCommit,
+}
+
+/// Entry from `GET /repos/{owner}/{repo}/tags`.
+#[derive(Deserialize, Debug)]
+pub struct TagInfo {
+    pub name: String,
+    pub commit: RefCommit,
+}
+
+/// Commit a branch or tag points at.
+#[derive(Deserialize, Debug)]
+pub struct RefCommit {
+    pub sha: String,
+}
+
+/// Response from `GET /repos/{owner}/{repo}/actions/runs`.
+#[derive(Deserialize, Debug)]
+pub struct WorkflowRunsResponse {
+    pub workflow_runs: Vec<WorkflowRun>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct WorkflowRun {
+    pub name: Option<String>,
+    pub html_url: String,
+    pub head_sha: String,
+    pub event: String,
+    /// `queued`, `in_progress`, `completed`, …
+    pub status: Option<String>,
+    /// Set once `status` is `completed`: `success`, `failure`, `cancelled`, …
+    pub conclusion: Option<String>,
+    pub created_at: String,
+}
+
+/// Entry from `GET /repos/{owner}/{repo}/commits`.
+#[derive(Deserialize, Debug)]
+pub struct CommitInfo {
+    pub sha: String,
+    pub html_url: String,
+    pub commit: CommitDetail,
+    /// The GitHub account matched to the commit author; `None` when the
+    /// author email isn't linked to one.
+    pub author: Option<UserInfo>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CommitDetail {
+    pub message: String,
+    pub author: Option<CommitAuthor>,
+}
+
+/// Git author as recorded in the commit.
+#[derive(Deserialize, Debug)]
+pub struct CommitAuthor {
+    pub name: Option<String>,
+    pub date: Option<String>,
+}
+
+/// Response from `GET /search/issues`.
+#[derive(Deserialize, Debug)]
+pub struct SearchIssuesResponse {
+    pub total_count: u64,
+    #[serde(default)]
+    pub incomplete_results: bool,
+    pub items: Vec<IssueSearchItem>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct IssueSearchItem {
+    pub number: u64,
+    pub title: String,
+    /// `open` or `closed`.
+    pub state: String,
+    pub html_url: String,
+    /// Present when the hit is a pull request.
+    pub pull_request: Option<serde_json::Value>,
+}
+
+/// Response from `GET /search/code` with the text-match media type.
+#[derive(Deserialize, Debug)]
+pub struct CodeSearchResponse {
+    pub total_count: u64,
+    #[serde(default)]
+    pub incomplete_results: bool,
+    pub items: Vec<CodeSearchItem>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CodeSearchItem {
+    pub path: String,
+    pub html_url: String,
+    #[serde(default)]
+    pub text_matches: Vec<TextMatch>,
+}
+
+/// A snippet of the file around a match.
+#[derive(Deserialize, Debug)]
+pub struct TextMatch {
+    pub fragment: String,
+}
+
+/// Response from `GET /repos/{owner}/{repo}/compare/{base}...{head}`.
+#[derive(Deserialize, Debug)]
+pub struct CompareResponse {
+    /// `ahead`, `behind`, `diverged`, or `identical`.
+    pub status: String,
+    pub ahead_by: u64,
+    pub behind_by: u64,
+    pub html_url: String,
+    #[serde(default)]
+    pub files: Vec<CompareFile>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CompareFile {
+    pub filename: String,
+    /// `added`, `modified`, `removed`, `renamed`, …
+    pub status: String,
+    pub additions: u64,
+    pub deletions: u64,
+    pub previous_filename: Option<String>,
+    /// Unified diff hunk; absent for binary or very large files.
+    pub patch: Option<String>,
 }
@@ -0,0 +1,111 @@
+//! stderr logging setup. `SCOUT_LOG_FORMAT=json` switches to one JSON object
+//! per line for log aggregators; `SCOUT_LOG` (falling back to `RUST_LOG`)
+//! sets the filter directives.
+
+use tracing::Subscriber;
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::util::SubscriberInitExt as _;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogFormat {
+    Pretty,
+    Json,
+}
+
+pub fn init() {
+    build(&|var| std::env::var(var).ok(), std::io::stderr).init();
+}
+
+fn build<W>(
+    env_reader: &impl Fn(&str) -> Option<String>,
+    writer: W,
+) -> Box<dyn Subscriber + Send + Sync>
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let builder = tracing_subscriber::fmt()
+        .with_writer(writer)
+        .with_env_filter(filter_with(env_reader));
+    match log_format_with(env_reader) {
+        LogFormat::Json => Box::new(builder.json().finish()),
+        LogFormat::Pretty => Box::new(builder.finish()),
+    }
+}
+
+fn log_format_with(env_reader: &impl Fn(&str) -> Option<String>) -> LogFormat {
+    match env_reader("SCOUT_LOG_FORMAT") {
+        Some(v) if v.trim().eq_ignore_ascii_case("json") => LogFormat::Json,
+        _ => LogFormat::Pretty,
+    }
+}
+
+fn filter_with(env_reader: &impl Fn(&str) -> Option<String>) -> EnvFilter {
+    let directives = env_reader("SCOUT_LOG")
+        .or_else(|| env_reader("RUST_LOG"))
+        .unwrap_or_default();
+    // `scout=info` comes first so a user-supplied `scout=<level>` overrides it.
+    EnvFilter::builder().parse_lossy(format!("scout=info,{directives}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn capture_logs(env: &[(&str, &str)]) -> String {
+        let capture = Capture::default();
+        let writer = capture.clone();
+        let env_reader = |var: &str| {
+            env.iter()
+                .find(|(k, _)| *k == var)
+                .map(|(_, v)| v.to_string())
+        };
+        tracing::subscriber::with_default(build(&env_reader, move || writer.clone()), || {
+            tracing::info!(url = "https://example.com", "fetch");
+            tracing::debug!("hidden at the default level");
+        });
+        String::from_utf8(capture.0.lock().unwrap().clone()).unwrap()
+    }
+
+    #[test]
+    fn json_format_emits_parseable_lines() {
+        let logs = capture_logs(&[("SCOUT_LOG_FORMAT", "JSON")]);
+        let lines: Vec<serde_json::Value> = logs
+            .lines()
+            .map(|l| serde_json::from_str(l).expect("each line is JSON"))
+            .collect();
+        assert_eq!(lines.len(), 1, "got:\n{logs}");
+        assert_eq!(lines[0]["level"], "INFO");
+        assert_eq!(lines[0]["fields"]["message"], "fetch");
+        assert_eq!(lines[0]["fields"]["url"], "https://example.com");
+    }
+
+    #[test]
+    fn pretty_is_the_default() {
+        let logs = capture_logs(&[]);
+        assert!(logs.contains("fetch"), "got:\n{logs}");
+        assert!(serde_json::from_str::<serde_json::Value>(logs.trim()).is_err());
+    }
+
+    #[test]
+    fn scout_log_takes_precedence_over_rust_log() {
+        let logs = capture_logs(&[("SCOUT_LOG", "scout=debug"), ("RUST_LOG", "off")]);
+        assert!(logs.contains("hidden at the default level"), "got:\n{logs}");
+    }
+}
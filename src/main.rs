@@ -1,6 +1,9 @@
+mod budget;
+mod env;
 mod fetch;
 mod gemini;
 mod github;
+mod logging;
 mod markdown;
 mod redacted;
 mod retry;
@@ -10,8 +13,8 @@ mod tools;
 
 pub const USER_AGENT: &str = concat!("scout/", env!("CARGO_PKG_VERSION"));
 
-use clap::Parser;
-use tools::{Command, Scout};
+use clap::{CommandFactory, FromArgMatches, Parser};
+use tools::{Command, EnabledTools, Scout};
 
 #[derive(Parser)]
 #[command(name = "scout", version, about = "Web search, page fetching, and GitHub repository exploration")]
@@ -22,17 +25,13 @@ struct Cli {
 
 #[tokio::main]
 async fn main() {
-    tracing_subscriber::fmt()
-        .with_writer(std::io::stderr)
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive("scout=info".parse().expect("valid tracing directive")),
-        )
-        .init();
+    logging::init();
 
-    let cli = Cli::parse();
+    let enabled = EnabledTools::from_env();
+    let matches = enabled.apply_to_cli(Cli::command()).get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
 
-    let scout = match Scout::new().await {
+    let scout = match Scout::new(enabled).await {
         Ok(s) => s,
         Err(e) => {
             eprintln!("error: {e}");
@@ -36,6 +36,107 @@ pub(crate) fn truncate_with_note(s: &str, max_bytes: usize) -> std::borrow::Cow<
     std::borrow::Cow::Owned(out)
 }
 
+/// Split `markdown` into pieces of at most `max_chars` characters.
+///
+/// Breaks before a heading when one falls in the back half of a piece,
+/// otherwise at the last blank line, then at the last line end; a single
+/// overlong line is cut mid-line. Breaks outside fenced code blocks are
+/// preferred; a block too long for one piece is closed at the cut and
+/// reopened in the next piece, so every piece renders on its own. Without
+/// such a block, the pieces concatenate back to the input.
+pub(crate) fn split_chunks(markdown: &str, max_chars: usize) -> Vec<std::borrow::Cow<'_, str>> {
+    let max_chars = max_chars.max(1);
+    let mut chunks = Vec::new();
+    let mut rest = markdown;
+    // Opening fence line of a code block the previous cut left open.
+    let mut open_fence: Option<&str> = None;
+    while let Some((limit, _)) = rest.char_indices().nth(max_chars) {
+        let window = &rest[..limit];
+        let (cut, fence_at_cut) = chunk_cut(window, open_fence);
+        chunks.push(fenced_piece(&rest[..cut], open_fence, fence_at_cut));
+        open_fence = fence_at_cut;
+        rest = &rest[cut..];
+    }
+    if !rest.is_empty() {
+        chunks.push(fenced_piece(rest, open_fence, None));
+    }
+    chunks
+}
+
+/// Where to end the piece in `window`, and the code block still open there.
+/// `open_fence` is the block open at the start of the window.
+fn chunk_cut<'a>(window: &'a str, open_fence: Option<&'a str>) -> (usize, Option<&'a str>) {
+    // Line starts a cut may fall on: (offset, open block, starts a heading).
+    let mut candidates = Vec::new();
+    let mut fence = open_fence;
+    let mut pos = 0;
+    for line in window.split_inclusive('\n') {
+        if pos > 0 {
+            candidates.push((pos, fence, line.starts_with('#')));
+        }
+        if !line.ends_with('\n') {
+            break;
+        }
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence = if fence.is_some() {
+                None
+            } else {
+                Some(line.trim())
+            };
+        }
+        pos += line.len();
+    }
+    if pos == window.len() {
+        candidates.push((pos, fence, false));
+    }
+
+    let after_blank = |p: usize| window[..p].ends_with("\n\n");
+    let outside = || candidates.iter().rev().filter(|(_, f, _)| f.is_none());
+    outside()
+        .find(|&&(p, _, heading)| heading && p > window.len() / 2 && after_blank(p))
+        .or_else(|| outside().find(|&&(p, ..)| after_blank(p)))
+        .or_else(|| outside().next())
+        .or(candidates.last())
+        .map(|&(p, f, _)| (p, f))
+        .unwrap_or((window.len(), fence))
+}
+
+/// `piece`, reopening `reopen` before it and closing `close` after it.
+fn fenced_piece<'a>(
+    piece: &'a str,
+    reopen: Option<&str>,
+    close: Option<&str>,
+) -> std::borrow::Cow<'a, str> {
+    if reopen.is_none() && close.is_none() {
+        return std::borrow::Cow::Borrowed(piece);
+    }
+    let mut out = String::with_capacity(piece.len() + 16);
+    if let Some(fence) = reopen {
+        out.push_str(fence);
+        out.push('\n');
+    }
+    out.push_str(piece);
+    if let Some(fence) = close {
+        if !piece.ends_with('\n') {
+            out.push('\n');
+        }
+        let marker_len = fence.len() - fence.trim_start_matches(['`', '~']).len();
+        out.push_str(&fence[..marker_len]);
+        out.push('\n');
+    }
+    std::borrow::Cow::Owned(out)
+}
+
+/// Wrap `content` in a fenced code block whose fence is longer than any
+/// backtick run inside it, so embedded fences cannot close the block early.
+pub(crate) fn fenced(content: &str, lang: &str) -> String {
+    let longest_run = content.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    let newline = if content.ends_with('\n') { "" } else { "\n" };
+    format!("{fence}{lang}\n{content}{newline}{fence}")
+}
+
 /// Shift all Markdown heading levels deeper by `levels` (e.g., `# Foo` → `#### Foo`
 /// with `levels = 3`).  Skips lines inside fenced code blocks so that comment
 /// lines like `# TODO` are not affected.
@@ -114,6 +215,19 @@ mod tests {
         assert_eq!(shift_headings(input, 3), input);
     }
 
+    #[test]
+    fn fenced_wraps_content() {
+        assert_eq!(fenced("let x = 1;", "rust"), "```rust\nlet x = 1;\n```");
+        assert_eq!(fenced("a\n", ""), "```\na\n```");
+    }
+
+    #[test]
+    fn fenced_outgrows_embedded_fences() {
+        let out = fenced("```\ninner\n```", "md");
+        assert!(out.starts_with("````md\n"), "got: {out}");
+        assert!(out.ends_with("\n````"));
+    }
+
     #[test]
     fn truncate_with_note_short_input_unchanged() {
         assert_eq!(truncate_with_note("hello", 100), "hello");
@@ -126,4 +240,59 @@ mod tests {
         assert!(result.len() < 200);
         assert!(result.contains("(truncated: showing 100 / 200 bytes)"));
     }
+
+    #[test]
+    fn split_chunks_breaks_at_paragraphs() {
+        let text = "aaaa\n\nbbbb\n\ncccc";
+        assert_eq!(split_chunks(text, 8), vec!["aaaa\n\n", "bbbb\n\n", "cccc"]);
+        assert_eq!(split_chunks(text, 12), vec!["aaaa\n\nbbbb\n\n", "cccc"]);
+        assert_eq!(split_chunks(text, 100), vec![text]);
+    }
+
+    #[test]
+    fn split_chunks_prefers_heading_boundary() {
+        let text = "intro\n\nmore text here\n\n## Next\n\nbody";
+        let chunks = split_chunks(text, 30);
+        assert_eq!(chunks[0], "intro\n\nmore text here\n\n");
+        assert!(chunks[1].starts_with("## Next"));
+    }
+
+    #[test]
+    fn split_chunks_final_partial_chunk_and_round_trip() {
+        let text = "line one\nline two\nline three\n".repeat(5);
+        let chunks = split_chunks(&text, 25);
+        assert!(chunks.iter().all(|c| c.chars().count() <= 25));
+        assert!(chunks.last().unwrap().chars().count() < 25);
+        assert_eq!(chunks.concat(), text);
+    }
+
+    #[test]
+    fn split_chunks_cuts_overlong_line_on_char_boundary() {
+        let text = "日本語".repeat(4);
+        let chunks = split_chunks(&text, 5);
+        assert_eq!(chunks, vec!["日本語日本", "語日本語日", "本語"]);
+    }
+
+    #[test]
+    fn split_chunks_breaks_outside_code_blocks() {
+        let text = "intro\n\n```\na\n\nb\n```\n";
+        assert_eq!(
+            split_chunks(text, 16),
+            vec!["intro\n\n", "```\na\n\nb\n```\n"]
+        );
+    }
+
+    #[test]
+    fn split_chunks_closes_and_reopens_long_code_block() {
+        let text = "```rust\nfn a() {}\nfn b() {}\n```\n";
+        assert_eq!(
+            split_chunks(text, 20),
+            vec!["```rust\nfn a() {}\n```\n", "```rust\nfn b() {}\n```\n"]
+        );
+    }
+
+    #[test]
+    fn split_chunks_empty_input() {
+        assert!(split_chunks("", 10).is_empty());
+    }
 }
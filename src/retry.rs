@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use tracing::debug;
 
@@ -15,11 +15,41 @@ pub(crate) fn is_transient_network(e: &reqwest::Error) -> bool {
     e.is_connect() || e.is_timeout()
 }
 
+/// Longest server-requested wait worth sitting through; beyond this the error
+/// is returned so the caller can report it instead of hanging.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// Parse a `Retry-After` value: delta-seconds or an HTTP-date. Dates in the
+/// past mean no wait.
+pub(crate) fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = httpdate::parse_http_date(value).ok()?;
+    Some(at.duration_since(now).unwrap_or(Duration::ZERO))
+}
+
 pub(crate) async fn retry_with<T, E, F, Fut>(
     operation: F,
     is_retriable: impl Fn(&E) -> bool,
     fallback_err: impl FnOnce() -> E,
 ) -> Result<T, E>
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = Result<T, E>>,
+{
+    retry_with_hint(operation, is_retriable, |_| None, fallback_err).await
+}
+
+/// [`retry_with`], but waits as long as `retry_after` says an error asks
+/// (e.g. a `Retry-After` header) instead of the computed backoff.
+pub(crate) async fn retry_with_hint<T, E, F, Fut>(
+    operation: F,
+    is_retriable: impl Fn(&E) -> bool,
+    retry_after: impl Fn(&E) -> Option<Duration>,
+    fallback_err: impl FnOnce() -> E,
+) -> Result<T, E>
 where
     F: Fn() -> Fut,
     Fut: std::future::Future<Output = Result<T, E>>,
@@ -29,15 +59,47 @@ where
         match operation().await {
             Ok(v) => return Ok(v),
             Err(e) if is_retriable(&e) => {
-                last_err = Some(e);
                 if attempt + 1 < MAX_RETRIES {
-                    let delay_ms = jittered_backoff(attempt);
-                    debug!(attempt = attempt + 1, delay_ms, "retrying after transient error");
-                    tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+                    let delay = match retry_after(&e) {
+                        Some(wait) if wait > MAX_RETRY_AFTER => return Err(e),
+                        Some(wait) => wait,
+                        None => Duration::from_millis(jittered_backoff(attempt)),
+                    };
+                    debug!(
+                        attempt = attempt + 1,
+                        delay_ms = delay.as_millis() as u64,
+                        "retrying after transient error"
+                    );
+                    tokio::time::sleep(delay).await;
                 }
+                last_err = Some(e);
             }
             Err(e) => return Err(e),
         }
     }
     Err(last_err.unwrap_or_else(fallback_err))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_after_accepts_seconds_and_http_dates() {
+        let now = httpdate::parse_http_date("Wed, 21 Oct 2026 07:28:00 GMT").unwrap();
+        assert_eq!(
+            parse_retry_after(" 120 ", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2026 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2026 07:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+        assert_eq!(parse_retry_after("-5", now), None);
+    }
+}
@@ -1,3 +1,15 @@
+use crate::env::parse_bool_env;
+
+/// Whether `lang=auto` research may expand Japanese queries into a second
+/// English search. Set `SCOUT_DISABLE_BILINGUAL=1` to always run one query.
+pub fn expansion_enabled() -> bool {
+    expansion_enabled_with(|var| std::env::var(var).ok())
+}
+
+fn expansion_enabled_with(env_reader: impl Fn(&str) -> Option<String>) -> bool {
+    !parse_bool_env(env_reader, "SCOUT_DISABLE_BILINGUAL").unwrap_or(false)
+}
+
 pub fn expand_bilingual(query: &str) -> Vec<String> {
     if contains_japanese(query) {
         let eng = to_english_query(query);
@@ -48,6 +60,15 @@ mod tests {
         assert!(queries[1].contains("TypeScript"));
     }
 
+    #[test]
+    fn expansion_disabled_only_by_truthy_env() {
+        let env = |value: &'static str| move |_: &str| Some(value.to_string());
+        assert!(expansion_enabled_with(|_| None));
+        assert!(expansion_enabled_with(env("0")));
+        assert!(!expansion_enabled_with(env("1")));
+        assert!(!expansion_enabled_with(env("TRUE")));
+    }
+
     #[test]
     fn english_query_stays_single() {
         let queries = expand_bilingual("React hooks best practices");
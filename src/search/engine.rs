@@ -1,22 +1,48 @@
+use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
+use std::sync::Mutex;
 use std::time::Duration;
 
+use clap::ValueEnum;
 use futures::future::join_all;
 use futures::stream::{self, StreamExt};
 use reqwest::Client;
+use tokio::sync::Semaphore;
 use tracing::warn;
 
+use crate::budget::RequestBudget;
 use crate::fetch;
 use crate::fetch::DnsResolver;
 use crate::fetch::converter::FetchResult;
 use crate::gemini::client::{GeminiError, SearchClient};
-use crate::gemini::types::{GroundedResult, Source};
+use crate::gemini::grounding::no_answer_note;
+use crate::gemini::types::{Citation, GroundedResult, Source};
 use crate::markdown::{escape_md_link, sanitize_heading, shift_headings, truncate_with_note};
+use crate::retry::retry_with;
 use crate::search::Lang;
 use crate::search::bilingual::expand_bilingual;
 
 const MAX_PAGE_BYTES: usize = 3000;
+/// Default for `SCOUT_MAX_REPORT_CHARS`: room for a depth-10 report with
+/// several search answers and full-size page excerpts.
+const DEFAULT_MAX_REPORT_CHARS: usize = 60_000;
+/// `search_and_read` shows its single page in full, up to the fetch tool's budget.
+const MAX_READ_PAGE_BYTES: usize = 100_000;
 const FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+/// Query variants searched at once; unbounded fan-out trips Gemini rate limits.
+const DEFAULT_SEARCH_CONCURRENCY: usize = 2;
+const DEFAULT_FETCH_CONCURRENCY: usize = 5;
+const MAX_RESEARCH_CONCURRENCY: usize = 16;
+
+/// How a research report cites its sources.
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CitationStyle {
+    /// A flat list of sources after the report.
+    #[default]
+    List,
+    /// `[^n]` markers in the search answers, defined in a footnotes section.
+    Footnotes,
+}
 
 /// Aggregated output of a multi-source research session.
 #[derive(Debug)]
@@ -25,6 +51,8 @@ pub(crate) struct ResearchReport {
     pub(crate) fetched_pages: Vec<FetchResult>,
     pub(crate) failed_urls: Vec<FailedUrl>,
     pub(crate) all_sources: Vec<Source>,
+    /// The request cap, when it stopped some searches or fetches.
+    pub(crate) request_cap_hit: Option<usize>,
 }
 
 #[derive(Debug)]
@@ -33,11 +61,76 @@ pub(crate) struct FailedUrl {
     pub(crate) reason: String,
 }
 
+/// Output of [`search_and_read`]: one grounded answer and its top source.
+#[derive(Debug)]
+pub(crate) struct SearchReadReport {
+    pub(crate) search_result: GroundedResult,
+    pub(crate) page: Option<FetchResult>,
+    pub(crate) failed: Option<FailedUrl>,
+    pub(crate) sources: Vec<Source>,
+}
+
 /// Parameters for a research session (query, depth, language).
 pub(crate) struct ResearchRequest<'a> {
     pub(crate) query: &'a str,
     pub(crate) depth: u8,
     pub(crate) lang: Lang,
+    /// Allow `Lang::Auto` to add an English search for Japanese queries.
+    pub(crate) bilingual: bool,
+    pub(crate) concurrency: ResearchConcurrency,
+    pub(crate) budget: RequestBudget,
+}
+
+/// How many searches and page fetches a research session runs at once.
+/// Set `SCOUT_RESEARCH_SEARCH_CONCURRENCY` / `SCOUT_RESEARCH_FETCH_CONCURRENCY`
+/// to override the defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ResearchConcurrency {
+    pub(crate) searches: usize,
+    pub(crate) fetches: usize,
+}
+
+impl Default for ResearchConcurrency {
+    fn default() -> Self {
+        Self {
+            searches: DEFAULT_SEARCH_CONCURRENCY,
+            fetches: DEFAULT_FETCH_CONCURRENCY,
+        }
+    }
+}
+
+impl ResearchConcurrency {
+    pub(crate) fn from_env() -> Self {
+        Self::from_env_with(|var| std::env::var(var).ok())
+    }
+
+    fn from_env_with(env_reader: impl Fn(&str) -> Option<String>) -> Self {
+        Self {
+            searches: read_limit(
+                &env_reader,
+                "SCOUT_RESEARCH_SEARCH_CONCURRENCY",
+                DEFAULT_SEARCH_CONCURRENCY,
+            ),
+            fetches: read_limit(
+                &env_reader,
+                "SCOUT_RESEARCH_FETCH_CONCURRENCY",
+                DEFAULT_FETCH_CONCURRENCY,
+            ),
+        }
+    }
+}
+
+fn read_limit(env_reader: &impl Fn(&str) -> Option<String>, var: &str, default: usize) -> usize {
+    let Some(raw) = env_reader(var) else {
+        return default;
+    };
+    match raw.trim().parse::<usize>() {
+        Ok(n) if n > 0 => n.min(MAX_RESEARCH_CONCURRENCY),
+        _ => {
+            warn!(value = %raw, var, "invalid research concurrency, using default");
+            default
+        }
+    }
 }
 
 pub async fn research(
@@ -47,11 +140,12 @@ pub async fn research(
     resolver: &impl DnsResolver,
 ) -> Result<ResearchReport, GeminiError> {
     let queries = match req.lang {
-        Lang::Auto => expand_bilingual(req.query),
+        Lang::Auto if req.bilingual => expand_bilingual(req.query),
         _ => vec![req.lang.apply_to_query(req.query)],
     };
 
-    let search_results = run_searches(gemini, &queries).await?;
+    let search_results =
+        run_searches(gemini, &queries, req.concurrency.searches, &req.budget).await?;
     let all_sources = collect_unique_sources(&search_results);
 
     let urls: Vec<String> = all_sources
@@ -60,22 +154,68 @@ pub async fn research(
         .map(|s| s.url.clone())
         .collect();
 
-    let (fetched_pages, failed_urls) = fetch_sources(http, urls, resolver).await;
+    let (fetched_pages, failed_urls) = fetch_sources(
+        http,
+        urls,
+        FETCH_TIMEOUT,
+        req.concurrency.fetches,
+        &req.budget,
+        resolver,
+    )
+    .await;
 
     Ok(ResearchReport {
         search_results,
         fetched_pages,
         failed_urls,
         all_sources,
+        request_cap_hit: req.budget.exhausted_at(),
+    })
+}
+
+/// Lightweight research: one search (no bilingual expansion), then a full
+/// read of the top source.
+pub async fn search_and_read(
+    gemini: &impl SearchClient,
+    http: &Client,
+    query: &str,
+    lang: Lang,
+    budget: &RequestBudget,
+    resolver: &impl DnsResolver,
+) -> Result<SearchReadReport, GeminiError> {
+    let search_result = gemini.search(&lang.apply_to_query(query), budget).await?;
+    let sources = collect_unique_sources(std::slice::from_ref(&search_result));
+
+    let urls: Vec<String> = sources.first().map(|s| s.url.clone()).into_iter().collect();
+    let (mut pages, mut failed) =
+        fetch_sources(http, urls, FETCH_TIMEOUT, 1, budget, resolver).await;
+
+    Ok(SearchReadReport {
+        search_result,
+        page: pages.pop(),
+        failed: failed.pop(),
+        sources,
     })
 }
 
+/// Run `queries`; those the budget turns away are dropped, not failed, unless
+/// the budget turns away every one.
 async fn run_searches(
     gemini: &impl SearchClient,
     queries: &[String],
+    concurrency: usize,
+    budget: &RequestBudget,
 ) -> Result<Vec<GroundedResult>, GeminiError> {
-    let search_futures = queries.iter().map(|q| gemini.search(q));
-    let search_outcomes = join_all(search_futures).await;
+    let permits = Semaphore::new(concurrency.max(1));
+    let search_futures = queries.iter().map(|q| async {
+        let _permit = permits.acquire().await.expect("semaphore is never closed");
+        gemini.search(q, budget).await
+    });
+    let search_outcomes: Vec<_> = join_all(search_futures)
+        .await
+        .into_iter()
+        .filter(|r| !matches!(r, Err(GeminiError::BudgetExhausted)))
+        .collect();
 
     let (successes, failures): (Vec<_>, Vec<_>) =
         search_outcomes.into_iter().partition(Result::is_ok);
@@ -84,7 +224,7 @@ async fn run_searches(
         let first_err = failures
             .into_iter()
             .find_map(Result::err)
-            .unwrap_or(GeminiError::RateLimited);
+            .unwrap_or(GeminiError::BudgetExhausted);
         warn!(
             queries = ?queries,
             error = %first_err,
@@ -100,20 +240,83 @@ async fn run_searches(
     Ok(successes.into_iter().filter_map(Result::ok).collect())
 }
 
+/// Per-run memory of hosts that failed at the network level, so URLs on the
+/// same host that start later fail fast instead of each waiting out the full
+/// timeout. Fetches already in flight are not held back.
+#[derive(Default)]
+struct HostBackoff {
+    failed: Mutex<HashSet<String>>,
+}
+
+impl HostBackoff {
+    fn host(url: &str) -> Option<String> {
+        Some(url::Url::parse(url).ok()?.host_str()?.to_ascii_lowercase())
+    }
+
+    fn is_failed(&self, host: &str) -> bool {
+        let failed = self.failed.lock().unwrap_or_else(|e| e.into_inner());
+        failed.contains(host)
+    }
+
+    fn mark_failed(&self, host: String) {
+        let mut failed = self.failed.lock().unwrap_or_else(|e| e.into_inner());
+        failed.insert(host);
+    }
+}
+
+/// Errors that say the host itself is unreachable, not just this page.
+fn is_host_failure(e: &fetch::FetchError) -> bool {
+    match e {
+        fetch::FetchError::Timeout(_) | fetch::FetchError::DnsResolution(_) => true,
+        fetch::FetchError::Http(e) => e.is_timeout() || e.is_connect(),
+        _ => false,
+    }
+}
+
+/// Fetch errors worth another attempt; blocks, 4xx, and content problems are
+/// permanent. Dropped connections are already retried inside the download.
+fn is_retriable_fetch(e: &fetch::FetchError) -> bool {
+    matches!(
+        e,
+        fetch::FetchError::Timeout(_) | fetch::FetchError::Status(500..=599)
+    )
+}
+
 async fn fetch_sources(
     http: &Client,
     urls: Vec<String>,
+    timeout: Duration,
+    concurrency: usize,
+    budget: &RequestBudget,
     resolver: &impl DnsResolver,
 ) -> (Vec<FetchResult>, Vec<FailedUrl>) {
+    let backoff = HostBackoff::default();
     let fetch_outcomes: Vec<_> = stream::iter(urls)
         .map(|url| async {
+            let host = HostBackoff::host(&url);
+            if host.as_deref().is_some_and(|h| backoff.is_failed(h)) {
+                return (
+                    url,
+                    Err("skipped: host previously unreachable in this run".into()),
+                );
+            }
+
+            // Retries share the per-source timeout, so a hung host still
+            // costs one timeout.
             let result = tokio::time::timeout(
-                FETCH_TIMEOUT,
-                fetch::fetch_page(
-                    http,
-                    &url,
-                    fetch::FetchOptions::default(),
-                    resolver,
+                timeout,
+                retry_with(
+                    || {
+                        fetch::fetch_page(
+                            http,
+                            &url,
+                            fetch::FetchOptions::default(),
+                            budget,
+                            resolver,
+                        )
+                    },
+                    is_retriable_fetch,
+                    || fetch::FetchError::Timeout("retries exhausted".into()),
                 ),
             )
             .await;
@@ -121,12 +324,17 @@ async fn fetch_sources(
                 Ok(inner) => inner,
                 Err(_) => Err(fetch::FetchError::Timeout(format!(
                     "page fetch timed out after {}s",
-                    FETCH_TIMEOUT.as_secs()
+                    timeout.as_secs()
                 ))),
             };
-            (url, result)
+            if let (Some(host), Err(e)) = (host, &result)
+                && is_host_failure(e)
+            {
+                backoff.mark_failed(host);
+            }
+            (url, result.map_err(|e| e.to_string()))
         })
-        .buffer_unordered(5)
+        .buffer_unordered(concurrency.max(1))
         .collect()
         .await;
 
@@ -135,11 +343,14 @@ async fn fetch_sources(
 
     for (url, outcome) in fetch_outcomes {
         match outcome {
-            Ok(page) => fetched_pages.push(page),
-            Err(e) => failed_urls.push(FailedUrl {
+            // A subscription stub would pass for the article; report it as
+            // unread instead.
+            Ok(page) if page.paywalled => failed_urls.push(FailedUrl {
                 url,
-                reason: e.to_string(),
+                reason: "likely paywalled: only a subscription stub was readable".into(),
             }),
+            Ok(page) => fetched_pages.push(page),
+            Err(reason) => failed_urls.push(FailedUrl { url, reason }),
         }
     }
 
@@ -150,13 +361,34 @@ async fn fetch_sources(
     (fetched_pages, failed_urls)
 }
 
+/// Dedup key for a source URL: scheme dropped (http and https collapse), host
+/// lowercased, default port and trailing slash removed, fragment ignored.
+/// Unparseable URLs key on their raw text.
+fn source_key(url: &str) -> String {
+    let Ok(parsed) = url::Url::parse(url) else {
+        return url.to_string();
+    };
+    let mut key = parsed.host_str().unwrap_or_default().to_string();
+    // `Url` already drops the scheme's default port; any port left is real.
+    if let Some(port) = parsed.port() {
+        let _ = write!(key, ":{port}");
+    }
+    key.push_str(parsed.path().trim_end_matches('/'));
+    if let Some(query) = parsed.query() {
+        let _ = write!(key, "?{query}");
+    }
+    key
+}
+
+/// Sources in first-seen order; URLs that differ only as [`source_key`]
+/// ignores keep the first one's URL and title.
 fn collect_unique_sources(results: &[GroundedResult]) -> Vec<Source> {
     let mut seen = std::collections::HashSet::new();
     let mut sources = Vec::new();
 
     for result in results {
         for source in &result.sources {
-            if !source.url.is_empty() && seen.insert(source.url.clone()) {
+            if !source.url.is_empty() && seen.insert(source_key(&source.url)) {
                 sources.push(source.clone());
             }
         }
@@ -165,35 +397,215 @@ fn collect_unique_sources(results: &[GroundedResult]) -> Vec<Source> {
     sources
 }
 
-pub fn format_report(report: &ResearchReport, query: &str) -> String {
+const NO_PAGES_NOTE: &str =
+    "> Note: No source pages could be fetched; this report is based on search answers only.\n\n";
+
+/// Render a research report in about `max_chars` characters. Only the page
+/// excerpts shrink to fit; search answers, failed URLs, and the sources or
+/// footnote definitions are always written in full so citations resolve.
+pub fn format_report(
+    report: &ResearchReport,
+    query: &str,
+    citations: CitationStyle,
+    max_chars: usize,
+) -> String {
     let mut out = format!("# Research: {}\n\n", sanitize_heading(query));
-    format_search_results(&report.search_results, &mut out);
-    format_fetched_pages(&report.fetched_pages, &mut out);
-    format_failed_urls(&report.failed_urls, &mut out);
-    format_sources(&report.all_sources, &mut out);
+    if report.fetched_pages.is_empty() && !report.search_results.is_empty() {
+        out.push_str(NO_PAGES_NOTE);
+    }
+    if let Some(cap) = report.request_cap_hit {
+        let _ = write!(
+            out,
+            "> Note: Stopped at {cap} requests (SCOUT_MAX_REQUESTS_PER_CALL); some searches or page fetches were skipped.\n\n"
+        );
+    }
+    let footnotes = match citations {
+        CitationStyle::List => None,
+        CitationStyle::Footnotes => Some(Footnotes::new(&report.all_sources)),
+    };
+    format_search_results(&report.search_results, footnotes.as_ref(), &mut out);
+    let mut tail = String::new();
+    format_failed_urls(&report.failed_urls, &mut tail);
+    match &footnotes {
+        Some(footnotes) => footnotes.format_definitions(&mut tail),
+        None => format_sources(&report.all_sources, &mut tail),
+    }
+    let available = max_chars.saturating_sub(out.chars().count() + tail.chars().count());
+    let excerpt_bytes = page_excerpt_budget(&report.fetched_pages, available);
+    format_fetched_pages(&report.fetched_pages, excerpt_bytes, &mut out);
+    out.push_str(&tail);
+    out
+}
+
+/// Room [`format_fetched_pages`] needs besides the excerpts: the section
+/// heading and a shortening note, plus per page its heading, a fallback note,
+/// and a truncation note.
+const FETCHED_PAGES_OVERHEAD: usize = 200;
+const PAGE_OVERHEAD: usize = 128;
+
+/// Bytes each page excerpt may take so the fetched-pages section fits in
+/// `available` characters, capped at [`MAX_PAGE_BYTES`].
+fn page_excerpt_budget(pages: &[FetchResult], available: usize) -> usize {
+    if pages.is_empty() {
+        return MAX_PAGE_BYTES;
+    }
+    let overhead = FETCHED_PAGES_OVERHEAD
+        + pages
+            .iter()
+            .map(|page| page.url.len() + PAGE_OVERHEAD)
+            .sum::<usize>();
+    (available.saturating_sub(overhead) / pages.len()).min(MAX_PAGE_BYTES)
+}
+
+/// Character budget for a formatted research report, from
+/// `SCOUT_MAX_REPORT_CHARS` (default [`DEFAULT_MAX_REPORT_CHARS`]).
+pub(crate) fn report_char_budget() -> usize {
+    report_char_budget_with(|var| std::env::var(var).ok())
+}
+
+fn report_char_budget_with(env_reader: impl Fn(&str) -> Option<String>) -> usize {
+    let Some(raw) = env_reader("SCOUT_MAX_REPORT_CHARS") else {
+        return DEFAULT_MAX_REPORT_CHARS;
+    };
+    match raw.trim().parse::<usize>() {
+        Ok(n) if n > 0 => n,
+        _ => {
+            warn!(value = %raw, "invalid SCOUT_MAX_REPORT_CHARS, using default");
+            DEFAULT_MAX_REPORT_CHARS
+        }
+    }
+}
+
+/// Footnote numbers for a report's sources, in `all_sources` order.
+struct Footnotes<'a> {
+    sources: &'a [Source],
+    /// Keyed by [`source_key`], so a variant of a listed URL shares its number.
+    numbers: HashMap<String, usize>,
+}
+
+impl<'a> Footnotes<'a> {
+    fn new(sources: &'a [Source]) -> Self {
+        let numbers = sources
+            .iter()
+            .enumerate()
+            .map(|(i, source)| (source_key(&source.url), i + 1))
+            .collect();
+        Self { sources, numbers }
+    }
+
+    /// `[^n]` markers for `urls`, ascending and without repeats.
+    fn markers<'u>(&self, urls: impl Iterator<Item = &'u str>) -> String {
+        let mut numbers: Vec<usize> = urls
+            .filter_map(|url| self.numbers.get(&source_key(url)).copied())
+            .collect();
+        numbers.sort_unstable();
+        numbers.dedup();
+        numbers.iter().map(|n| format!("[^{n}]")).collect()
+    }
+
+    /// `answer` with markers after each span Gemini tied to sources, or after
+    /// the whole answer when it gave no such mapping.
+    fn annotate(&self, answer: &str, result: &GroundedResult) -> String {
+        if result.citations.is_empty() {
+            let markers = self.markers(result.sources.iter().map(|s| s.url.as_str()));
+            return format!("{answer}{markers}");
+        }
+        let mut citations: Vec<&Citation> = result.citations.iter().collect();
+        citations.sort_by_key(|c| c.end);
+        let mut out = String::with_capacity(answer.len() + citations.len() * 5);
+        let mut last = 0;
+        for citation in citations {
+            // Offsets that split a character or overrun the text can't be
+            // trusted; skip them rather than guess.
+            if citation.end < last || !answer.is_char_boundary(citation.end) {
+                continue;
+            }
+            out.push_str(&answer[last..citation.end]);
+            out.push_str(&self.markers(citation.urls.iter().map(String::as_str)));
+            last = citation.end;
+        }
+        out.push_str(&answer[last..]);
+        out
+    }
+
+    fn format_definitions(&self, out: &mut String) {
+        if self.sources.is_empty() {
+            return;
+        }
+        out.push_str("## Sources\n\n");
+        for (i, source) in self.sources.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "[^{}]: [{}]({})",
+                i + 1,
+                escape_md_link(&source.title),
+                escape_md_link(&source.url)
+            );
+        }
+    }
+}
+
+pub fn format_search_read(report: &SearchReadReport, query: &str) -> String {
+    let mut out = format!("# Search & Read: {}\n\n", sanitize_heading(query));
+    format_search_results(std::slice::from_ref(&report.search_result), None, &mut out);
+    out.push_str("---\n\n## Top Source\n\n");
+    match (&report.page, &report.failed) {
+        (Some(page), _) => {
+            let _ = writeln!(out, "### {}\n", escape_md_link(&page.url));
+            if page.used_raw_fallback {
+                out.push_str(fetch::converter::RAW_FALLBACK_NOTE);
+            }
+            let content = shift_headings(&page.markdown, 3);
+            out.push_str(&truncate_with_note(&content, MAX_READ_PAGE_BYTES));
+            out.push_str("\n\n");
+        }
+        (None, Some(f)) => {
+            let _ = writeln!(
+                out,
+                "_Could not read {}: {}_\n",
+                escape_md_link(&f.url),
+                f.reason
+            );
+        }
+        (None, None) => out.push_str("_No sources returned._\n\n"),
+    }
+    format_sources(&report.sources, &mut out);
     out
 }
 
-fn format_search_results(results: &[GroundedResult], out: &mut String) {
+fn format_search_results(
+    results: &[GroundedResult],
+    footnotes: Option<&Footnotes>,
+    out: &mut String,
+) {
     for (i, result) in results.iter().enumerate() {
         if results.len() > 1 {
             let _ = writeln!(out, "## Search Result {}\n", i + 1);
         }
-        match &result.answer {
-            Some(answer) => out.push_str(answer),
-            None => out.push_str(
-                "(No answer returned — the query may have been filtered by safety settings.)\n",
-            ),
+        match (&result.answer, footnotes) {
+            (Some(answer), Some(footnotes)) => out.push_str(&footnotes.annotate(answer, result)),
+            (Some(answer), None) => out.push_str(answer),
+            (None, _) => {
+                out.push_str(&no_answer_note(result));
+                out.push('\n');
+            }
         }
         out.push_str("\n\n");
     }
 }
 
-fn format_fetched_pages(pages: &[FetchResult], out: &mut String) {
+fn format_fetched_pages(pages: &[FetchResult], excerpt_bytes: usize, out: &mut String) {
+    out.push_str("---\n\n## Fetched Pages\n\n");
     if pages.is_empty() {
+        out.push_str("_No pages fetched._\n\n");
         return;
     }
-    out.push_str("---\n\n## Fetched Pages\n\n");
+    if excerpt_bytes < MAX_PAGE_BYTES {
+        let _ = write!(
+            out,
+            "> Note: Page excerpts cut to {excerpt_bytes} bytes each to fit SCOUT_MAX_REPORT_CHARS; reduce --depth for longer excerpts.\n\n"
+        );
+    }
     for page in pages {
         let _ = writeln!(out, "### {}\n", escape_md_link(&page.url));
         if page.used_raw_fallback {
@@ -202,12 +614,12 @@ fn format_fetched_pages(pages: &[FetchResult], out: &mut String) {
         // Shift headings by 3 levels so page content (h1→h4, h2→h5, …)
         // does not collide with the report's own heading hierarchy.
         let content = shift_headings(&page.markdown, 3);
-        out.push_str(&truncate_with_note(&content, MAX_PAGE_BYTES));
+        out.push_str(&truncate_with_note(&content, excerpt_bytes));
         out.push_str("\n\n");
     }
 }
 
-fn format_failed_urls(failed: &[FailedUrl], out: &mut String) {
+pub(crate) fn format_failed_urls(failed: &[FailedUrl], out: &mut String) {
     if failed.is_empty() {
         return;
     }
@@ -236,6 +648,7 @@ fn format_sources(sources: &[Source], out: &mut String) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fetch::PublicDns;
     use std::collections::VecDeque;
     use std::sync::Mutex;
 
@@ -272,19 +685,28 @@ mod tests {
     }
 
     impl SearchClient for MockSearch {
-        async fn search(&self, query: &str) -> Result<GroundedResult, GeminiError> {
+        async fn search(
+            &self,
+            query: &str,
+            budget: &RequestBudget,
+        ) -> Result<GroundedResult, GeminiError> {
+            if !budget.try_acquire() {
+                return Err(GeminiError::BudgetExhausted);
+            }
             self.queries.lock().unwrap().push(query.to_string());
             self.responses
                 .lock()
                 .unwrap()
                 .pop_front()
-                .unwrap_or(Err(GeminiError::RateLimited))
+                .unwrap_or(Err(GeminiError::RateLimited(None)))
         }
     }
 
     fn make_grounded(sources: Vec<(&str, &str)>) -> GroundedResult {
         GroundedResult {
             answer: Some("test answer".into()),
+            block_reason: None,
+            citations: vec![],
             sources: sources
                 .into_iter()
                 .map(|(url, title)| Source {
@@ -309,6 +731,28 @@ mod tests {
         assert_eq!(sources[2].url, "https://c.com");
     }
 
+    #[test]
+    fn collect_sources_collapses_url_variants() {
+        let results = vec![
+            make_grounded(vec![
+                ("https://a.com/p", "First"),
+                ("https://A.com:443/p/", "Second"),
+            ]),
+            make_grounded(vec![
+                ("http://a.com/p", "Third"),
+                ("https://a.com/p?page=2", "Q"),
+                ("https://a.com:80/p", "Port 80 over TLS"),
+            ]),
+        ];
+
+        let sources = collect_unique_sources(&results);
+        assert_eq!(sources.len(), 3);
+        assert_eq!(sources[0].url, "https://a.com/p");
+        assert_eq!(sources[0].title, "First");
+        assert_eq!(sources[1].url, "https://a.com/p?page=2");
+        assert_eq!(sources[2].url, "https://a.com:80/p");
+    }
+
     #[test]
     fn collect_sources_skips_empty_urls() {
         let results = vec![make_grounded(vec![("", "Empty"), ("https://a.com", "A")])];
@@ -331,9 +775,15 @@ mod tests {
                 url: "https://a.com".into(),
                 title: "A".into(),
             }],
+            request_cap_hit: None,
         };
 
-        let text = format_report(&report, "test query");
+        let text = format_report(
+            &report,
+            "test query",
+            CitationStyle::List,
+            DEFAULT_MAX_REPORT_CHARS,
+        );
         assert!(text.contains("# Research: test query"));
         assert!(text.contains("test answer"));
         assert!(text.contains("Failed URLs"));
@@ -350,12 +800,19 @@ mod tests {
                 url: "https://example.com".into(),
                 markdown: "# Example Page\n\n## Section\n\nSome content here.".into(),
                 used_raw_fallback: false,
+                paywalled: false,
             }],
             failed_urls: vec![],
             all_sources: vec![],
+            request_cap_hit: None,
         };
 
-        let text = format_report(&report, "test");
+        let text = format_report(
+            &report,
+            "test",
+            CitationStyle::List,
+            DEFAULT_MAX_REPORT_CHARS,
+        );
         assert!(text.contains("Fetched Pages"));
         assert!(text.contains("### https://example.com"));
         assert!(text.contains("Some content here."));
@@ -373,6 +830,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn format_report_marks_no_fetched_pages() {
+        let report = ResearchReport {
+            search_results: vec![make_grounded(vec![])],
+            fetched_pages: vec![],
+            failed_urls: vec![],
+            all_sources: vec![],
+            request_cap_hit: None,
+        };
+
+        let text = format_report(
+            &report,
+            "test",
+            CitationStyle::List,
+            DEFAULT_MAX_REPORT_CHARS,
+        );
+        assert!(text.contains("## Fetched Pages\n\n_No pages fetched._"));
+    }
+
+    #[test]
+    fn format_report_flags_all_fetches_failing_at_the_top() {
+        let mut report = ResearchReport {
+            search_results: vec![make_grounded(vec![("https://a.com", "A")])],
+            fetched_pages: vec![],
+            failed_urls: vec![FailedUrl {
+                url: "https://a.com".into(),
+                reason: "status 503".into(),
+            }],
+            all_sources: vec![],
+            request_cap_hit: None,
+        };
+
+        let text = format_report(
+            &report,
+            "test",
+            CitationStyle::List,
+            DEFAULT_MAX_REPORT_CHARS,
+        );
+        assert!(
+            text.starts_with(&format!("# Research: test\n\n{NO_PAGES_NOTE}")),
+            "got:\n{text}"
+        );
+
+        report.fetched_pages.push(FetchResult {
+            url: "https://b.com".into(),
+            markdown: "Body".into(),
+            used_raw_fallback: false,
+            paywalled: false,
+        });
+        let text = format_report(
+            &report,
+            "test",
+            CitationStyle::List,
+            DEFAULT_MAX_REPORT_CHARS,
+        );
+        assert!(!text.contains(NO_PAGES_NOTE));
+    }
+
     #[test]
     fn format_report_truncates_long_pages() {
         let long_content = "x".repeat(5000);
@@ -382,12 +897,19 @@ mod tests {
                 url: "https://long.com".into(),
                 markdown: long_content,
                 used_raw_fallback: false,
+                paywalled: false,
             }],
             failed_urls: vec![],
             all_sources: vec![],
+            request_cap_hit: None,
         };
 
-        let text = format_report(&report, "test");
+        let text = format_report(
+            &report,
+            "test",
+            CitationStyle::List,
+            DEFAULT_MAX_REPORT_CHARS,
+        );
         // Verify truncation message includes both shown and total byte counts
         assert!(
             text.contains("(truncated: showing 3000 / 5000 bytes)"),
@@ -395,6 +917,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn format_report_shrinks_page_excerpts_but_keeps_sources() {
+        let report = ResearchReport {
+            search_results: vec![make_grounded(vec![("https://a.com", "A")])],
+            fetched_pages: (1..=5)
+                .map(|i| FetchResult {
+                    url: format!("https://page{i}.com"),
+                    markdown: format!("Page {i} body. ").repeat(150),
+                    used_raw_fallback: false,
+                    paywalled: false,
+                })
+                .collect(),
+            failed_urls: vec![],
+            all_sources: vec![Source {
+                url: "https://a.com".into(),
+                title: "A".into(),
+            }],
+            request_cap_hit: None,
+        };
+        let full = format_report(
+            &report,
+            "test",
+            CitationStyle::List,
+            DEFAULT_MAX_REPORT_CHARS,
+        );
+        assert!(!full.contains("Page excerpts cut"));
+
+        let budget = 6_000;
+        let capped = format_report(&report, "test", CitationStyle::List, budget);
+        assert!(
+            capped.chars().count() <= budget,
+            "{} chars",
+            capped.chars().count()
+        );
+        assert!(capped.contains("> Note: Page excerpts cut to "));
+        for i in 1..=5 {
+            assert!(capped.contains(&format!("### https://page{i}.com")));
+            assert!(capped.contains(&format!("Page {i} body.")));
+        }
+        assert!(capped.ends_with("## Sources\n\n- [A](https://a.com)\n"));
+
+        let footnoted = format_report(&report, "test", CitationStyle::Footnotes, budget);
+        assert!(
+            footnoted.ends_with("[^1]: [A](https://a.com)\n"),
+            "got:\n{footnoted}"
+        );
+    }
+
+    #[test]
+    fn report_char_budget_reads_env_and_rejects_zero() {
+        let budget = |value: &'static str| {
+            report_char_budget_with(move |var| {
+                (var == "SCOUT_MAX_REPORT_CHARS").then(|| value.to_string())
+            })
+        };
+        assert_eq!(budget("20000"), 20_000);
+        assert_eq!(budget("0"), DEFAULT_MAX_REPORT_CHARS);
+        assert_eq!(report_char_budget_with(|_| None), DEFAULT_MAX_REPORT_CHARS);
+    }
+
     #[test]
     fn format_report_multiple_search_results_numbered() {
         let report = ResearchReport {
@@ -405,13 +987,68 @@ mod tests {
             fetched_pages: vec![],
             failed_urls: vec![],
             all_sources: vec![],
+            request_cap_hit: None,
         };
 
-        let text = format_report(&report, "test");
+        let text = format_report(
+            &report,
+            "test",
+            CitationStyle::List,
+            DEFAULT_MAX_REPORT_CHARS,
+        );
         assert!(text.contains("## Search Result 1"));
         assert!(text.contains("## Search Result 2"));
     }
 
+    #[test]
+    fn format_report_footnotes_mark_cited_spans() {
+        let mut cited = make_grounded(vec![("https://a.com", "A"), ("https://b.com", "B")]);
+        cited.answer = Some("Fact one. Fact two.".into());
+        cited.citations = vec![
+            Citation {
+                end: 19,
+                urls: vec!["https://b.com".into(), "https://a.com".into()],
+            },
+            Citation {
+                end: 9,
+                urls: vec!["https://a.com".into()],
+            },
+        ];
+        let report = ResearchReport {
+            search_results: vec![cited, make_grounded(vec![("https://c.com", "C")])],
+            fetched_pages: vec![],
+            failed_urls: vec![],
+            all_sources: ["a", "b", "c"]
+                .iter()
+                .map(|name| Source {
+                    url: format!("https://{name}.com"),
+                    title: name.to_uppercase(),
+                })
+                .collect(),
+            request_cap_hit: None,
+        };
+
+        let text = format_report(
+            &report,
+            "test",
+            CitationStyle::Footnotes,
+            DEFAULT_MAX_REPORT_CHARS,
+        );
+        assert!(
+            text.contains("Fact one.[^1] Fact two.[^1][^2]\n"),
+            "got:\n{text}"
+        );
+        // No span mapping: the answer's sources go after the whole answer.
+        assert!(text.contains("test answer[^3]\n"), "got:\n{text}");
+        assert!(text.contains(
+            "## Sources\n\n\
+             [^1]: [A](https://a.com)\n\
+             [^2]: [B](https://b.com)\n\
+             [^3]: [C](https://c.com)\n"
+        ));
+        assert!(!text.contains("- [A]"));
+    }
+
     #[test]
     fn format_report_sanitizes_query_newlines() {
         let report = ResearchReport {
@@ -419,9 +1056,15 @@ mod tests {
             fetched_pages: vec![],
             failed_urls: vec![],
             all_sources: vec![],
+            request_cap_hit: None,
         };
 
-        let text = format_report(&report, "line1\nline2");
+        let text = format_report(
+            &report,
+            "line1\nline2",
+            CitationStyle::List,
+            DEFAULT_MAX_REPORT_CHARS,
+        );
         assert!(text.contains("# Research: line1 line2"));
         assert!(!text.contains("# Research: line1\n"));
     }
@@ -436,6 +1079,9 @@ mod tests {
             query: "test",
             depth: 3,
             lang: Lang::En,
+            bilingual: true,
+            concurrency: ResearchConcurrency::default(),
+            budget: RequestBudget::default(),
         };
         let report = research(&mock, &http, &req, &resolver).await.unwrap();
 
@@ -451,7 +1097,7 @@ mod tests {
     async fn research_partial_search_failure_still_returns() {
         let mock = MockSearch::success_then_failure(
             make_grounded(vec![("https://a.com", "A")]),
-            GeminiError::RateLimited,
+            GeminiError::RateLimited(None),
         );
         let http = Client::new();
         let resolver = fetch::TokioDnsResolver;
@@ -460,6 +1106,9 @@ mod tests {
             query: "テスト query",
             depth: 3,
             lang: Lang::Auto,
+            bilingual: true,
+            concurrency: ResearchConcurrency::default(),
+            budget: RequestBudget::default(),
         };
         let report = research(&mock, &http, &req, &resolver).await.unwrap();
 
@@ -471,9 +1120,243 @@ mod tests {
         assert!(queries[1].contains("query"));
     }
 
+    /// Records the peak number of searches in flight.
+    #[derive(Default)]
+    struct ConcurrencyProbe {
+        in_flight: std::sync::atomic::AtomicUsize,
+        peak: std::sync::atomic::AtomicUsize,
+    }
+
+    impl SearchClient for ConcurrencyProbe {
+        async fn search(
+            &self,
+            _query: &str,
+            _budget: &RequestBudget,
+        ) -> Result<GroundedResult, GeminiError> {
+            use std::sync::atomic::Ordering;
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(make_grounded(vec![]))
+        }
+    }
+
+    #[tokio::test]
+    async fn run_searches_respects_concurrency_limit() {
+        use std::sync::atomic::Ordering;
+        let queries = vec!["テスト query".to_string(), "test query".to_string()];
+
+        let probe = ConcurrencyProbe::default();
+        let results = run_searches(&probe, &queries, 1, &RequestBudget::default())
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(probe.peak.load(Ordering::SeqCst), 1);
+
+        let probe = ConcurrencyProbe::default();
+        run_searches(&probe, &queries, 2, &RequestBudget::default())
+            .await
+            .unwrap();
+        assert_eq!(probe.peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn research_concurrency_parses_env_with_fallback() {
+        let env = |search: &'static str, fetch: &'static str| {
+            move |var: &str| match var {
+                "SCOUT_RESEARCH_SEARCH_CONCURRENCY" => Some(search.to_string()),
+                "SCOUT_RESEARCH_FETCH_CONCURRENCY" => Some(fetch.to_string()),
+                _ => None,
+            }
+        };
+        assert_eq!(
+            ResearchConcurrency::from_env_with(|_| None),
+            ResearchConcurrency::default()
+        );
+        let limits = ResearchConcurrency::from_env_with(env("1", "8"));
+        assert_eq!((limits.searches, limits.fetches), (1, 8));
+        let limits = ResearchConcurrency::from_env_with(env("0", "1000"));
+        assert_eq!(
+            (limits.searches, limits.fetches),
+            (DEFAULT_SEARCH_CONCURRENCY, MAX_RESEARCH_CONCURRENCY)
+        );
+        let limits = ResearchConcurrency::from_env_with(env("many", " 3 "));
+        assert_eq!(
+            (limits.searches, limits.fetches),
+            (DEFAULT_SEARCH_CONCURRENCY, 3)
+        );
+    }
+
+    #[tokio::test]
+    async fn research_without_bilingual_runs_single_query() {
+        let mock = MockSearch::with_results(vec![make_grounded(vec![])]);
+        let http = Client::new();
+        let resolver = fetch::TokioDnsResolver;
+
+        let req = ResearchRequest {
+            query: "テスト query",
+            depth: 0,
+            lang: Lang::Auto,
+            bilingual: false,
+            concurrency: ResearchConcurrency::default(),
+            budget: RequestBudget::default(),
+        };
+        research(&mock, &http, &req, &resolver).await.unwrap();
+
+        assert_eq!(mock.captured_queries(), vec!["テスト query".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn fetch_sources_skips_host_after_timeout() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(10)))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let http = Client::builder()
+            .resolve("slow.test", *server.address())
+            .build()
+            .unwrap();
+        let port = server.address().port();
+        let urls = vec![
+            format!("http://slow.test:{port}/a"),
+            format!("http://slow.test:{port}/b"),
+        ];
+
+        let started = std::time::Instant::now();
+        let (pages, failed) = fetch_sources(
+            &http,
+            urls,
+            Duration::from_secs(1),
+            1,
+            &RequestBudget::default(),
+            &PublicDns,
+        )
+        .await;
+
+        assert!(pages.is_empty());
+        assert!(
+            started.elapsed() < Duration::from_millis(1800),
+            "second URL waited out its own timeout: {:?}",
+            started.elapsed()
+        );
+        let mut reasons: Vec<_> = failed.iter().map(|f| f.reason.as_str()).collect();
+        reasons.sort();
+        assert!(reasons[0].contains("timed out"), "got: {reasons:?}");
+        assert!(
+            reasons[1].contains("previously unreachable"),
+            "got: {reasons:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_sources_runs_same_host_urls_concurrently() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404).set_delay(Duration::from_millis(500)))
+            .expect(3)
+            .mount(&server)
+            .await;
+        let http = Client::builder()
+            .resolve("busy.test", *server.address())
+            .build()
+            .unwrap();
+        let port = server.address().port();
+        let urls = (0..3)
+            .map(|i| format!("http://busy.test:{port}/{i}"))
+            .collect();
+
+        let started = std::time::Instant::now();
+        let (_, failed) = fetch_sources(
+            &http,
+            urls,
+            FETCH_TIMEOUT,
+            3,
+            &RequestBudget::default(),
+            &PublicDns,
+        )
+        .await;
+
+        assert_eq!(failed.len(), 3);
+        assert!(
+            started.elapsed() < Duration::from_millis(1200),
+            "same-host fetches ran one at a time: {:?}",
+            started.elapsed()
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_sources_retries_transient_failure() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let filler = "Enough prose to look like a real article body. ".repeat(4);
+        let paragraphs = (0..4)
+            .map(|i| format!("<p>Flaky page paragraph {i}. {filler}</p>"))
+            .collect::<String>();
+        Mock::given(method("GET"))
+            .and(path("/flaky"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/flaky"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                format!(
+                    "<html><head><title>Flaky</title></head>\
+                     <body><article><h1>Flaky</h1>{paragraphs}</article></body></html>"
+                ),
+                "text/html",
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/gone"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let http = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+        let port = server.address().port();
+        let urls = vec![
+            format!("http://pages.test:{port}/flaky"),
+            format!("http://pages.test:{port}/gone"),
+        ];
+
+        let (pages, failed) = fetch_sources(
+            &http,
+            urls,
+            FETCH_TIMEOUT,
+            5,
+            &RequestBudget::default(),
+            &PublicDns,
+        )
+        .await;
+
+        assert_eq!(pages.len(), 1, "failed: {failed:?}");
+        assert!(pages[0].markdown.contains("Flaky page paragraph"));
+        assert_eq!(failed.len(), 1);
+        assert!(failed[0].reason.contains("404"), "got: {failed:?}");
+    }
+
     #[tokio::test]
     async fn research_all_searches_fail_returns_error() {
-        let mock = MockSearch::all_fail(GeminiError::RateLimited);
+        let mock = MockSearch::all_fail(GeminiError::RateLimited(None));
         let http = Client::new();
         let resolver = fetch::TokioDnsResolver;
 
@@ -481,8 +1364,164 @@ mod tests {
             query: "test",
             depth: 3,
             lang: Lang::En,
+            bilingual: true,
+            concurrency: ResearchConcurrency::default(),
+            budget: RequestBudget::default(),
         };
         let err = research(&mock, &http, &req, &resolver).await.unwrap_err();
         assert!(err.to_string().contains("rate limit"));
     }
+
+    #[tokio::test]
+    async fn research_with_every_search_refused_reports_budget() {
+        let mock = MockSearch::with_results(vec![make_grounded(vec![])]);
+        let http = Client::new();
+        let resolver = fetch::TokioDnsResolver;
+
+        let req = ResearchRequest {
+            query: "test",
+            depth: 3,
+            lang: Lang::En,
+            bilingual: true,
+            concurrency: ResearchConcurrency::default(),
+            budget: RequestBudget::new(Some(0)),
+        };
+        let err = research(&mock, &http, &req, &resolver).await.unwrap_err();
+        assert!(matches!(err, GeminiError::BudgetExhausted), "got: {err:?}");
+        assert!(mock.captured_queries().is_empty());
+    }
+
+    #[tokio::test]
+    async fn research_stops_at_request_cap() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        let http = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+        let port = server.address().port();
+        let urls: Vec<String> = ["a", "b", "c"]
+            .iter()
+            .map(|p| format!("http://pages.test:{port}/{p}"))
+            .collect();
+        let mock = MockSearch::with_results(vec![make_grounded(
+            urls.iter().map(|u| (u.as_str(), "Page")).collect(),
+        )]);
+
+        // Bilingual expansion runs two searches; the cap leaves one page
+        // fetch of the three sources.
+        let req = ResearchRequest {
+            query: "テスト query",
+            depth: 3,
+            lang: Lang::Auto,
+            bilingual: true,
+            concurrency: ResearchConcurrency::default(),
+            budget: RequestBudget::new(Some(3)),
+        };
+        let report = research(&mock, &http, &req, &PublicDns).await.unwrap();
+
+        assert_eq!(mock.captured_queries().len(), 2);
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+        assert_eq!(report.request_cap_hit, Some(3));
+        let skipped = report
+            .failed_urls
+            .iter()
+            .filter(|f| f.reason.contains("request budget"))
+            .count();
+        assert_eq!(skipped, 2, "got: {:?}", report.failed_urls);
+
+        let text = format_report(
+            &report,
+            "テスト query",
+            CitationStyle::List,
+            DEFAULT_MAX_REPORT_CHARS,
+        );
+        assert!(
+            text.contains("> Note: Stopped at 3 requests (SCOUT_MAX_REQUESTS_PER_CALL)"),
+            "got:\n{text}"
+        );
+    }
+
+    #[tokio::test]
+    async fn search_and_read_returns_answer_and_top_page() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let filler = "Enough prose to look like a real article body. ".repeat(4);
+        let paragraphs = (0..4)
+            .map(|i| format!("<p>Top page paragraph {i}. {filler}</p>"))
+            .collect::<String>();
+        Mock::given(method("GET"))
+            .and(path("/top"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                "<html><head><title>Top</title></head>\
+                 <body><article><h1>Top</h1>{paragraphs}</article></body></html>"
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/second"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let port = server.address().port();
+        let top = format!("http://pages.test:{port}/top");
+        let second = format!("http://pages.test:{port}/second");
+        let mock = MockSearch::with_results(vec![make_grounded(vec![
+            (&top, "Top"),
+            (&second, "Second"),
+        ])]);
+        let http = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+
+        let report = search_and_read(
+            &mock,
+            &http,
+            "テスト query",
+            Lang::Auto,
+            &RequestBudget::default(),
+            &PublicDns,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(mock.captured_queries(), vec!["テスト query".to_string()]);
+        assert_eq!(report.sources.len(), 2);
+        assert!(report.failed.is_none());
+
+        let text = format_search_read(&report, "テスト query");
+        assert!(text.contains("test answer"), "got:\n{text}");
+        assert!(text.contains("## Top Source"), "got:\n{text}");
+        assert!(text.contains("Top page paragraph 0."), "got:\n{text}");
+        assert!(text.contains("[Second]("), "got:\n{text}");
+    }
+
+    #[test]
+    fn format_search_read_reports_failed_fetch() {
+        let report = SearchReadReport {
+            search_result: make_grounded(vec![("https://a.com", "A")]),
+            page: None,
+            failed: Some(FailedUrl {
+                url: "https://a.com".into(),
+                reason: "fetch failed: status 404".into(),
+            }),
+            sources: vec![],
+        };
+
+        let text = format_search_read(&report, "q");
+        assert!(text.contains("# Search & Read: q"));
+        assert!(text.contains("_Could not read https://a.com: fetch failed: status 404_"));
+    }
 }
@@ -16,6 +16,15 @@ impl Lang {
             Lang::Auto => query.to_string(),
         }
     }
+
+    /// English name of the language, or `None` for `Auto`.
+    pub fn name(self) -> Option<&'static str> {
+        match self {
+            Lang::Ja => Some("Japanese"),
+            Lang::En => Some("English"),
+            Lang::Auto => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -4,4 +4,5 @@ pub(crate) mod bilingual;
 pub(crate) mod engine;
 mod lang;
 
+pub use engine::CitationStyle;
 pub use lang::Lang;
@@ -0,0 +1,104 @@
+use std::collections::BTreeSet;
+
+use tracing::warn;
+
+use super::params::Command;
+
+/// Tools exposed by this process, from `SCOUT_ENABLED_TOOLS` (comma-separated
+/// subcommand names, e.g. `repo-tree,repo-read`). Unset or blank enables all.
+#[derive(Debug, Clone, Default)]
+pub struct EnabledTools {
+    only: Option<BTreeSet<String>>,
+}
+
+impl EnabledTools {
+    pub fn from_env() -> Self {
+        Self::from_env_with(|var| std::env::var(var).ok())
+    }
+
+    fn from_env_with(env_reader: impl Fn(&str) -> Option<String>) -> Self {
+        let Some(raw) = env_reader("SCOUT_ENABLED_TOOLS") else {
+            return Self::default();
+        };
+        let names: BTreeSet<String> = raw
+            .split(',')
+            .map(|n| n.trim().to_ascii_lowercase().replace('_', "-"))
+            .filter(|n| !n.is_empty())
+            .collect();
+        if names.is_empty() {
+            return Self::default();
+        }
+        for unknown in names
+            .iter()
+            .filter(|n| !Command::NAMES.contains(&n.as_str()))
+        {
+            warn!(tool = %unknown, "SCOUT_ENABLED_TOOLS names an unknown tool");
+        }
+        Self { only: Some(names) }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn only(names: &[&str]) -> Self {
+        Self {
+            only: Some(names.iter().map(|n| n.to_string()).collect()),
+        }
+    }
+
+    pub fn allows(&self, name: &str) -> bool {
+        self.only.as_ref().is_none_or(|names| names.contains(name))
+    }
+
+    /// Hide disabled subcommands from `--help`. They still parse, so that
+    /// [`Scout::run`](super::Scout::run) can reject them with a clear error.
+    pub fn apply_to_cli(&self, mut cli: clap::Command) -> clap::Command {
+        for name in Command::NAMES {
+            if !self.allows(name) {
+                cli = cli.mut_subcommand(*name, |sub| sub.hide(true));
+            }
+        }
+        cli
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Subcommand;
+
+    fn with_env(value: &'static str) -> EnabledTools {
+        EnabledTools::from_env_with(|_| Some(value.to_string()))
+    }
+
+    #[test]
+    fn unset_or_blank_enables_everything() {
+        assert!(EnabledTools::from_env_with(|_| None).allows("fetch"));
+        assert!(with_env(" , ").allows("fetch"));
+    }
+
+    #[test]
+    fn names_are_normalized() {
+        let enabled = with_env(" Repo_Tree, repo-read ");
+        assert!(enabled.allows("repo-tree"));
+        assert!(enabled.allows("repo-read"));
+        assert!(!enabled.allows("fetch"));
+    }
+
+    #[test]
+    fn disabled_tools_are_hidden_from_help() {
+        let cli = Command::augment_subcommands(clap::Command::new("scout"));
+        let cli = with_env("repo-tree,repo-read").apply_to_cli(cli);
+        let advertised: Vec<&str> = cli
+            .get_subcommands()
+            .filter(|c| !c.is_hide_set())
+            .map(|c| c.get_name())
+            .collect();
+        assert_eq!(advertised, ["repo-tree", "repo-read"]);
+    }
+
+    #[test]
+    fn names_match_clap_subcommands() {
+        let cli = Command::augment_subcommands(clap::Command::new("scout"));
+        let names: Vec<&str> = cli.get_subcommands().map(|c| c.get_name()).collect();
+        assert_eq!(names, Command::NAMES);
+    }
+}
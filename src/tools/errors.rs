@@ -1,4 +1,5 @@
 use std::fmt;
+use std::time::SystemTime;
 use tracing::warn;
 
 use crate::fetch::FetchError;
@@ -49,15 +50,31 @@ impl From<github::GitHubError> for ScoutError {
         match &e {
             github::GitHubError::NotFound(_)
             | github::GitHubError::InvalidRepo(_)
+            | github::GitHubError::NotARepository(_)
             | github::GitHubError::InvalidRef(_)
+            | github::GitHubError::ReflogRef(_)
             | github::GitHubError::InvalidPath(_)
             | github::GitHubError::InvalidLineRange(_)
-            | github::GitHubError::InvalidPattern(_) => Self::user_error(e.to_string()),
-            github::GitHubError::RateLimited => Self::user_error(e.to_string()),
+            | github::GitHubError::InvalidPattern(_)
+            | github::GitHubError::TooLarge(_)
+            | github::GitHubError::NotADirectory { .. }
+            | github::GitHubError::AuthRequired(_)
+            | github::GitHubError::SsoRequired(_) => Self::user_error(e.to_string()),
+            github::GitHubError::RateLimited { .. } => {
+                match e.rate_limit_reset_in(SystemTime::now()) {
+                    Some(wait) => Self::user_error(format!(
+                        "{e} Retry after {} seconds.",
+                        wait.as_secs().max(1)
+                    )),
+                    None => Self::user_error(e.to_string()),
+                }
+            }
             github::GitHubError::Forbidden(_) => Self::user_error(format!(
                 "{e} — check that your GITHUB_TOKEN has the required scopes"
             )),
             github::GitHubError::Api { .. }
+            | github::GitHubError::EmptyResponse
+            | github::GitHubError::GraphQl(_)
             | github::GitHubError::Network(_)
             | github::GitHubError::Decode(_) => Self::internal(e.to_string()),
         }
@@ -69,11 +86,21 @@ impl From<FetchError> for ScoutError {
         match &e {
             FetchError::InvalidScheme
             | FetchError::InvalidUrl(_)
-            | FetchError::InternalHost
-            | FetchError::UnsupportedContentType(_) => Self::user_error(e.to_string()),
-            FetchError::Playwright(_) => Self::user_error(e.to_string()),
+            | FetchError::InternalHost(_)
+            | FetchError::UnsupportedContentType(_)
+            | FetchError::UnknownCharset(_)
+            | FetchError::InvalidDate(_)
+            | FetchError::BlockedByRobots(_)
+            | FetchError::NotAllowed(_)
+            | FetchError::BudgetExhausted
+            | FetchError::NotOpenApi(_)
+            | FetchError::InvalidSelector(_)
+            | FetchError::SelectorNoMatch(_) => Self::user_error(e.to_string()),
+            FetchError::Playwright(_) | FetchError::BotChallenge | FetchError::Pdf(_) => {
+                Self::user_error(e.to_string())
+            }
             FetchError::Timeout(_) | FetchError::DnsResolution(_) => Self::internal(e.to_string()),
-            FetchError::Http(_) | FetchError::Status(_) | FetchError::TooLarge => {
+            FetchError::Http(_) | FetchError::Status(_) | FetchError::TooLarge(_) => {
                 Self::internal(e.to_string())
             }
         }
@@ -95,7 +122,9 @@ impl From<GeminiError> for ScoutError {
     fn from(e: GeminiError) -> Self {
         match &e {
             GeminiError::ApiKeyNotSet => Self::user_error(e.to_string()),
-            GeminiError::RateLimited => Self::user_error(e.to_string()),
+            GeminiError::RateLimited(_)
+            | GeminiError::NoAnswer(_)
+            | GeminiError::BudgetExhausted => Self::user_error(e.to_string()),
             GeminiError::QuotaExhausted(_) => Self::user_error(format!(
                 "{e} — check your API billing at https://aistudio.google.com"
             )),
@@ -104,17 +133,19 @@ impl From<GeminiError> for ScoutError {
     }
 }
 
+/// `None` (plus a note) when the fetch failed, so callers can tell a failure
+/// apart from a successful empty result.
 pub(super) fn unwrap_or_note<T>(
-    result: Result<Vec<T>, github::GitHubError>,
+    result: Result<T, github::GitHubError>,
     label: &str,
     notes: &mut Vec<String>,
-) -> Vec<T> {
+) -> Option<T> {
     match result {
-        Ok(v) => v,
+        Ok(v) => Some(v),
         Err(e) => {
             warn!(%e, "failed to fetch {}", label);
             notes.push(format!("Could not fetch {label} ({e})"));
-            vec![]
+            None
         }
     }
 }
@@ -131,9 +162,28 @@ mod tests {
 
     #[test]
     fn github_rate_limited_is_user_error() {
-        let err = ScoutError::from(github::GitHubError::RateLimited);
+        let err = ScoutError::from(github::GitHubError::RateLimited { reset_epoch: None });
         assert_eq!(err.exit_code(), 1);
         assert!(err.to_string().contains("rate limit"));
+        assert!(!err.to_string().contains("Retry after"));
+    }
+
+    #[test]
+    fn github_rate_limited_reports_wait_until_reset() {
+        let reset = SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + 120;
+        let err = ScoutError::from(github::GitHubError::RateLimited {
+            reset_epoch: Some(reset),
+        });
+        let message = err.to_string();
+        assert!(
+            message.contains("Retry after 120 seconds.")
+                || message.contains("Retry after 119 seconds."),
+            "got: {message}"
+        );
     }
 
     #[test]
@@ -180,7 +230,7 @@ mod tests {
 
     #[test]
     fn gemini_rate_limited_is_user_error() {
-        let err = ScoutError::from(GeminiError::RateLimited);
+        let err = ScoutError::from(GeminiError::RateLimited(None));
         assert_eq!(err.exit_code(), 1);
     }
 }
@@ -0,0 +1,204 @@
+use super::params::{FetchManyParams, FetchParams};
+use crate::env::parse_bool_env;
+use crate::fetch::{FetchError, FetchOptions};
+
+/// Implicit values for `fetch` flags the caller leaves out, from
+/// `SCOUT_FETCH_DEFAULT_RAW` and `SCOUT_FETCH_DEFAULT_META`. Explicit flags
+/// (`--raw=false`, `--metadata-only`) always win.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct FetchDefaults {
+    raw: bool,
+    metadata_only: bool,
+}
+
+impl FetchDefaults {
+    pub(super) fn from_env() -> Self {
+        Self::from_env_with(|var| std::env::var(var).ok())
+    }
+
+    fn from_env_with(env_reader: impl Fn(&str) -> Option<String>) -> Self {
+        let flag = |var: &str| parse_bool_env(&env_reader, var);
+        Self {
+            raw: flag("SCOUT_FETCH_DEFAULT_RAW").unwrap_or(false),
+            metadata_only: flag("SCOUT_FETCH_DEFAULT_META").unwrap_or(false),
+        }
+    }
+
+    /// Options for `fetch-many`, which only exposes `--raw` and `--metadata-only`.
+    pub(super) fn batch_options(&self, p: &FetchManyParams) -> FetchOptions {
+        FetchOptions {
+            raw: p.raw.unwrap_or(self.raw),
+            metadata_only: p.metadata_only.unwrap_or(self.metadata_only),
+            ..FetchOptions::default()
+        }
+    }
+
+    pub(super) fn options(&self, p: &FetchParams) -> Result<FetchOptions, FetchError> {
+        let charset = p
+            .charset
+            .as_deref()
+            .map(crate::fetch::parse_charset)
+            .transpose()?;
+        let selector = p
+            .selector
+            .as_deref()
+            .map(crate::fetch::parse_selector)
+            .transpose()?;
+        let if_modified_since = p
+            .if_modified_since
+            .as_deref()
+            .map(crate::fetch::parse_if_modified_since)
+            .transpose()?;
+        // A metadata default would drop the body, so any flag that works on
+        // the body overrides it. --compare also needs the extracted side, so
+        // it overrides a raw default too.
+        let wants_body = p.code_only
+            || p.links
+            || p.image
+            || p.compare
+            || selector.is_some()
+            || p.follow_pagination.is_some();
+        let metadata_only = p.metadata_only.unwrap_or(self.metadata_only && !wants_body);
+        Ok(FetchOptions {
+            js: p.js,
+            raw: p.raw.unwrap_or(self.raw && !p.compare),
+            charset,
+            metadata_only,
+            follow_pagination: p.follow_pagination,
+            code_only: p.code_only,
+            links: p.links,
+            flatten_links: p.flatten_links,
+            image: p.image,
+            compare: p.compare,
+            selector,
+            max_bytes: p.max_bytes.map(|n| n as usize),
+            if_modified_since,
+            accept_specs: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(raw: Option<bool>, metadata_only: Option<bool>) -> FetchParams {
+        FetchParams {
+            url: "https://example.com".into(),
+            js: false,
+            raw,
+            charset: None,
+            metadata_only,
+            follow_pagination: None,
+            code_only: false,
+            links: false,
+            flatten_links: false,
+            image: false,
+            compare: false,
+            selector: None,
+            max_bytes: None,
+            respect_robots: false,
+            chunk: None,
+            chunk_size: None,
+            translate_to: None,
+            if_modified_since: None,
+        }
+    }
+
+    fn with_env(pairs: &'static [(&'static str, &'static str)]) -> FetchDefaults {
+        FetchDefaults::from_env_with(|var| {
+            pairs
+                .iter()
+                .find(|(k, _)| *k == var)
+                .map(|(_, v)| v.to_string())
+        })
+    }
+
+    #[test]
+    fn unset_env_keeps_flags_off() {
+        let opts = with_env(&[]).options(&params(None, None)).unwrap();
+        assert!(!opts.raw);
+        assert!(!opts.metadata_only);
+    }
+
+    #[test]
+    fn omitted_flags_take_configured_defaults() {
+        let defaults = with_env(&[
+            ("SCOUT_FETCH_DEFAULT_RAW", "1"),
+            ("SCOUT_FETCH_DEFAULT_META", "true"),
+        ]);
+        let opts = defaults.options(&params(None, None)).unwrap();
+        assert!(opts.raw);
+        assert!(opts.metadata_only);
+    }
+
+    #[test]
+    fn explicit_flags_override_defaults() {
+        let defaults = with_env(&[
+            ("SCOUT_FETCH_DEFAULT_RAW", "yes"),
+            ("SCOUT_FETCH_DEFAULT_META", "1"),
+        ]);
+        let opts = defaults.options(&params(Some(false), Some(false))).unwrap();
+        assert!(!opts.raw);
+        assert!(!opts.metadata_only);
+
+        let opts = with_env(&[])
+            .options(&params(Some(true), Some(true)))
+            .unwrap();
+        assert!(opts.raw);
+        assert!(opts.metadata_only);
+    }
+
+    #[test]
+    fn code_only_suppresses_metadata_default() {
+        let defaults = with_env(&[("SCOUT_FETCH_DEFAULT_META", "1")]);
+        let mut p = params(None, None);
+        p.code_only = true;
+        assert!(!defaults.options(&p).unwrap().metadata_only);
+    }
+
+    #[test]
+    fn links_suppresses_metadata_default() {
+        let defaults = with_env(&[("SCOUT_FETCH_DEFAULT_META", "1")]);
+        let mut p = params(None, None);
+        p.links = true;
+        let opts = defaults.options(&p).unwrap();
+        assert!(opts.links);
+        assert!(!opts.metadata_only);
+    }
+
+    #[test]
+    fn body_flags_suppress_metadata_default() {
+        let defaults = with_env(&[("SCOUT_FETCH_DEFAULT_META", "1")]);
+        let cases: [fn(&mut FetchParams); 3] = [
+            |p| p.selector = Some("article".into()),
+            |p| p.image = true,
+            |p| p.follow_pagination = Some(3),
+        ];
+        for set in cases {
+            let mut p = params(None, None);
+            set(&mut p);
+            assert!(!defaults.options(&p).unwrap().metadata_only);
+        }
+    }
+
+    #[test]
+    fn compare_suppresses_raw_and_metadata_defaults() {
+        let defaults = with_env(&[
+            ("SCOUT_FETCH_DEFAULT_RAW", "1"),
+            ("SCOUT_FETCH_DEFAULT_META", "1"),
+        ]);
+        let mut p = params(None, None);
+        p.compare = true;
+        let opts = defaults.options(&p).unwrap();
+        assert!(opts.compare);
+        assert!(!opts.raw);
+        assert!(!opts.metadata_only);
+    }
+
+    #[test]
+    fn invalid_values_are_ignored() {
+        let defaults = with_env(&[("SCOUT_FETCH_DEFAULT_RAW", "sometimes")]);
+        assert!(!defaults.options(&params(None, None)).unwrap().raw);
+    }
+}
@@ -1,31 +1,38 @@
+mod enabled;
 mod errors;
+mod fetch_defaults;
 mod params;
 
+pub use enabled::EnabledTools;
 pub use errors::ScoutError;
 pub use params::Command;
 
+use std::collections::HashMap;
 use std::time::Duration;
 
 use reqwest::Client;
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 
 use errors::{parse_repo_param, unwrap_or_note};
+use fetch_defaults::FetchDefaults;
 use params::{
-    FetchParams, RepoOverviewParams, RepoReadParams, RepoTreeParams, ResearchParams, SearchParams,
+    FetchManyParams, FetchParams, OpenapiSummaryParams, RepoBranchesParams, RepoCiParams,
+    RepoCommitsParams, RepoDiffParams, RepoFileHistoryParams, RepoGrepFilesParams,
+    RepoIssueDigestParams, RepoIssueParams, RepoLsParams, RepoOverviewParams, RepoPrFilesParams,
+    RepoReadParams, RepoSearchCodeParams, RepoSearchIssuesParams, RepoTagsParams, RepoTreeParams,
+    ResearchParams, SearchParams, SearchReadParams, Verbosity,
 };
 
-use crate::fetch::{FetchOptions, TokioDnsResolver};
+use crate::budget::RequestBudget;
+use crate::fetch::{PublicOnlyResolver, TokioDnsResolver};
 use crate::gemini::client::{GeminiClient, GeminiError, SearchClient as _};
+use crate::gemini::grounding::EmptyAnswerMode;
+use crate::github::dates::DateStyle;
+use crate::github::types::{EntryType, TreeEntry};
 use crate::github::{self, GitHubClient};
-use crate::markdown::{escape_md_link, shift_headings, truncate_with_note};
+use crate::markdown::{escape_md_link, shift_headings, split_chunks, truncate_with_note};
 use crate::search::engine;
 
-impl From<&FetchParams> for FetchOptions {
-    fn from(p: &FetchParams) -> Self {
-        Self { js: p.js, raw: p.raw }
-    }
-}
-
 const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
 const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
 /// HTTP_TIMEOUT (30s) + PLAYWRIGHT_TIMEOUT (60s) + 5s margin.
@@ -34,31 +41,65 @@ const MAX_REDIRECTS: usize = 5;
 const OVERVIEW_ITEMS: u8 = 5;
 const OVERVIEW_RELEASES: u8 = 3;
 const MAX_FETCH_OUTPUT_BYTES: usize = 100_000;
+/// Default `fetch --chunk-size`, roughly 5K tokens of English prose.
+const DEFAULT_CHUNK_CHARS: usize = 20_000;
+/// Tags considered by `repo_read --resolve-tag` (one API page).
+const MAX_RESOLVE_TAGS: u8 = 100;
+/// Files per `repo_read` call, counting the main path.
+const MAX_REPO_READ_FILES: usize = 10;
+/// Caps for `repo_tree --inline-under-bytes`: keeps a broad listing from
+/// turning into a bulk download.
+const MAX_INLINE_FILES: usize = 20;
+const MAX_INLINE_TOTAL_BYTES: u64 = 64_000;
+/// Budgets for `repo_grep_files`: files read, preview text returned, and the
+/// largest blob worth downloading for a few preview lines.
+const MAX_PREVIEW_FILES: usize = 20;
+const MAX_PREVIEW_TOTAL_BYTES: usize = 64_000;
+const MAX_PREVIEW_FILE_BYTES: u64 = 256_000;
+/// `fetch_many` limits: URLs per call, and pages fetched at once.
+const MAX_FETCH_MANY_URLS: usize = 20;
+const FETCH_MANY_CONCURRENCY: usize = 5;
+/// Comments read by `repo_issue_digest` (one API page).
+const MAX_DIGEST_COMMENTS: u8 = 100;
 /// Slack: up to 3 API calls + N user resolutions; 60s covers large threads.
 const SLACK_TOOL_TIMEOUT: Duration = Duration::from_secs(60);
 
 pub struct Scout {
+    /// Page fetches; refuses private and loopback addresses.
     http: Client,
+    /// GitHub, Gemini, and Slack APIs.
+    api_http: Client,
     gemini: Option<GeminiClient>,
     github: GitHubClient,
+    enabled: EnabledTools,
+    fetch_defaults: FetchDefaults,
+    empty_answer: EmptyAnswerMode,
+    date_style: DateStyle,
 }
 
 impl Scout {
-    pub async fn new() -> Result<Self, ScoutError> {
-        let http = Client::builder()
-            .connect_timeout(CONNECT_TIMEOUT)
-            .timeout(HTTP_TIMEOUT)
-            .redirect(reqwest::redirect::Policy::limited(MAX_REDIRECTS))
-            .build()
-            .map_err(|e| ScoutError::internal(format!("HTTP client init failed: {e}")))?;
-        let gemini = GeminiClient::from_env(http.clone())
+    pub async fn new(enabled: EnabledTools) -> Result<Self, ScoutError> {
+        // Page fetches go to arbitrary user-supplied URLs and are confined to
+        // public addresses. Through a proxy, reqwest only resolves the proxy's
+        // own host (often loopback) and the proxy resolves the target, so the
+        // connect-time check cannot apply; the DNS pre-check still does.
+        let http = build_http(!proxy_configured())?;
+        // API clients talk to fixed, operator-configured endpoints, which may
+        // legitimately sit on a private network (GitHub Enterprise).
+        let api_http = build_http(false)?;
+        let gemini = GeminiClient::from_env(api_http.clone())
             .inspect_err(|e| warn!("Gemini client not available: {e}"))
             .ok();
-        let github = GitHubClient::from_env(http.clone()).await;
+        let github = GitHubClient::from_env(api_http.clone());
         Ok(Self {
             http,
+            api_http,
             gemini,
             github,
+            enabled,
+            fetch_defaults: FetchDefaults::from_env(),
+            empty_answer: EmptyAnswerMode::from_env(),
+            date_style: DateStyle::from_env(),
         })
     }
 
@@ -69,13 +110,35 @@ impl Scout {
     }
 
     pub async fn run(&self, cmd: Command) -> Result<String, ScoutError> {
+        if !self.enabled.allows(cmd.name()) {
+            return Err(ScoutError::user_error(format!(
+                "tool '{}' is disabled (not listed in SCOUT_ENABLED_TOOLS)",
+                cmd.name()
+            )));
+        }
         match cmd {
             Command::Search(params) => self.search(params).await,
             Command::Fetch(params) => self.fetch(params).await,
+            Command::FetchMany(params) => self.fetch_many(params).await,
+            Command::OpenapiSummary(params) => self.openapi_summary(params).await,
             Command::Research(params) => self.research(params).await,
+            Command::SearchRead(params) => self.search_read(params).await,
             Command::RepoTree(params) => self.repo_tree(params).await,
+            Command::RepoLs(params) => self.repo_ls(params).await,
             Command::RepoRead(params) => self.repo_read(params).await,
+            Command::RepoGrepFiles(params) => self.repo_grep_files(params).await,
             Command::RepoOverview(params) => self.repo_overview(params).await,
+            Command::RepoCi(params) => self.repo_ci(params).await,
+            Command::RepoCommits(params) => self.repo_commits(params).await,
+            Command::RepoFileHistory(params) => self.repo_file_history(params).await,
+            Command::RepoBranches(params) => self.repo_branches(params).await,
+            Command::RepoTags(params) => self.repo_tags(params).await,
+            Command::RepoSearchCode(params) => self.repo_search_code(params).await,
+            Command::RepoSearchIssues(params) => self.repo_search_issues(params).await,
+            Command::RepoDiff(params) => self.repo_diff(params).await,
+            Command::RepoPrFiles(params) => self.repo_pr_files(params).await,
+            Command::RepoIssue(params) => self.repo_issue(params).await,
+            Command::RepoIssueDigest(params) => self.repo_issue_digest(params).await,
         }
     }
 
@@ -84,14 +147,14 @@ impl Scout {
 
         let gemini = self.gemini()?;
         let search_query = params.lang.apply_to_query(&params.query);
-        let result = gemini.search(&search_query).await?;
+        let result = gemini
+            .search(&search_query, &RequestBudget::from_env())
+            .await?;
 
-        let mut output = result
-            .answer
-            .unwrap_or_else(|| {
-                "(No answer returned — the query may have been filtered by safety settings.)"
-                    .to_string()
-            });
+        let mut output = self.empty_answer.answer(&result)?;
+        if let Some(max_chars) = params.max_answer_chars {
+            output = cap_answer(output, max_chars);
+        }
 
         if !result.sources.is_empty() {
             output.push_str("\n\n---\n**Sources:**\n");
@@ -113,31 +176,66 @@ impl Scout {
             return self.fetch_slack(slack_url).await;
         }
 
-        info!(url = %params.url, js = params.js, raw = params.raw, "fetch");
+        let opts = self.fetch_defaults.options(&params)?;
+        info!(url = %params.url, js = opts.js, raw = opts.raw, "fetch");
 
-        let opts = FetchOptions::from(&params);
-        let result = tokio::time::timeout(
-            FETCH_TOOL_TIMEOUT,
-            crate::fetch::fetch_page(&self.http, &params.url, opts, &TokioDnsResolver),
-        )
+        // Check translation prerequisites before spending a download.
+        let translate = match params.translate_to {
+            Some(lang) => {
+                let target = lang.name().ok_or_else(|| {
+                    ScoutError::user_error("--translate-to needs a concrete language (ja or en)")
+                })?;
+                Some((self.gemini()?, target))
+            }
+            None => None,
+        };
+
+        let chunked = params.chunk.is_some() || params.chunk_size.is_some();
+        let budget = RequestBudget::from_env();
+        let result = tokio::time::timeout(FETCH_TOOL_TIMEOUT, async {
+            if params.respect_robots {
+                crate::fetch::robots::check(&self.http, &params.url, &budget, &TokioDnsResolver)
+                    .await?;
+            }
+            let result =
+                crate::fetch::fetch_page(&self.http, &params.url, opts, &budget, &TokioDnsResolver)
+                    .await?;
+            if result.used_raw_fallback {
+                warn!(url = %params.url, "readability extraction failed, using raw fallback");
+            }
+            let result = match translate {
+                // Chunk numbering needs the whole translated page; otherwise only
+                // what the output budget keeps is worth translating.
+                Some((gemini, target)) => {
+                    let max_body_bytes = (!chunked).then_some(MAX_FETCH_OUTPUT_BYTES);
+                    translate_fetch_result(gemini, result, target, max_body_bytes, &budget).await?
+                }
+                None => result,
+            };
+            Ok::<_, ScoutError>(result)
+        })
         .await
         .unwrap_or_else(|_| {
             Err(crate::fetch::FetchError::Timeout(format!(
                 "fetch timed out after {}s",
                 FETCH_TOOL_TIMEOUT.as_secs()
-            )))
+            ))
+            .into())
         })?;
 
-        if result.used_raw_fallback {
-            warn!(url = %params.url, "readability extraction failed, using raw fallback");
+        if chunked {
+            return format_fetch_chunk(
+                &result,
+                params.chunk.unwrap_or(1),
+                params.chunk_size.unwrap_or(DEFAULT_CHUNK_CHARS),
+            );
         }
-
         Ok(format_fetch_output(&result))
     }
 
     async fn fetch_slack(&self, slack_url: crate::slack::SlackUrl) -> Result<String, ScoutError> {
         info!(workspace = %slack_url.workspace, channel = %slack_url.channel, "fetch (slack)");
-        let client = crate::slack::SlackClient::from_env(self.http.clone())?;
+        let client = crate::slack::SlackClient::from_env(self.api_http.clone())?;
         let output = tokio::time::timeout(
             SLACK_TOOL_TIMEOUT,
             client.fetch_message(&slack_url),
@@ -155,6 +253,58 @@ impl Scout {
         Ok(truncate_with_note(&output, MAX_FETCH_OUTPUT_BYTES).into_owned())
     }
 
+    async fn fetch_many(&self, params: FetchManyParams) -> Result<String, ScoutError> {
+        let mut seen = std::collections::HashSet::new();
+        let urls: Vec<String> = params
+            .urls
+            .iter()
+            .filter(|url| seen.insert(url.as_str()))
+            .cloned()
+            .collect();
+        if urls.len() > MAX_FETCH_MANY_URLS {
+            return Err(ScoutError::user_error(format!(
+                "too many URLs: {} given, at most {MAX_FETCH_MANY_URLS}",
+                urls.len()
+            )));
+        }
+
+        let opts = self.fetch_defaults.batch_options(&params);
+        info!(urls = urls.len(), raw = opts.raw, "fetch_many");
+
+        let outcomes = fetch_urls(
+            &self.http,
+            urls,
+            opts,
+            FETCH_TOOL_TIMEOUT,
+            &RequestBudget::from_env(),
+            &TokioDnsResolver,
+        )
+        .await;
+        Ok(format_fetch_many(&outcomes))
+    }
+
+    async fn openapi_summary(&self, params: OpenapiSummaryParams) -> Result<String, ScoutError> {
+        info!(url = %params.url, "openapi_summary");
+        let budget = RequestBudget::from_env();
+        let summary = tokio::time::timeout(
+            FETCH_TOOL_TIMEOUT,
+            crate::fetch::fetch_openapi_summary(
+                &self.http,
+                &params.url,
+                &budget,
+                &TokioDnsResolver,
+            ),
+        )
+        .await
+        .unwrap_or_else(|_| {
+            Err(crate::fetch::FetchError::Timeout(format!(
+                "fetch timed out after {}s",
+                FETCH_TOOL_TIMEOUT.as_secs()
+            )))
+        })?;
+        Ok(truncate_with_note(&summary, MAX_FETCH_OUTPUT_BYTES).into_owned())
+    }
+
     async fn research(&self, params: ResearchParams) -> Result<String, ScoutError> {
         info!(query = %params.query, depth = params.depth, "research");
 
@@ -164,8 +314,13 @@ impl Scout {
             query: &params.query,
             depth: params.depth,
             lang: params.lang,
+            bilingual: crate::search::bilingual::expansion_enabled(),
+            concurrency: engine::ResearchConcurrency::from_env(),
+            budget: RequestBudget::from_env(),
         };
         let report = engine::research(gemini, &self.http, &req, &TokioDnsResolver).await?;
+        self.empty_answer
+            .ensure_any_answer(&report.search_results)?;
 
         info!(
             pages = report.fetched_pages.len(),
@@ -174,7 +329,37 @@ impl Scout {
             "research complete"
         );
 
-        Ok(engine::format_report(&report, &params.query))
+        Ok(engine::format_report(
+            &report,
+            &params.query,
+            params.citations.unwrap_or_default(),
+            engine::report_char_budget(),
+        ))
+    }
+
+    async fn search_read(&self, params: SearchReadParams) -> Result<String, ScoutError> {
+        info!(query = %params.query, "search_read");
+
+        let gemini = self.gemini()?;
+        let report = engine::search_and_read(
+            gemini,
+            &self.http,
+            &params.query,
+            params.lang,
+            &RequestBudget::from_env(),
+            &TokioDnsResolver,
+        )
+        .await?;
+        self.empty_answer
+            .ensure_any_answer(std::slice::from_ref(&report.search_result))?;
+
+        info!(
+            fetched = report.page.is_some(),
+            sources = report.sources.len(),
+            "search_read complete"
+        );
+
+        Ok(engine::format_search_read(&report, &params.query))
     }
 
     async fn repo_tree(&self, params: RepoTreeParams) -> Result<String, ScoutError> {
@@ -195,99 +380,498 @@ impl Scout {
         }
 
         let tree = self.github.get_tree(owner, repo, &ref_).await?;
+        let subtree = match params.path.as_deref() {
+            Some(p) if tree.truncated => self.subtree_fallback(owner, repo, &ref_, p).await,
+            _ => None,
+        };
+        let (entries, truncated) = match &subtree {
+            Some((dir, sub)) => {
+                debug!(dir, "root tree truncated, listing subtree instead");
+                (&sub.tree, sub.truncated)
+            }
+            None => (&tree.tree, tree.truncated),
+        };
 
         let filtered = github::filter_tree_entries(
-            &tree.tree,
+            entries,
             params.path.as_deref(),
             params.pattern.as_deref(),
         )?;
 
-        let output = github::format::format_tree(owner, repo, &ref_, &filtered, tree.truncated);
+        let (inlined, skipped) = match params.inline_under_bytes {
+            Some(threshold) => {
+                self.inline_small_files(owner, repo, &ref_, &filtered, threshold)
+                    .await
+            }
+            None => (HashMap::new(), 0),
+        };
+
+        let filter = github::format::TreeFilter {
+            path: params.path.as_deref(),
+            pattern: params.pattern.as_deref(),
+            total_files: entries
+                .iter()
+                .filter(|e| e.entry_type == EntryType::Blob)
+                .count(),
+        };
+        let verbosity = params.verbosity.unwrap_or_default();
+        let inlined_count = inlined.len();
+        let mut output = if params.tree_view {
+            github::format::format_tree_diagram(
+                owner, repo, &ref_, &filtered, &filter, truncated, verbosity,
+            )
+        } else {
+            let style = github::format::TreeStyle {
+                verbosity,
+                blob_base: format!("{}/{owner}/{repo}/blob", self.github.web_base()),
+                inlined,
+            };
+            github::format::format_tree(owner, repo, &ref_, &filtered, &filter, truncated, &style)
+        };
+        if let Some((dir, _)) = &subtree {
+            output.push_str(&format!(
+                "\n> **Note:** GitHub truncated the full tree, so `{dir}/` was listed from its own subtree (2 extra requests).\n"
+            ));
+        }
+        if skipped > 0 {
+            output.push_str(&format!(
+                "\n> **Note:** {skipped} more small files not inlined (limit: {MAX_INLINE_FILES} files / {MAX_INLINE_TOTAL_BYTES} bytes).\n"
+            ));
+        }
 
-        info!(files = filtered.len(), "repo_tree complete");
+        info!(
+            files = filtered.len(),
+            inlined = inlined_count,
+            "repo_tree complete"
+        );
         Ok(output)
     }
 
-    async fn repo_read(&self, params: RepoReadParams) -> Result<String, ScoutError> {
+    async fn repo_ls(&self, params: RepoLsParams) -> Result<String, ScoutError> {
         let (owner, repo) = parse_repo_param(&params.repository)?;
 
-        info!(repository = %params.repository, path = %params.path, "repo_read");
+        info!(repository = %params.repository, path = ?params.path, "repo_ls");
 
-        github::validate_path(&params.path)?;
+        let path = params.path.as_deref().map(|p| p.trim_end_matches('/'));
+        if let Some(p) = path {
+            github::validate_path(p)?;
+        }
         if let Some(ref r) = params.ref_ {
             github::validate_ref(r)?;
         }
 
-        let contents = self
+        let entries = self
             .github
-            .get_contents(owner, repo, &params.path, params.ref_.as_deref())
+            .list_dir(
+                owner,
+                repo,
+                path.unwrap_or_default(),
+                params.ref_.as_deref(),
+            )
             .await?;
 
-        let raw = if let Some(ref encoded) = contents.content {
-            github::decode_content(encoded)?
-        } else {
-            let blob = self.github.get_blob(owner, repo, &contents.sha).await?;
-            github::decode_content(&blob.content)?
+        info!(entries = entries.len(), "repo_ls complete");
+        Ok(github::format::format_dir_listing(
+            owner,
+            repo,
+            path,
+            params.ref_.as_deref(),
+            &entries,
+            params.verbosity.unwrap_or_default(),
+        ))
+    }
+
+    /// The subtree under path prefix `path`, for when the root tree came back
+    /// truncated. Best effort: on failure the truncated root tree is used.
+    async fn subtree_fallback(
+        &self,
+        owner: &str,
+        repo: &str,
+        ref_: &str,
+        path: &str,
+    ) -> Option<(String, github::types::TreeResponse)> {
+        let dir = path.trim_end_matches('/');
+        if dir.is_empty() {
+            return None;
+        }
+        match self.github.get_subtree(owner, repo, ref_, dir).await {
+            Ok(subtree) => subtree.map(|t| (dir.to_string(), t)),
+            Err(e) => {
+                warn!(dir, error = %e, "subtree fetch failed, using truncated tree");
+                None
+            }
+        }
+    }
+
+    async fn repo_grep_files(&self, params: RepoGrepFilesParams) -> Result<String, ScoutError> {
+        let (owner, repo) = parse_repo_param(&params.repository)?;
+
+        info!(repository = %params.repository, pattern = %params.pattern, "repo_grep_files");
+
+        let ref_ = match params.ref_ {
+            Some(r) => {
+                github::validate_ref(&r)?;
+                r
+            }
+            None => self.github.get_repo(owner, repo).await?.default_branch,
         };
 
-        let total = raw.lines().count();
-        let content = if let Some(ref range) = params.lines {
-            let (start, end) = github::parse_line_range(range)?;
-            github::apply_line_range(&raw, start, end)
-        } else {
-            github::apply_line_range(&raw, 1, None)
+        if let Some(ref p) = params.path {
+            github::validate_path(p)?;
+        }
+
+        let tree = self.github.get_tree(owner, repo, &ref_).await?;
+        let matched =
+            github::filter_tree_entries(&tree.tree, params.path.as_deref(), Some(&params.pattern))?;
+        // Large blobs (often binaries or generated files) are listed, not
+        // downloaded whole for a few preview lines.
+        let (small, large): (Vec<&TreeEntry>, Vec<&TreeEntry>) = matched
+            .iter()
+            .copied()
+            .partition(|e| e.size.is_none_or(|s| s <= MAX_PREVIEW_FILE_BYTES));
+        let paths: Vec<&str> = small.iter().map(|e| e.path.as_str()).collect();
+        let (to_read, unread) = paths.split_at(paths.len().min(MAX_PREVIEW_FILES));
+
+        let lines = usize::from(params.lines);
+        let mut budget = MAX_PREVIEW_TOTAL_BYTES;
+        let mut previews = Vec::new();
+        let mut over_budget = Vec::new();
+        for (path, result) in self
+            .github
+            .read_files(owner, repo, to_read, Some(&ref_))
+            .await
+        {
+            let body = result.map(|content| {
+                let total = content.lines().count();
+                (
+                    github::fence_line_range(&content, path, 1, Some(lines)),
+                    total,
+                )
+            });
+            let size = body.as_ref().map_or(0, |(fenced, _)| fenced.len());
+            if size > budget {
+                over_budget.push(path);
+                continue;
+            }
+            budget -= size;
+            previews.push(github::format::FilePreview {
+                path,
+                body: body.map_err(|e| e.to_string()),
+            });
+        }
+        let mut omitted = over_budget;
+        omitted.extend_from_slice(unread);
+        omitted.extend(large.iter().map(|e| e.path.as_str()));
+
+        let mut output = github::format::format_file_previews(
+            owner,
+            repo,
+            &ref_,
+            &params.pattern,
+            &previews,
+            &omitted,
+        );
+        if !omitted.is_empty() {
+            output.push_str(&format!(
+                "\n> **Note:** {} matching files not previewed (limit: {MAX_PREVIEW_FILES} files / {MAX_PREVIEW_TOTAL_BYTES} bytes, {MAX_PREVIEW_FILE_BYTES} bytes per file).\n",
+                omitted.len()
+            ));
+        }
+        if tree.truncated {
+            output.push_str(
+                "\n> **Note:** tree truncated by GitHub — some matching files may be missing.\n",
+            );
+        }
+
+        info!(
+            matched = matched.len(),
+            previewed = previews.len(),
+            "repo_grep_files complete"
+        );
+        Ok(output)
+    }
+
+    /// Read blobs under `threshold` bytes concurrently, stopping at the file-count
+    /// and total-byte budgets. Returns the inlined contents and the number of
+    /// eligible files left out. Read failures are inlined as a short note.
+    async fn inline_small_files(
+        &self,
+        owner: &str,
+        repo: &str,
+        ref_: &str,
+        entries: &[&TreeEntry],
+        threshold: u64,
+    ) -> (HashMap<String, String>, usize) {
+        let eligible: Vec<&TreeEntry> = entries
+            .iter()
+            .copied()
+            .filter(|e| e.size.is_some_and(|s| s < threshold))
+            .collect();
+
+        let mut budget = MAX_INLINE_TOTAL_BYTES;
+        let mut selected = Vec::new();
+        for entry in &eligible {
+            let size = entry.size.unwrap_or(0);
+            if selected.len() >= MAX_INLINE_FILES || size > budget {
+                break;
+            }
+            budget -= size;
+            selected.push(entry.path.as_str());
+        }
+        let skipped = eligible.len() - selected.len();
+
+        let inlined = self
+            .github
+            .read_files(owner, repo, &selected, Some(ref_))
+            .await
+            .into_iter()
+            .map(|(path, result)| {
+                let content = result.unwrap_or_else(|e| {
+                    warn!(path, %e, "failed to inline file");
+                    format!("(could not read file: {e})")
+                });
+                (path.to_string(), content)
+            })
+            .collect();
+
+        (inlined, skipped)
+    }
+
+    async fn repo_read(&self, params: RepoReadParams) -> Result<String, ScoutError> {
+        let (owner, repo) = parse_repo_param(&params.repository)?;
+
+        info!(repository = %params.repository, path = %params.path, "repo_read");
+
+        github::validate_path(&params.path)?;
+        if let Some(ref r) = params.ref_ {
+            github::validate_ref(r)?;
+        }
+        let line_range = params
+            .lines
+            .as_deref()
+            .map(github::parse_line_range)
+            .transpose()?;
+
+        let mut notes = Vec::new();
+        let pr_head = match params.pr {
+            Some(number) => Some((number, self.pull_head(owner, repo, number).await?)),
+            None => None,
+        };
+        let (owner, repo, ref_) = match &pr_head {
+            Some((number, (head_owner, head_repo, sha))) => {
+                notes.push(format!(
+                    "Read at the head of pull request #{number}: `{sha}` in {head_owner}/{head_repo}."
+                ));
+                (head_owner.as_str(), head_repo.as_str(), Some(sha.as_str()))
+            }
+            None => (owner, repo, params.ref_.as_deref()),
         };
+        if !params.paths.is_empty() {
+            return self
+                .repo_read_many(owner, repo, ref_, &params, line_range, notes)
+                .await;
+        }
 
-        let output = format!("{} ({total} lines)\n\n{content}", params.path);
+        let raw = match self.github.read_file(owner, repo, &params.path, ref_).await {
+            Err(github::GitHubError::NotFound(missing)) if wants_tag_fallback(&params) => {
+                let requested = params.ref_.as_deref().unwrap_or_default();
+                // Only a ref that does not exist is retried as a tag; a file
+                // missing at an existing ref is reported as is.
+                if self.github.ref_exists(owner, repo, requested).await? {
+                    return Err(github::GitHubError::NotFound(missing).into());
+                }
+                let tag = self.resolve_version_ref(owner, repo, requested).await?;
+                notes.push(format!("Ref `{requested}` not found; using tag `{tag}`."));
+                self.github
+                    .read_file(owner, repo, &params.path, Some(&tag))
+                    .await?
+            }
+            result => result?,
+        };
 
-        info!(path = %params.path, lines = total, "repo_read complete");
+        let output = format_read_file(&params.path, &raw, &params, line_range, notes);
+        info!(path = %params.path, lines = raw.lines().count(), "repo_read complete");
         Ok(output)
     }
 
+    /// `repo_read` with extra paths: every file is read concurrently with the
+    /// same ref and range, and a file that cannot be read becomes a note.
+    async fn repo_read_many(
+        &self,
+        owner: &str,
+        repo: &str,
+        ref_: Option<&str>,
+        params: &RepoReadParams,
+        line_range: Option<(usize, Option<usize>)>,
+        mut notes: Vec<String>,
+    ) -> Result<String, ScoutError> {
+        let mut seen = std::collections::HashSet::new();
+        let paths: Vec<&str> = std::iter::once(&params.path)
+            .chain(&params.paths)
+            .map(String::as_str)
+            .filter(|p| seen.insert(*p))
+            .collect();
+        if paths.len() > MAX_REPO_READ_FILES {
+            return Err(ScoutError::user_error(format!(
+                "too many paths: {} given, at most {MAX_REPO_READ_FILES}",
+                paths.len()
+            )));
+        }
+        for path in &paths {
+            github::validate_path(path)?;
+        }
+
+        let mut results = self.github.read_files(owner, repo, &paths, ref_).await;
+        let all_missing = results
+            .iter()
+            .all(|(_, r)| matches!(r, Err(github::GitHubError::NotFound(_))));
+        let requested = params.ref_.as_deref().unwrap_or_default();
+        if all_missing
+            && wants_tag_fallback(params)
+            && !self.github.ref_exists(owner, repo, requested).await?
+        {
+            let tag = self.resolve_version_ref(owner, repo, requested).await?;
+            notes.push(format!("Ref `{requested}` not found; using tag `{tag}`."));
+            results = self
+                .github
+                .read_files(owner, repo, &paths, Some(&tag))
+                .await;
+        }
+
+        let sections: Vec<String> = results
+            .into_iter()
+            .map(|(path, result)| match result {
+                Ok(raw) => format_read_file(path, &raw, params, line_range, notes.clone()),
+                Err(e) => format!("{path}\n\n> **Note:** Could not read: {e}\n"),
+            })
+            .collect();
+        let output = sections
+            .iter()
+            .map(|s| s.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n\n---\n\n")
+            + "\n";
+
+        info!(files = paths.len(), "repo_read complete");
+        Ok(truncate_with_note(&output, MAX_FETCH_OUTPUT_BYTES).into_owned())
+    }
+
+    /// Owner, name, and head commit SHA of the repository a pull request's
+    /// changes live in: the contributor's fork when the PR comes from one.
+    async fn pull_head(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<(String, String, String), ScoutError> {
+        let pull = self
+            .github
+            .get_pull(owner, repo, number)
+            .await
+            .map_err(|e| match e {
+                github::GitHubError::NotFound(_) => github::GitHubError::NotFound(format!(
+                    "pull request #{number} in {owner}/{repo}"
+                )),
+                e => e,
+            })?;
+        let head_repo = pull.head.repo.ok_or_else(|| {
+            github::GitHubError::NotFound(format!(
+                "head repository of pull request #{number} (the fork was deleted)"
+            ))
+        })?;
+        let (head_owner, head_name) = parse_repo_param(&head_repo.full_name)?;
+        Ok((head_owner.to_string(), head_name.to_string(), pull.head.sha))
+    }
+
+    /// Map a partial version ref like `v1.2` to the best matching tag from
+    /// the repository's most recent tags.
+    async fn resolve_version_ref(
+        &self,
+        owner: &str,
+        repo: &str,
+        requested: &str,
+    ) -> Result<String, ScoutError> {
+        let tags = self.github.get_tags(owner, repo, MAX_RESOLVE_TAGS).await?;
+        github::resolve_version_tag(requested, tags.iter().map(|t| t.name.as_str()))
+            .map(str::to_string)
+            .ok_or_else(|| {
+                ScoutError::from(github::GitHubError::NotFound(format!(
+                    "ref '{requested}' (no tag matches this version)"
+                )))
+            })
+    }
+
     async fn repo_overview(&self, params: RepoOverviewParams) -> Result<String, ScoutError> {
         let (owner, repo) = parse_repo_param(&params.repository)?;
 
         info!(repository = %params.repository, "repo_overview");
 
-        let (repo_info, readme, issues, pulls, releases) = tokio::join!(
-            self.github.get_repo(owner, repo),
-            self.github.get_readme(owner, repo),
-            self.github.get_issues(owner, repo, OVERVIEW_ITEMS),
-            self.github.get_pulls(owner, repo, OVERVIEW_ITEMS),
-            self.github.get_releases(owner, repo, OVERVIEW_RELEASES),
-        );
-
-        let repo_info = repo_info?;
+        let wants = |section: &str| {
+            params
+                .sections
+                .as_ref()
+                .is_none_or(|s| s.iter().any(|x| x == section))
+        };
+        let verbosity = params.verbosity.unwrap_or_default();
+        let sections = github::OverviewSections {
+            meta: wants("meta"),
+            readme: wants("readme") && verbosity != Verbosity::Compact,
+            issues: wants("issues"),
+            pulls: wants("pulls"),
+            releases: wants("releases"),
+        };
 
         let mut notes = Vec::new();
-
-        let readme_content = match readme {
-            Ok(r) => r.content.and_then(|c| match github::decode_content(&c) {
-                Ok(content) => Some(content),
-                Err(e) => {
-                    warn!(%e, "failed to decode README");
-                    notes.push(format!("README could not be decoded ({e})"));
-                    None
-                }
-            }),
-            Err(e) => {
-                if !matches!(e, github::GitHubError::NotFound(_)) {
-                    warn!(%e, "failed to fetch README");
-                    notes.push(format!("Could not fetch README ({e})"));
+        // With a token, one GraphQL request replaces `rest_overview`; any
+        // GraphQL failure falls back to REST, which reports errors per section.
+        let graphql = if self.github.has_token().await {
+            self.github
+                .get_overview(owner, repo, sections, OVERVIEW_ITEMS, OVERVIEW_RELEASES)
+                .await
+                .inspect_err(|e| warn!(%e, "GraphQL overview failed, falling back to REST"))
+                .ok()
+        } else {
+            None
+        };
+        let github::Overview {
+            repo: repo_info,
+            readme: readme_content,
+            issues,
+            pulls,
+            releases,
+        } = match graphql {
+            Some(mut overview) => {
+                if sections.readme && overview.readme.is_none() {
+                    let readme = self.github.get_readme(owner, repo).await;
+                    overview.readme = readme_text(readme, &mut notes);
                 }
-                None
+                overview
             }
+            None => {
+                self.rest_overview(owner, repo, sections, &mut notes)
+                    .await?
+            }
+        };
+        let readme_content = match readme_content {
+            Some(raw) if params.render_readme => Some(
+                self.render_readme(owner, repo, &repo_info, raw, &mut notes)
+                    .await,
+            ),
+            readme => readme,
         };
-        let issues = unwrap_or_note(issues, "issues", &mut notes);
-        let pulls = unwrap_or_note(pulls, "pull requests", &mut notes);
-        let releases = unwrap_or_note(releases, "releases", &mut notes);
 
         let mut output = github::format::format_overview(
             &repo_info,
+            sections.meta,
             readme_content.as_deref(),
-            &issues,
-            &pulls,
-            &releases,
+            issues.as_deref(),
+            pulls.as_deref(),
+            releases.as_deref(),
+            github::format::OverviewStyle {
+                max_labels: params.max_labels.into(),
+                dates: self.date_style.formatter(),
+                verbosity,
+            },
         );
 
         if !notes.is_empty() {
@@ -297,107 +881,2692 @@ impl Scout {
         }
 
         info!(
-            issues = issues.len(),
-            pulls = pulls.len(),
-            releases = releases.len(),
+            issues = issues.as_ref().map_or(0, Vec::len),
+            pulls = pulls.as_ref().map_or(0, Vec::len),
+            releases = releases.as_ref().map_or(0, Vec::len),
             has_readme = readme_content.is_some(),
             "repo_overview complete"
         );
         Ok(output)
     }
-}
-
-fn format_fetch_output(result: &crate::fetch::converter::FetchResult) -> String {
-    let shifted = shift_headings(&result.markdown, 2);
-    let output = if result.used_raw_fallback {
-        format!("{}{shifted}", crate::fetch::converter::RAW_FALLBACK_NOTE)
-    } else {
-        shifted
-    };
 
-    truncate_with_note(&output, MAX_FETCH_OUTPUT_BYTES).into_owned()
-}
+    /// `raw` rendered by GitHub and converted back to Markdown, which
+    /// normalizes GFM constructs and tables. Relative links and images are
+    /// resolved against the default branch first, since the rendered HTML
+    /// leaves them relative. Falls back to `raw` with a note when the
+    /// Markdown API fails.
+    async fn render_readme(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_info: &github::types::RepoInfo,
+        raw: String,
+        notes: &mut Vec<String>,
+    ) -> String {
+        match self.github.render_markdown(owner, repo, &raw).await {
+            Ok(html) => {
+                let web = repo_info.html_url.trim_end_matches('/');
+                let ref_ = &repo_info.default_branch;
+                let html = resolve_readme_urls(
+                    &html,
+                    &format!("{web}/blob/{ref_}/"),
+                    &format!("{web}/raw/{ref_}/"),
+                );
+                html2md::rewrite_html(&html, false)
+            }
+            Err(e) => {
+                warn!(%e, "failed to render README");
+                notes.push(format!("README shown unrendered ({e})"));
+                raw
+            }
+        }
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::search::Lang;
-    use wiremock::matchers::{method, path_regex};
-    use wiremock::{Mock, MockServer, ResponseTemplate};
+    /// `repo_overview` data from one REST call per section, run concurrently.
+    /// Failed optional sections become `notes` instead of errors.
+    async fn rest_overview(
+        &self,
+        owner: &str,
+        repo: &str,
+        sections: github::OverviewSections,
+        notes: &mut Vec<String>,
+    ) -> Result<github::Overview, ScoutError> {
+        let (repo_info, languages, readme, issues, pulls, releases) = tokio::join!(
+            self.github.get_repo(owner, repo),
+            fetch_if(sections.meta, self.github.get_languages(owner, repo)),
+            fetch_if(sections.readme, self.github.get_readme(owner, repo)),
+            fetch_if(
+                sections.issues,
+                self.github.get_issues(owner, repo, OVERVIEW_ITEMS)
+            ),
+            fetch_if(
+                sections.pulls,
+                self.github.get_pulls(owner, repo, OVERVIEW_ITEMS)
+            ),
+            fetch_if(
+                sections.releases,
+                self.github.get_releases(owner, repo, OVERVIEW_RELEASES)
+            ),
+        );
 
-    fn scout_with_gemini(gemini_uri: &str) -> Scout {
-        let http = Client::builder()
-            .connect_timeout(CONNECT_TIMEOUT)
-            .timeout(HTTP_TIMEOUT)
-            .redirect(reqwest::redirect::Policy::limited(MAX_REDIRECTS))
-            .build()
-            .unwrap();
-        Scout {
-            http: http.clone(),
-            gemini: Some(GeminiClient::with_base_url(http.clone(), gemini_uri)),
-            github: GitHubClient::with_base_url(http, "http://localhost:0"),
+        let mut repo_info = repo_info?;
+        if let Some(languages) = languages.and_then(|r| unwrap_or_note(r, "languages", notes)) {
+            repo_info.languages = languages;
         }
+
+        Ok(github::Overview {
+            repo: repo_info,
+            readme: readme.and_then(|r| readme_text(r, notes)),
+            issues: issues.and_then(|r| unwrap_or_note(r, "issues", notes)),
+            pulls: pulls.and_then(|r| unwrap_or_note(r, "pull requests", notes)),
+            releases: releases.and_then(|r| unwrap_or_note(r, "releases", notes)),
+        })
     }
 
-    #[tokio::test]
-    async fn search_success_returns_content() {
-        let server = MockServer::start().await;
-        Mock::given(method("POST"))
-            .and(path_regex(r":generateContent$"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-                "candidates": [{
-                    "content": {
-                        "parts": [{"text": "Rust is a systems programming language."}],
-                        "role": "model"
-                    },
-                    "groundingMetadata": {
-                        "groundingChunks": [{
-                            "web": {
-                                "uri": "https://rust-lang.org",
-                                "title": "Rust"
-                            }
-                        }]
-                    }
-                }]
-            })))
-            .mount(&server)
-            .await;
+    async fn repo_ci(&self, params: RepoCiParams) -> Result<String, ScoutError> {
+        let (owner, repo) = parse_repo_param(&params.repository)?;
 
-        let s = scout_with_gemini(&server.uri());
-        let params = SearchParams {
-            query: "What is Rust?".into(),
-            lang: Lang::Auto,
+        info!(repository = %params.repository, "repo_ci");
+
+        let branch = match params.ref_ {
+            Some(r) => {
+                github::validate_ref(&r)?;
+                r
+            }
+            None => self.github.get_repo(owner, repo).await?.default_branch,
         };
 
-        let result = s.search(params).await.unwrap();
-        assert!(!result.is_empty());
-        assert!(
-            result.contains("Rust is a systems programming language"),
-            "should contain answer text"
-        );
-        assert!(
-            !result.contains("**Query:**"),
-            "should not contain Query header (redundant for LLMs)"
-        );
+        // A 404 means a missing repo, or no access to it; only an empty run
+        // list means there is nothing to show.
+        let runs = self
+            .github
+            .get_workflow_runs(owner, repo, &branch, params.per_page)
+            .await?
+            .workflow_runs;
+
+        info!(runs = runs.len(), "repo_ci complete");
+        Ok(github::format::format_workflow_runs(
+            owner,
+            repo,
+            &branch,
+            &runs,
+            self.date_style.formatter(),
+        ))
     }
 
-    #[tokio::test]
-    async fn research_success_returns_report() {
-        let server = MockServer::start().await;
-        Mock::given(method("POST"))
-            .and(path_regex(r":generateContent$"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-                "candidates": [{
-                    "content": {
-                        "parts": [{"text": "Rust is a systems programming language focused on safety."}],
-                        "role": "model"
-                    },
-                    "groundingMetadata": {
-                        "groundingChunks": [{
-                            "web": {
-                                "uri": "https://rust-lang.org",
-                                "title": "Rust Language"
-                            }
+    async fn repo_commits(&self, params: RepoCommitsParams) -> Result<String, ScoutError> {
+        let (owner, repo) = parse_repo_param(&params.repository)?;
+
+        info!(repository = %params.repository, "repo_commits");
+
+        if let Some(ref r) = params.ref_ {
+            github::validate_ref(r)?;
+        }
+        if let Some(ref p) = params.path {
+            github::validate_path(p)?;
+        }
+
+        // An empty repository answers 409 Conflict on the commits endpoint.
+        let commits = match self
+            .github
+            .get_commits(
+                owner,
+                repo,
+                params.ref_.as_deref(),
+                params.path.as_deref(),
+                params.per_page,
+            )
+            .await
+        {
+            Ok(c) => c,
+            Err(github::GitHubError::Api { code: 409, .. }) => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        info!(commits = commits.len(), "repo_commits complete");
+        let scope = github::format::CommitScope {
+            ref_: params.ref_.as_deref(),
+            path: params.path.as_deref(),
+        };
+        Ok(github::format::format_commits(
+            owner,
+            repo,
+            &scope,
+            &commits,
+            self.date_style.formatter(),
+        ))
+    }
+
+    /// `repo_commits` with the path filter required.
+    async fn repo_file_history(&self, params: RepoFileHistoryParams) -> Result<String, ScoutError> {
+        info!(repository = %params.repository, path = %params.path, "repo_file_history");
+        self.repo_commits(RepoCommitsParams {
+            repository: params.repository,
+            ref_: params.ref_,
+            path: Some(params.path),
+            per_page: params.per_page,
+        })
+        .await
+    }
+
+    async fn repo_branches(&self, params: RepoBranchesParams) -> Result<String, ScoutError> {
+        let (owner, repo) = parse_repo_param(&params.repository)?;
+
+        info!(repository = %params.repository, "repo_branches");
+
+        let branches = self
+            .github
+            .get_branches(owner, repo, params.per_page)
+            .await?;
+
+        info!(branches = branches.len(), "repo_branches complete");
+        Ok(github::format::format_branches(
+            owner,
+            repo,
+            &branches,
+            params.per_page,
+        ))
+    }
+
+    async fn repo_tags(&self, params: RepoTagsParams) -> Result<String, ScoutError> {
+        let (owner, repo) = parse_repo_param(&params.repository)?;
+
+        info!(repository = %params.repository, "repo_tags");
+
+        let tags = self.github.get_tags(owner, repo, params.per_page).await?;
+
+        info!(tags = tags.len(), "repo_tags complete");
+        Ok(github::format::format_tags(
+            owner,
+            repo,
+            &tags,
+            params.per_page,
+        ))
+    }
+
+    async fn repo_search_code(&self, params: RepoSearchCodeParams) -> Result<String, ScoutError> {
+        let (owner, repo) = parse_repo_param(&params.repository)?;
+
+        info!(repository = %params.repository, query = %params.query, "repo_search_code");
+
+        let response = self
+            .github
+            .search_code(owner, repo, &params.query, params.per_page)
+            .await?;
+
+        info!(
+            matches = response.total_count,
+            shown = response.items.len(),
+            "repo_search_code complete"
+        );
+        Ok(github::format::format_code_search(
+            owner,
+            repo,
+            &params.query,
+            &response,
+        ))
+    }
+
+    async fn repo_search_issues(
+        &self,
+        params: RepoSearchIssuesParams,
+    ) -> Result<String, ScoutError> {
+        let (owner, repo) = parse_repo_param(&params.repository)?;
+
+        info!(repository = %params.repository, query = %params.query, "repo_search_issues");
+
+        let response = self
+            .github
+            .search_issues(owner, repo, &params.query, params.per_page)
+            .await?;
+
+        info!(
+            matches = response.total_count,
+            shown = response.items.len(),
+            "repo_search_issues complete"
+        );
+        Ok(github::format::format_issue_search(
+            owner,
+            repo,
+            &params.query,
+            &response,
+        ))
+    }
+
+    async fn repo_diff(&self, params: RepoDiffParams) -> Result<String, ScoutError> {
+        let (owner, repo) = parse_repo_param(&params.repository)?;
+        github::validate_ref(&params.base)?;
+        github::validate_ref(&params.head)?;
+
+        info!(repository = %params.repository, base = %params.base, head = %params.head, "repo_diff");
+
+        let cmp = self
+            .github
+            .compare_refs(owner, repo, &params.base, &params.head)
+            .await?;
+
+        info!(
+            files = cmp.files.len(),
+            ahead = cmp.ahead_by,
+            "repo_diff complete"
+        );
+        Ok(github::format::format_compare(
+            owner,
+            repo,
+            &params.base,
+            &params.head,
+            &cmp,
+            MAX_FETCH_OUTPUT_BYTES,
+        ))
+    }
+
+    async fn repo_pr_files(&self, params: RepoPrFilesParams) -> Result<String, ScoutError> {
+        let (owner, repo) = parse_repo_param(&params.repository)?;
+        let number = params.number;
+
+        info!(repository = %params.repository, number, "repo_pr_files");
+
+        let (pull, files) = tokio::join!(
+            self.github.get_pull(owner, repo, number),
+            self.github
+                .get_pull_files(owner, repo, number, params.per_page),
+        );
+        let pull = pull.map_err(|e| match e {
+            github::GitHubError::NotFound(_) => {
+                github::GitHubError::NotFound(format!("pull request #{number} in {owner}/{repo}"))
+            }
+            e => e,
+        })?;
+        let files = files?;
+
+        let mut output =
+            github::format::format_pull_files(owner, repo, &pull, &files, params.patches);
+        if (files.len() as u64) < pull.changed_files {
+            output.push_str(&format!(
+                "\n> **Note:** Showing the first {} of {} changed files; raise --per-page (up to 100) to see more.\n",
+                files.len(),
+                pull.changed_files
+            ));
+        }
+
+        info!(files = files.len(), "repo_pr_files complete");
+        Ok(truncate_with_note(&output, MAX_FETCH_OUTPUT_BYTES).into_owned())
+    }
+
+    /// An issue and its first `max_comments` comments, fetched together. A
+    /// missing issue is reported by number and repository.
+    async fn issue_thread(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        max_comments: u8,
+    ) -> Result<(github::types::IssueDetail, Vec<github::types::IssueComment>), ScoutError> {
+        let (issue, comments) = tokio::join!(
+            self.github.get_issue(owner, repo, number),
+            self.github
+                .get_issue_comments(owner, repo, number, max_comments),
+        );
+        let issue = issue.map_err(|e| match e {
+            github::GitHubError::NotFound(_) => {
+                github::GitHubError::NotFound(format!("issue #{number} in {owner}/{repo}"))
+            }
+            e => e,
+        })?;
+        Ok((issue, comments?))
+    }
+
+    async fn repo_issue(&self, params: RepoIssueParams) -> Result<String, ScoutError> {
+        let (owner, repo) = parse_repo_param(&params.repository)?;
+        let number = params.number;
+
+        info!(repository = %params.repository, number, "repo_issue");
+
+        let (issue, comments) = self
+            .issue_thread(owner, repo, number, params.max_comments)
+            .await?;
+
+        let dates = self.date_style.formatter();
+        let mut output = github::format::format_issue_header(owner, repo, &issue, dates);
+        output.push_str(&github::format::format_issue_thread(
+            &issue, &comments, dates,
+        ));
+        if (comments.len() as u64) < issue.comments {
+            output.push_str(&format!(
+                "\n> **Note:** Showing the first {} of {} comments; raise --max-comments (up to 100) to see more.\n",
+                comments.len(),
+                issue.comments
+            ));
+        }
+
+        info!(comments = comments.len(), "repo_issue complete");
+        Ok(truncate_with_note(&output, MAX_FETCH_OUTPUT_BYTES).into_owned())
+    }
+
+    async fn repo_issue_digest(&self, params: RepoIssueDigestParams) -> Result<String, ScoutError> {
+        let (owner, repo) = parse_repo_param(&params.repository)?;
+
+        info!(repository = %params.repository, number = params.number, "repo_issue_digest");
+
+        let (issue, comments) = self
+            .issue_thread(owner, repo, params.number, MAX_DIGEST_COMMENTS)
+            .await?;
+
+        let mut notes = Vec::new();
+        if (comments.len() as u64) < issue.comments {
+            notes.push(format!(
+                "Only the first {} of {} comments were read",
+                comments.len(),
+                issue.comments
+            ));
+        }
+
+        let dates = self.date_style.formatter();
+        let thread = github::format::format_issue_thread(&issue, &comments, dates);
+        let digest = match &self.gemini {
+            Some(gemini) => {
+                let budget = RequestBudget::from_env();
+                match crate::gemini::digest::digest_thread(gemini, &issue.title, &thread, &budget)
+                    .await
+                {
+                    Ok(digest) => Some(digest),
+                    Err(e) => {
+                        warn!(%e, "issue digest failed");
+                        notes.push(format!(
+                            "Gemini digest failed ({e}); showing the raw thread"
+                        ));
+                        None
+                    }
+                }
+            }
+            None => {
+                notes.push("GEMINI_API_KEY is not set; showing the raw thread".into());
+                None
+            }
+        };
+
+        let mut output = github::format::format_issue_header(owner, repo, &issue, dates);
+        match &digest {
+            Some(digest) => {
+                output.push_str(digest);
+                output.push('\n');
+            }
+            None => output.push_str(&thread),
+        }
+
+        if !notes.is_empty() {
+            output.push_str("\n> **Note:** ");
+            output.push_str(&notes.join(". "));
+            output.push_str(".\n");
+        }
+
+        info!(
+            comments = comments.len(),
+            digested = digest.is_some(),
+            "repo_issue_digest complete"
+        );
+        Ok(output)
+    }
+}
+
+/// HTTP client with scout's timeouts and redirect limit. With `public_only`,
+/// hostnames are resolved through [`PublicOnlyResolver`], so connections to
+/// private addresses are refused even after a DNS rebind.
+fn build_http(public_only: bool) -> Result<Client, ScoutError> {
+    let mut builder = Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .timeout(HTTP_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::limited(MAX_REDIRECTS));
+    if public_only {
+        builder = builder.dns_resolver(PublicOnlyResolver);
+    }
+    builder
+        .build()
+        .map_err(|e| ScoutError::internal(format!("HTTP client init failed: {e}")))
+}
+
+/// Whether reqwest will pick up a proxy from the environment. The proxy then
+/// does the resolving, so the public-only resolver would never run.
+fn proxy_configured() -> bool {
+    ["HTTP_PROXY", "HTTPS_PROXY", "ALL_PROXY"]
+        .iter()
+        .any(|name| {
+            [name.to_string(), name.to_ascii_lowercase()]
+                .iter()
+                .any(|var| std::env::var_os(var).is_some_and(|v| !v.is_empty()))
+        })
+}
+
+/// Await `fut` only when its overview section was requested.
+async fn fetch_if<T>(wanted: bool, fut: impl Future<Output = T>) -> Option<T> {
+    if wanted { Some(fut.await) } else { None }
+}
+
+/// Decoded README text. A missing README is silently `None`; other failures
+/// also add a note.
+fn readme_text(
+    readme: Result<github::types::ContentsResponse, github::GitHubError>,
+    notes: &mut Vec<String>,
+) -> Option<String> {
+    match readme {
+        Ok(r) => r.content.and_then(|c| match github::decode_content(&c) {
+            Ok(content) => Some(content),
+            Err(e) => {
+                warn!(%e, "failed to decode README");
+                notes.push(format!("README could not be decoded ({e})"));
+                None
+            }
+        }),
+        Err(e) => {
+            if !matches!(e, github::GitHubError::NotFound(_)) {
+                warn!(%e, "failed to fetch README");
+                notes.push(format!("Could not fetch README ({e})"));
+            }
+            None
+        }
+    }
+}
+
+/// `html` with relative link targets resolved against `blob_base` and
+/// relative image sources against `raw_base`, as GitHub does on the repo
+/// page. In-page `#` links are left alone.
+fn resolve_readme_urls(html: &str, blob_base: &str, raw_base: &str) -> String {
+    let (Ok(blob_base), Ok(raw_base)) = (url::Url::parse(blob_base), url::Url::parse(raw_base))
+    else {
+        return html.to_string();
+    };
+    let doc = dom_query::Document::from(html);
+    for (selector, attr, base) in [
+        ("a[href]", "href", &blob_base),
+        ("img[src]", "src", &raw_base),
+    ] {
+        for node in doc.select(selector).nodes() {
+            let Some(value) = node.attr(attr) else {
+                continue;
+            };
+            let value = value.trim();
+            if value.is_empty() || value.starts_with('#') {
+                continue;
+            }
+            if let Ok(resolved) = base.join(value) {
+                node.set_attr(attr, resolved.as_str());
+            }
+        }
+    }
+    doc.select("body").inner_html().to_string()
+}
+
+/// Fetch each URL with bounded concurrency, in input order. Every URL goes
+/// through `fetch_page`'s own validation and SSRF checks, and gets its own
+/// timeout so one slow page cannot hold up the rest. All pages draw on one
+/// request `budget`.
+async fn fetch_urls(
+    http: &Client,
+    urls: Vec<String>,
+    opts: crate::fetch::FetchOptions,
+    timeout: Duration,
+    budget: &RequestBudget,
+    resolver: &impl crate::fetch::DnsResolver,
+) -> Vec<(String, Result<crate::fetch::converter::FetchResult, String>)> {
+    use futures::stream::{self, StreamExt};
+
+    stream::iter(urls)
+        .map(|url| {
+            let opts = opts.clone();
+            async move {
+                let result = tokio::time::timeout(
+                    timeout,
+                    crate::fetch::fetch_page(http, &url, opts, budget, resolver),
+                )
+                .await
+                .unwrap_or_else(|_| {
+                    Err(crate::fetch::FetchError::Timeout(format!(
+                        "page fetch timed out after {}s",
+                        timeout.as_secs()
+                    )))
+                });
+                if let Err(e) = &result {
+                    warn!(url = %url, error = %e, "fetch_many: page failed");
+                }
+                (url, result.map_err(|e| e.to_string()))
+            }
+        })
+        .buffered(FETCH_MANY_CONCURRENCY)
+        .collect()
+        .await
+}
+
+/// One section per fetched page, then the failures. Pages share the output
+/// budget; the failure list is never truncated away.
+fn format_fetch_many(
+    outcomes: &[(String, Result<crate::fetch::converter::FetchResult, String>)],
+) -> String {
+    let mut failed = Vec::new();
+    let mut pages = String::new();
+    let mut fetched = 0;
+    for (url, outcome) in outcomes {
+        match outcome {
+            Ok(result) => {
+                fetched += 1;
+                pages.push_str(&format!(
+                    "## {}\n\n{}\n\n",
+                    escape_md_link(&result.url),
+                    fetch_markdown(result).trim_end()
+                ));
+            }
+            Err(reason) => failed.push(engine::FailedUrl {
+                url: url.clone(),
+                reason: reason.clone(),
+            }),
+        }
+    }
+
+    let mut failures = String::new();
+    engine::format_failed_urls(&failed, &mut failures);
+    let header = format!("# Fetched {fetched} of {} URLs\n\n", outcomes.len());
+    let budget = MAX_FETCH_OUTPUT_BYTES.saturating_sub(header.len() + failures.len());
+    let mut out = header;
+    out.push_str(&truncate_with_note(&pages, budget));
+    if !out.ends_with("\n\n") {
+        out.push_str("\n\n");
+    }
+    out.push_str(&failures);
+    out.trim_end().to_string() + "\n"
+}
+
+/// Replace the page body with its translation, keeping the frontmatter and
+/// noting the source language. A body longer than `max_body_bytes` is cut
+/// before translating, with a truncation note after the translation.
+/// Frontmatter-only results pass through.
+async fn translate_fetch_result(
+    gemini: &GeminiClient,
+    mut result: crate::fetch::converter::FetchResult,
+    target: &str,
+    max_body_bytes: Option<usize>,
+    budget: &RequestBudget,
+) -> Result<crate::fetch::converter::FetchResult, GeminiError> {
+    let (frontmatter, body) = split_frontmatter(&result.markdown);
+    let body = body.trim();
+    if body.is_empty() {
+        return Ok(result);
+    }
+    let (body, cut_note) = match max_body_bytes {
+        Some(max) if body.len() > max => {
+            let end = body.floor_char_boundary(max);
+            let note = format!("\n\n(truncated: translated {end} / {} bytes)", body.len());
+            (&body[..end], note)
+        }
+        _ => (body, String::new()),
+    };
+    let translation =
+        crate::gemini::translate::translate_markdown(gemini, body, target, budget).await?;
+    let note = match &translation.source_language {
+        Some(source) => {
+            format!("> **Note:** Machine-translated from {source} into {target} by Gemini.")
+        }
+        None => format!("> **Note:** Machine-translated into {target} by Gemini."),
+    };
+    result.markdown = format!(
+        "{frontmatter}\n{note}\n\n{}{cut_note}\n",
+        translation.markdown
+    );
+    Ok(result)
+}
+
+/// Split leading `---` frontmatter (delimiters included) from the body.
+fn split_frontmatter(markdown: &str) -> (&str, &str) {
+    if let Some(rest) = markdown.strip_prefix("---\n")
+        && let Some(end) = rest
+            .find("---\n")
+            .filter(|&i| i == 0 || rest[..i].ends_with('\n'))
+    {
+        return markdown.split_at(4 + end + 4);
+    }
+    ("", markdown)
+}
+
+/// `answer` cut to at most `max_chars` characters, after the last sentence
+/// end in that window or else the last word break, and marked "(truncated)".
+fn cap_answer(answer: String, max_chars: usize) -> String {
+    let Some((limit, _)) = answer.char_indices().nth(max_chars) else {
+        return answer;
+    };
+    let window = &answer[..limit];
+    let cut = window
+        .rmatch_indices(['.', '!', '?', '。', '！', '？'])
+        .map(|(i, m)| i + m.len())
+        .find(|&i| {
+            !answer[..i].ends_with(['.', '!', '?']) || answer[i..].starts_with(char::is_whitespace)
+        })
+        .or_else(|| window.rfind(char::is_whitespace))
+        .unwrap_or(limit);
+    format!("{} (truncated)", answer[..cut].trim_end())
+}
+
+fn fetch_markdown(result: &crate::fetch::converter::FetchResult) -> String {
+    let shifted = shift_headings(&result.markdown, 2);
+    let paywall = if result.paywalled {
+        crate::fetch::converter::PAYWALL_NOTE
+    } else {
+        ""
+    };
+    if result.used_raw_fallback {
+        format!(
+            "{paywall}{}{shifted}",
+            crate::fetch::converter::RAW_FALLBACK_NOTE
+        )
+    } else {
+        format!("{paywall}{shifted}")
+    }
+}
+
+/// Whether a missing `--ref` should be retried as the best matching tag.
+fn wants_tag_fallback(params: &RepoReadParams) -> bool {
+    params.resolve_tag
+        && params
+            .ref_
+            .as_deref()
+            .is_some_and(github::looks_like_version)
+}
+
+/// One file of `repo_read` output: the "path (N lines)" header, the selected
+/// lines (`--between` or `--lines`, else all), and any notes.
+fn format_read_file(
+    path: &str,
+    raw: &str,
+    params: &RepoReadParams,
+    line_range: Option<(usize, Option<usize>)>,
+    mut notes: Vec<String>,
+) -> String {
+    let total = raw.lines().count();
+    if let Some(size) = github::lfs_pointer_size(raw) {
+        notes.push(format!(
+            "This is a Git LFS pointer, not the file itself: the real file ({}) is stored in LFS and not served by the GitHub contents API.",
+            github::format::format_size(size)
+        ));
+    }
+    let (from, to) = if let Some([start, end]) = params.between.as_deref() {
+        match github::find_marker_range(raw, start, end) {
+            Some((from, Some(to))) => (from, Some(to)),
+            Some((from, None)) => {
+                notes.push(format!(
+                    "End marker {end:?} not found after line {from}; showing to end of file."
+                ));
+                (from, None)
+            }
+            None => {
+                notes.push(format!(
+                    "Start marker {start:?} not found; showing the whole file."
+                ));
+                (1, None)
+            }
+        }
+    } else {
+        line_range.unwrap_or((1, None))
+    };
+    let content = if params.fenced {
+        github::fence_line_range(raw, path, from, to)
+    } else {
+        github::apply_line_range(raw, from, to)
+    };
+
+    let mut output = format!("{path} ({total} lines)\n\n{content}");
+    if !notes.is_empty() {
+        output.push('\n');
+    }
+    for note in notes {
+        output.push_str(&format!("\n> **Note:** {note}\n"));
+    }
+    output
+}
+
+fn format_fetch_output(result: &crate::fetch::converter::FetchResult) -> String {
+    let output = fetch_markdown(result);
+    truncate_with_note(&output, MAX_FETCH_OUTPUT_BYTES).into_owned()
+}
+
+/// Chunk `chunk` (1-based) of the page, under a "Chunk N of M" header.
+fn format_fetch_chunk(
+    result: &crate::fetch::converter::FetchResult,
+    chunk: usize,
+    chunk_size: usize,
+) -> Result<String, ScoutError> {
+    let output = fetch_markdown(result);
+    let chunks = split_chunks(&output, chunk_size);
+    let total = chunks.len().max(1);
+    let body = match chunk.checked_sub(1).map(|i| chunks.get(i)) {
+        Some(Some(body)) => body.trim_end(),
+        _ if chunk == 1 => "",
+        _ => {
+            return Err(ScoutError::user_error(format!(
+                "chunk {chunk} is out of range: the page has {total} chunk(s) of up to {chunk_size} characters"
+            )));
+        }
+    };
+
+    let mut out = format!("> Chunk {chunk} of {total}");
+    if chunk < total {
+        out.push_str(&format!(" (next: --chunk {})", chunk + 1));
+    }
+    out.push_str("\n\n");
+    out.push_str(body);
+    out.push('\n');
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::PublicDns;
+    use crate::search::Lang;
+    use wiremock::matchers::{method, path_regex};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_http() -> Client {
+        Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(HTTP_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::limited(MAX_REDIRECTS))
+            .build()
+            .unwrap()
+    }
+
+    fn scout_with_gemini(gemini_uri: &str) -> Scout {
+        let http = test_http();
+        Scout {
+            http: http.clone(),
+            api_http: http.clone(),
+            gemini: Some(GeminiClient::with_base_url(http.clone(), gemini_uri)),
+            github: GitHubClient::with_base_url(http, "http://localhost:0"),
+            enabled: EnabledTools::default(),
+            fetch_defaults: FetchDefaults::default(),
+            empty_answer: EmptyAnswerMode::default(),
+            date_style: DateStyle::default(),
+        }
+    }
+
+    fn scout_with_github(github_uri: &str) -> Scout {
+        let http = test_http();
+        Scout {
+            http: http.clone(),
+            api_http: http.clone(),
+            gemini: None,
+            github: GitHubClient::with_base_url(http, github_uri),
+            enabled: EnabledTools::default(),
+            fetch_defaults: FetchDefaults::default(),
+            empty_answer: EmptyAnswerMode::default(),
+            date_style: DateStyle::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn api_client_reaches_private_hosts_that_page_fetches_cannot() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        let url = format!("http://localhost:{}/api/v3", server.address().port());
+
+        let api = build_http(false).unwrap();
+        assert!(api.get(&url).send().await.is_ok());
+        let pages = build_http(true).unwrap();
+        assert!(pages.get(&url).send().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn run_rejects_disabled_tool() {
+        let mut s = scout_with_github("http://localhost:0");
+        s.enabled = EnabledTools::only(&["repo-tree"]);
+        let cmd = Command::Fetch(FetchParams {
+            url: "https://example.com".into(),
+            js: false,
+            raw: None,
+            charset: None,
+            metadata_only: None,
+            follow_pagination: None,
+            code_only: false,
+            links: false,
+            flatten_links: false,
+            image: false,
+            compare: false,
+            selector: None,
+            max_bytes: None,
+            respect_robots: false,
+            chunk: None,
+            chunk_size: None,
+            translate_to: None,
+            if_modified_since: None,
+        });
+
+        let err = s.run(cmd).await.unwrap_err();
+        assert_eq!(err.exit_code(), 1);
+        assert!(
+            err.to_string().contains("tool 'fetch' is disabled"),
+            "got: {err}"
+        );
+    }
+
+    /// Parse `args` the way the `scout` binary does and run the command, so a
+    /// test covers the CLI boundary (flag names, value parsers, conflicts,
+    /// the enabled-tools check) as well as the tool itself.
+    async fn run_cli(scout: &Scout, args: &[&str]) -> Result<String, ScoutError> {
+        use clap::{FromArgMatches, Subcommand};
+
+        let cli = Command::augment_subcommands(clap::Command::new("scout"));
+        let matches = scout
+            .enabled
+            .apply_to_cli(cli)
+            .try_get_matches_from(args)
+            .map_err(|e| ScoutError::user_error(e.to_string()))?;
+        let cmd = Command::from_arg_matches(&matches)
+            .map_err(|e| ScoutError::user_error(e.to_string()))?;
+        scout.run(cmd).await
+    }
+
+    #[tokio::test]
+    async fn cli_repo_tree_round_trip() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/git/trees/main$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "tree": [
+                    {"path": "src", "type": "tree"},
+                    {"path": "src/main.rs", "type": "blob", "size": 2048},
+                    {"path": "src/github/mod.rs", "type": "blob", "size": 512},
+                    {"path": "README.md", "type": "blob", "size": 100}
+                ],
+                "truncated": false
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let output = run_cli(
+            &s,
+            &[
+                "scout",
+                "repo-tree",
+                "owner/repo",
+                "--ref=main",
+                "--pattern",
+                "src/**/*.rs",
+                "--verbosity",
+                "compact",
+            ],
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            output,
+            "owner/repo (ref: main)\n\
+             pattern: src/**/*.rs → 2 of 3 files\n\
+             files: 2\n\n\
+             src/main.rs\n\
+             src/github/mod.rs\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn cli_fetch_round_trip_blocks_loopback_host() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<p>secret</p>"))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github("http://localhost:0");
+        let url = format!("{}/page", server.uri());
+        let err = run_cli(
+            &s,
+            &["scout", "fetch", &url, "--raw", "--max-bytes", "4096"],
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.exit_code(), 1);
+        assert!(err.to_string().starts_with("blocked:"), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn cli_rejects_invalid_arguments_before_running() {
+        let s = scout_with_github("http://localhost:0");
+        for args in [
+            &["scout", "repo-tree", "owner/repo", "--verbosity", "loud"][..],
+            &[
+                "scout",
+                "fetch",
+                "https://example.com",
+                "--compare",
+                "--raw",
+            ],
+            &["scout", "repo-overview", "owner/repo", "--sections", "wiki"],
+        ] {
+            let err = run_cli(&s, args).await.unwrap_err();
+            assert_eq!(err.exit_code(), 1, "{args:?}");
+            assert!(err.to_string().starts_with("error:"), "got: {err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn repo_tree_inlines_small_files_only() {
+        use base64::Engine as _;
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/git/trees/main$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "tree": [
+                    {"path": "config/small.toml", "type": "blob", "size": 10},
+                    {"path": "config/large.json", "type": "blob", "size": 90000}
+                ],
+                "truncated": false
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/contents/config/small\.toml$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sha": "abc",
+                "content": base64::engine::general_purpose::STANDARD.encode("key = 1\n")
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/contents/config/large\.json$"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let params = RepoTreeParams {
+            repository: "owner/repo".into(),
+            ref_: Some("main".into()),
+            path: None,
+            pattern: None,
+            inline_under_bytes: Some(1024),
+            tree_view: false,
+            verbosity: None,
+        };
+
+        let output = s.repo_tree(params).await.unwrap();
+        assert!(
+            output.contains("config/small.toml (10 B)\n```\nkey = 1\n```"),
+            "small file should be inlined, got:\n{output}"
+        );
+        assert!(output.contains("config/large.json (87.9 KB)\n"));
+        assert!(!output.contains("not inlined"));
+    }
+
+    #[tokio::test]
+    async fn repo_ls_lists_directory_and_rejects_files() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/contents/src$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"name": "main.rs", "path": "src/main.rs", "sha": "a", "type": "file", "size": 2048},
+                {"name": "tools", "path": "src/tools", "sha": "b", "type": "dir", "size": 0},
+                {"name": "vendor", "path": "src/vendor", "sha": "c", "type": "submodule", "size": 0}
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/contents/Cargo\.toml$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "name": "Cargo.toml", "path": "Cargo.toml", "sha": "d", "type": "file",
+                "size": 10, "content": ""
+            })))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let params = |path: &str| RepoLsParams {
+            repository: "owner/repo".into(),
+            path: Some(path.into()),
+            ref_: None,
+            verbosity: None,
+        };
+
+        let output = s.repo_ls(params("src/")).await.unwrap();
+        assert_eq!(
+            output,
+            "owner/repo/src\ndirs: 1, files: 2\n\ntools/\nmain.rs (2.0 KB)\nvendor (submodule)\n"
+        );
+
+        let err = s.repo_ls(params("Cargo.toml")).await.unwrap_err();
+        assert_eq!(err.exit_code(), 1);
+        assert!(
+            err.to_string().contains("'Cargo.toml' is a file"),
+            "got: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn repo_tree_lists_subtree_when_root_tree_is_truncated() {
+        use wiremock::matchers::query_param;
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/git/trees/main$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "tree": [{"path": "README.md", "type": "blob", "size": 10}],
+                "truncated": true
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/contents/packages$"))
+            .and(query_param("ref", "main"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"name": "app", "path": "packages/app", "sha": "1111111111111111111111111111111111111111", "type": "dir"},
+                {"name": "core", "path": "packages/core", "sha": "2222222222222222222222222222222222222222", "type": "dir"}
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(
+                r"/git/trees/2222222222222222222222222222222222222222$",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "tree": [
+                    {"path": "src", "type": "tree"},
+                    {"path": "src/lib.rs", "type": "blob", "size": 120}
+                ],
+                "truncated": false
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let params = RepoTreeParams {
+            repository: "owner/repo".into(),
+            ref_: Some("main".into()),
+            path: Some("packages/core/".into()),
+            pattern: None,
+            inline_under_bytes: None,
+            tree_view: false,
+            verbosity: None,
+        };
+
+        let output = s.repo_tree(params).await.unwrap();
+        assert!(
+            output.contains("packages/core/src/lib.rs"),
+            "got:\n{output}"
+        );
+        assert!(
+            !output.contains("tree truncated by GitHub"),
+            "got:\n{output}"
+        );
+        assert!(
+            output.contains("`packages/core/` was listed from its own subtree (2 extra requests)"),
+            "got:\n{output}"
+        );
+        assert!(
+            output.contains("path prefix: packages/core/ → 1 of 1 files"),
+            "got:\n{output}"
+        );
+    }
+
+    fn workflow_run(name: &str, conclusion: &str) -> serde_json::Value {
+        serde_json::json!({
+            "name": name,
+            "html_url": "https://github.com/owner/repo/actions/runs/1",
+            "head_sha": "0123456789abcdef0123456789abcdef01234567",
+            "event": "push",
+            "status": "completed",
+            "conclusion": conclusion,
+            "created_at": "2026-01-15T10:00:00Z"
+        })
+    }
+
+    #[tokio::test]
+    async fn repo_ci_lists_passing_and_failing_runs() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/owner/repo$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "full_name": "owner/repo",
+                "description": null,
+                "html_url": "https://github.com/owner/repo",
+                "default_branch": "main",
+                "language": null,
+                "stargazers_count": 0,
+                "forks_count": 0,
+                "open_issues_count": 0,
+                "topics": null,
+                "license": null
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/actions/runs$"))
+            .and(wiremock::matchers::query_param("branch", "main"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "total_count": 2,
+                "workflow_runs": [workflow_run("CI", "failure"), workflow_run("Lint", "success")]
+            })))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let params = RepoCiParams {
+            repository: "owner/repo".into(),
+            ref_: None,
+            per_page: 10,
+        };
+
+        let output = s.repo_ci(params).await.unwrap();
+        assert!(output.contains("(branch: main)"), "got:\n{output}");
+        assert!(output.contains("[CI]("));
+        assert!(output.contains("**failure**"));
+        assert!(output.contains("[Lint]("));
+        assert!(output.contains("**success**"));
+        assert!(output.contains("`0123456`"));
+    }
+
+    #[tokio::test]
+    async fn repo_overview_renders_only_requested_sections() {
+        use base64::Engine as _;
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/owner/repo$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "full_name": "owner/repo",
+                "description": "A description",
+                "html_url": "https://github.com/owner/repo",
+                "default_branch": "main",
+                "language": "Rust",
+                "stargazers_count": 42,
+                "forks_count": 0,
+                "open_issues_count": 0,
+                "topics": null,
+                "license": null
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/readme$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sha": "abc",
+                "content": base64::engine::general_purpose::STANDARD.encode("Readme body")
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/(issues|pulls|releases)$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let output = s
+            .repo_overview(RepoOverviewParams {
+                repository: "owner/repo".into(),
+                max_labels: 8,
+                sections: Some(vec!["readme".into()]),
+                render_readme: false,
+                verbosity: None,
+            })
+            .await
+            .unwrap();
+
+        assert!(output.starts_with("# owner/repo"), "got:\n{output}");
+        assert!(
+            output.contains("## README\n\nReadme body"),
+            "got:\n{output}"
+        );
+        assert!(!output.contains("| Stars |"), "got:\n{output}");
+        assert!(!output.contains("A description"), "got:\n{output}");
+        assert!(!output.contains("## Recent"), "got:\n{output}");
+        assert!(!output.contains("Note:"), "got:\n{output}");
+    }
+
+    async fn mount_readme_only_repo(server: &MockServer, readme: &str) {
+        use base64::Engine as _;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/owner/repo$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "full_name": "owner/repo",
+                "description": null,
+                "html_url": "https://github.com/owner/repo",
+                "default_branch": "main",
+                "language": null,
+                "stargazers_count": 0,
+                "forks_count": 0,
+                "open_issues_count": 0,
+                "topics": null,
+                "license": null
+            })))
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/readme$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sha": "abc",
+                "content": base64::engine::general_purpose::STANDARD.encode(readme)
+            })))
+            .mount(server)
+            .await;
+    }
+
+    fn readme_params(render_readme: bool) -> RepoOverviewParams {
+        RepoOverviewParams {
+            repository: "owner/repo".into(),
+            max_labels: 8,
+            sections: Some(vec!["readme".into()]),
+            render_readme,
+            verbosity: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn repo_overview_renders_readme_through_markdown_api() {
+        let server = MockServer::start().await;
+        mount_readme_only_repo(&server, "Install\n=======\n\n- [x] done").await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/markdown$"))
+            .and(wiremock::matchers::body_partial_json(serde_json::json!({
+                "mode": "gfm",
+                "context": "owner/repo"
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("<h1>Install</h1>\n<ul><li>done</li></ul>\n"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let output = s.repo_overview(readme_params(true)).await.unwrap();
+        assert!(output.contains("### Install"), "got:\n{output}");
+        assert!(!output.contains("======="), "got:\n{output}");
+        assert!(!output.contains("Note:"), "got:\n{output}");
+    }
+
+    #[tokio::test]
+    async fn repo_overview_resolves_relative_readme_links_and_images() {
+        let server = MockServer::start().await;
+        mount_readme_only_repo(&server, "[Guide](docs/guide.md) ![Shot](img/shot.png)").await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/markdown$"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r##"<p><a href="docs/guide.md">Guide</a> <img src="img/shot.png" alt="Shot"> <a href="#usage">Usage</a> <a href="https://example.com/">Site</a></p>"##,
+            ))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let output = s.repo_overview(readme_params(true)).await.unwrap();
+        assert!(
+            output.contains("[Guide](https://github.com/owner/repo/blob/main/docs/guide.md)"),
+            "got:\n{output}"
+        );
+        assert!(
+            output.contains("![Shot](https://github.com/owner/repo/raw/main/img/shot.png)"),
+            "got:\n{output}"
+        );
+        assert!(output.contains("[Usage](#usage)"), "got:\n{output}");
+        assert!(
+            output.contains("[Site](https://example.com/)"),
+            "got:\n{output}"
+        );
+    }
+
+    #[tokio::test]
+    async fn repo_overview_keeps_raw_readme_when_rendering_fails() {
+        let server = MockServer::start().await;
+        mount_readme_only_repo(&server, "Raw *readme*").await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/markdown$"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let output = s.repo_overview(readme_params(true)).await.unwrap();
+        assert!(
+            output.contains("## README\n\nRaw *readme*"),
+            "got:\n{output}"
+        );
+        assert!(
+            output.contains("> **Note:** README shown unrendered"),
+            "got:\n{output}"
+        );
+    }
+
+    #[tokio::test]
+    async fn repo_overview_notes_when_languages_cannot_be_fetched() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/owner/repo$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "full_name": "owner/repo",
+                "description": null,
+                "html_url": "https://github.com/owner/repo",
+                "default_branch": "main",
+                "language": "Rust",
+                "stargazers_count": 1,
+                "forks_count": 0,
+                "open_issues_count": 0,
+                "topics": null,
+                "license": null
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/languages$"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let output = s
+            .repo_overview(RepoOverviewParams {
+                repository: "owner/repo".into(),
+                max_labels: 8,
+                sections: Some(vec!["meta".into()]),
+                render_readme: false,
+                verbosity: None,
+            })
+            .await
+            .unwrap();
+
+        assert!(output.contains("| Language | Rust |"), "got:\n{output}");
+        assert!(!output.contains("| Languages |"), "got:\n{output}");
+        assert!(
+            output.contains("> **Note:** Could not fetch languages"),
+            "got:\n{output}"
+        );
+    }
+
+    #[tokio::test]
+    async fn repo_overview_uses_one_graphql_request_with_a_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/graphql$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "repository": {
+                    "nameWithOwner": "owner/repo",
+                    "description": "Via GraphQL",
+                    "url": "https://github.com/owner/repo",
+                    "defaultBranchRef": { "name": "main" },
+                    "primaryLanguage": { "name": "Rust" },
+                    "stargazerCount": 42,
+                    "forkCount": 3,
+                    "openIssues": { "totalCount": 2 },
+                    "openPulls": { "totalCount": 1 },
+                    "repositoryTopics": { "nodes": [{ "topic": { "name": "cli" } }] },
+                    "licenseInfo": { "spdxId": "MIT", "name": "MIT License" },
+                    "languages": { "edges": [{ "size": 100, "node": { "name": "Rust" } }] },
+                    "readme0": null,
+                    "readme1": { "text": "Readme body" },
+                    "readme2": null,
+                    "issues": { "nodes": [{
+                        "number": 7, "title": "Crash on start", "url": "https://github.com/owner/repo/issues/7",
+                        "updatedAt": "2024-02-01T00:00:00Z", "author": { "login": "alice" },
+                        "comments": { "totalCount": 4 }, "labels": { "nodes": [{ "name": "bug" }] }
+                    }] },
+                    "pullRequests": { "nodes": [{
+                        "number": 8, "title": "Fix crash", "url": "https://github.com/owner/repo/pull/8",
+                        "isDraft": false, "updatedAt": "2024-02-02T00:00:00Z", "author": { "login": "bob" },
+                        "comments": { "totalCount": 1 }
+                    }] },
+                    "releases": { "nodes": [{
+                        "tagName": "v1.0.0", "name": "First", "url": "https://github.com/owner/repo/releases/tag/v1.0.0",
+                        "publishedAt": "2024-01-01T00:00:00Z", "isPrerelease": false,
+                        "releaseAssets": { "nodes": [] }
+                    }] }
+                } }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let mut s = scout_with_github(&server.uri());
+        s.github = s.github.with_token("test-token");
+        let output = s
+            .repo_overview(RepoOverviewParams {
+                repository: "owner/repo".into(),
+                max_labels: 8,
+                sections: None,
+                render_readme: false,
+                verbosity: Some(Verbosity::Detailed),
+            })
+            .await
+            .unwrap();
+
+        assert!(output.starts_with("# owner/repo"), "got:\n{output}");
+        assert!(output.contains("Via GraphQL"), "got:\n{output}");
+        assert!(
+            output.contains("— @alice · 4 comments · updated "),
+            "got:\n{output}"
+        );
+        assert!(
+            output.contains("— @bob · 1 comment · updated "),
+            "got:\n{output}"
+        );
+        assert!(output.contains("| Stars | 42 |"), "got:\n{output}");
+        assert!(output.contains("| Open Issues | 3 |"), "got:\n{output}");
+        assert!(
+            output.contains("## README\n\nReadme body"),
+            "got:\n{output}"
+        );
+        assert!(output.contains("Crash on start"), "got:\n{output}");
+        assert!(output.contains("Fix crash"), "got:\n{output}");
+        assert!(output.contains("v1.0.0"), "got:\n{output}");
+        assert!(!output.contains("Note:"), "got:\n{output}");
+    }
+
+    #[tokio::test]
+    async fn repo_overview_falls_back_to_rest_when_graphql_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/graphql$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": null,
+                "errors": [{ "message": "Resource not accessible by integration" }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/owner/repo$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "full_name": "owner/repo",
+                "description": "Via REST",
+                "html_url": "https://github.com/owner/repo",
+                "default_branch": "main",
+                "language": "Rust",
+                "stargazers_count": 1,
+                "forks_count": 0,
+                "open_issues_count": 0,
+                "topics": null,
+                "license": null
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut s = scout_with_github(&server.uri());
+        s.github = s.github.with_token("test-token");
+        let output = s
+            .repo_overview(RepoOverviewParams {
+                repository: "owner/repo".into(),
+                max_labels: 8,
+                sections: Some(vec!["meta".into()]),
+                render_readme: false,
+                verbosity: None,
+            })
+            .await
+            .unwrap();
+
+        assert!(output.contains("Via REST"), "got:\n{output}");
+    }
+
+    async fn mount_issue_thread(server: &MockServer) {
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/owner/repo/issues/7$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "number": 7,
+                "title": "Config reload drops connections",
+                "html_url": "https://github.com/owner/repo/issues/7",
+                "state": "open",
+                "body": "Reloading the config closes every open connection.",
+                "user": {"login": "alice"},
+                "labels": [{"name": "bug"}],
+                "comments": 2,
+                "created_at": "2026-03-01T09:00:00Z"
+            })))
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/owner/repo/issues/7/comments$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {
+                    "user": {"login": "bob"},
+                    "body": "We could drain connections before swapping.",
+                    "created_at": "2026-03-02T10:00:00Z"
+                },
+                {
+                    "user": {"login": "alice"},
+                    "body": "Agreed, draining it is.",
+                    "created_at": "2026-03-03T11:00:00Z"
+                }
+            ])))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn repo_pr_files_lists_changes_and_merge_status() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/owner/repo/pulls/12$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "number": 12,
+                "title": "Drain connections on reload",
+                "html_url": "https://github.com/owner/repo/pull/12",
+                "state": "open",
+                "merged": false,
+                "mergeable": false,
+                "mergeable_state": "dirty",
+                "changed_files": 3,
+                "additions": 14,
+                "deletions": 5,
+                "base": {"ref": "main", "sha": "5f0c0a1d9e"},
+                "head": {"ref": "drain-on-reload", "sha": "c4e1b7a2f3"}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/owner/repo/pulls/12/files$"))
+            .and(wiremock::matchers::query_param("per_page", "30"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"filename": "src/server.rs", "status": "modified", "additions": 12, "deletions": 3,
+                 "patch": "@@ -1 +1 @@\n-old\n+new"},
+                {"filename": "src/reload.rs", "status": "renamed", "additions": 2, "deletions": 0,
+                 "previous_filename": "src/config.rs"},
+                {"filename": "legacy.rs", "status": "removed", "additions": 0, "deletions": 2}
+            ])))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let params = |number, patches| RepoPrFilesParams {
+            repository: "owner/repo".into(),
+            number,
+            per_page: 30,
+            patches,
+        };
+
+        let output = s.repo_pr_files(params(12, false)).await.unwrap();
+        assert_eq!(
+            output,
+            "# owner/repo#12: Drain connections on reload\n\n\
+             - **Pull request:** https://github.com/owner/repo/pull/12\n\
+             - **State:** open\n\
+             - **Mergeable:** no (dirty)\n\
+             - **Branches:** `drain-on-reload` → `main`\n\
+             - **Changed files:** 3 (+14 −5)\n\n\
+             ## Files\n\n\
+             - `src/server.rs` — modified (+12 −3)\n\
+             - `src/reload.rs` — renamed from `src/config.rs` (+2 −0)\n\
+             - `legacy.rs` — removed (+0 −2)\n\n"
+        );
+
+        let output = s.repo_pr_files(params(12, true)).await.unwrap();
+        assert!(
+            output.contains("## Patches\n\n### `src/server.rs`\n\n```diff\n@@ -1 +1 @@"),
+            "got:\n{output}"
+        );
+
+        let err = s.repo_pr_files(params(99, false)).await.unwrap_err();
+        assert_eq!(err.exit_code(), 1);
+        assert!(
+            err.to_string().contains("pull request #99 in owner/repo"),
+            "got: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn repo_issue_shows_thread_truncated_to_max_comments() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/owner/repo/issues/8$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "number": 8,
+                "title": "Crash on [startup](docs)",
+                "html_url": "https://github.com/owner/repo/issues/8",
+                "state": "closed",
+                "body": null,
+                "user": {"login": "carol"},
+                "labels": [],
+                "comments": 3,
+                "created_at": "2026-04-01T08:00:00Z"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/owner/repo/issues/8/comments$"))
+            .and(wiremock::matchers::query_param("per_page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {
+                    "user": {"login": "dave"},
+                    "body": "Fixed by the config loader rewrite.",
+                    "created_at": "2026-04-02T08:00:00Z"
+                }
+            ])))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let params = |number| RepoIssueParams {
+            repository: "owner/repo".into(),
+            number,
+            max_comments: 1,
+        };
+        let output = s.repo_issue(params(8)).await.unwrap();
+
+        assert!(
+            output.starts_with("# owner/repo#8: Crash on [startup](docs)\n\n"),
+            "got:\n{output}"
+        );
+        assert!(output.contains("- **State:** closed\n"), "got:\n{output}");
+        assert!(
+            output.contains("## Opening post — @carol, 2026-04-01T08:00:00Z\n\n_(empty)_"),
+            "got:\n{output}"
+        );
+        assert!(
+            output.contains(
+                "## Comment 1 — @dave, 2026-04-02T08:00:00Z\n\nFixed by the config loader rewrite."
+            ),
+            "got:\n{output}"
+        );
+        assert!(
+            output.contains("Showing the first 1 of 3 comments"),
+            "got:\n{output}"
+        );
+
+        let err = s.repo_issue(params(9)).await.unwrap_err();
+        assert_eq!(err.exit_code(), 1);
+        assert!(
+            err.to_string().contains("issue #9 in owner/repo"),
+            "got: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn repo_issue_digest_summarizes_thread_with_gemini() {
+        use wiremock::matchers::body_string_contains;
+        let github = MockServer::start().await;
+        mount_issue_thread(&github).await;
+        let gemini = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("We could drain connections"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "candidates": [{"content": {"parts": [{"text":
+                    "## Problem\n- Reload drops connections.\n\n\
+                     ## Proposed solutions\n- Drain first (@bob).\n\n\
+                     ## Decisions\n- Drain before swapping (@alice).\n\n\
+                     ## Open questions\n- None."
+                }], "role": "model"}}]
+            })))
+            .expect(1)
+            .mount(&gemini)
+            .await;
+
+        let mut s = scout_with_github(&github.uri());
+        s.gemini = Some(GeminiClient::with_base_url(test_http(), &gemini.uri()));
+        let output = s
+            .repo_issue_digest(RepoIssueDigestParams {
+                repository: "owner/repo".into(),
+                number: 7,
+            })
+            .await
+            .unwrap();
+
+        assert!(
+            output.starts_with("# owner/repo#7: Config reload drops connections\n"),
+            "got:\n{output}"
+        );
+        assert!(output.contains("- **State:** open"), "got:\n{output}");
+        assert!(output.contains("- **Labels:** bug"), "got:\n{output}");
+        let sections: Vec<usize> = crate::gemini::digest::DIGEST_SECTIONS
+            .iter()
+            .map(|h| output.find(&format!("## {h}\n")).unwrap())
+            .collect();
+        assert!(sections.is_sorted(), "got:\n{output}");
+        assert!(!output.contains("## Comment 1"), "got:\n{output}");
+        assert!(!output.contains("Note:"), "got:\n{output}");
+    }
+
+    #[tokio::test]
+    async fn repo_issue_digest_without_gemini_shows_raw_thread() {
+        let github = MockServer::start().await;
+        mount_issue_thread(&github).await;
+
+        let s = scout_with_github(&github.uri());
+        let output = s
+            .repo_issue_digest(RepoIssueDigestParams {
+                repository: "owner/repo".into(),
+                number: 7,
+            })
+            .await
+            .unwrap();
+
+        assert!(
+            output
+                .contains("## Opening post — @alice, 2026-03-01T09:00:00Z\n\nReloading the config"),
+            "got:\n{output}"
+        );
+        assert!(
+            output
+                .contains("## Comment 2 — @alice, 2026-03-03T11:00:00Z\n\nAgreed, draining it is."),
+            "got:\n{output}"
+        );
+        assert!(
+            output.contains("GEMINI_API_KEY is not set"),
+            "got:\n{output}"
+        );
+
+        let err = s
+            .repo_issue_digest(RepoIssueDigestParams {
+                repository: "owner/repo".into(),
+                number: 9,
+            })
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("issue #9 in owner/repo"),
+            "got: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn repo_ci_without_actions_reports_no_runs() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/actions/runs$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "total_count": 0,
+                "workflow_runs": []
+            })))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let params = RepoCiParams {
+            repository: "owner/repo".into(),
+            ref_: Some("main".into()),
+            per_page: 10,
+        };
+
+        let output = s.repo_ci(params).await.unwrap();
+        assert!(output.contains("No workflow runs found"), "got:\n{output}");
+    }
+
+    #[tokio::test]
+    async fn repo_ci_reports_missing_repo_as_not_found() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/actions/runs$"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let params = RepoCiParams {
+            repository: "owner/repo".into(),
+            ref_: Some("main".into()),
+            per_page: 10,
+        };
+
+        let err = s.repo_ci(params).await.unwrap_err();
+        assert!(err.to_string().contains("Not found"), "got: {err}");
+    }
+
+    fn commits_params(path: Option<&str>) -> RepoCommitsParams {
+        RepoCommitsParams {
+            repository: "owner/repo".into(),
+            ref_: None,
+            path: path.map(Into::into),
+            per_page: 20,
+        }
+    }
+
+    #[tokio::test]
+    async fn repo_commits_lists_short_sha_author_and_subject() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/owner/repo/commits$"))
+            .and(wiremock::matchers::query_param("path", "src/lib.rs"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                "sha": "0123456789abcdef0123456789abcdef01234567",
+                "html_url": "https://github.com/owner/repo/commit/0123456789abcdef0123456789abcdef01234567",
+                "commit": {
+                    "message": "Speed up parsing\n\nDetails follow.",
+                    "author": {"name": "Jo Dev", "date": "2026-01-15T10:00:00Z"}
+                },
+                "author": {"login": "jodev"}
+            }])))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let output = s
+            .repo_commits(commits_params(Some("src/lib.rs")))
+            .await
+            .unwrap();
+        assert!(
+            output.contains("- [`0123456`](https://github.com/owner/repo/commit/0123456789abcdef0123456789abcdef01234567) — **jodev** — 2026-01-15T10:00:00Z — Speed up parsing\n"),
+            "got:\n{output}"
+        );
+        assert!(!output.contains("Details follow"));
+    }
+
+    #[tokio::test]
+    async fn repo_file_history_lists_commits_for_the_path() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/owner/repo/commits$"))
+            .and(wiremock::matchers::query_param("path", "src/my file.rs"))
+            .and(wiremock::matchers::query_param("sha", "v1.0"))
+            .and(wiremock::matchers::query_param("per_page", "5"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                "sha": "89abcdef0123456789abcdef0123456789abcdef",
+                "html_url": "https://github.com/owner/repo/commit/89abcdef0123456789abcdef0123456789abcdef",
+                "commit": {
+                    "message": "Rename helper",
+                    "author": {"name": "Jo Dev", "date": "2026-02-01T09:00:00Z"}
+                },
+                "author": null
+            }])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let params = |path: &str| RepoFileHistoryParams {
+            repository: "owner/repo".into(),
+            path: path.into(),
+            ref_: Some("v1.0".into()),
+            per_page: 5,
+        };
+        let output = s.repo_file_history(params("src/my file.rs")).await.unwrap();
+        assert!(
+            output.starts_with("# owner/repo commits (ref: v1.0, path: src/my file.rs)\n"),
+            "got:\n{output}"
+        );
+        assert!(
+            output.contains("— **Jo Dev** — 2026-02-01T09:00:00Z — Rename helper\n"),
+            "got:\n{output}"
+        );
+
+        let err = s.repo_file_history(params("../etc")).await.unwrap_err();
+        assert_eq!(err.exit_code(), 1);
+    }
+
+    #[tokio::test]
+    async fn repo_branches_lists_names_and_head_shas() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/owner/repo/branches$"))
+            .and(wiremock::matchers::query_param("per_page", "30"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"name": "main", "commit": {"sha": "1111111111111111111111111111111111111111"}},
+                {"name": "release/v1", "commit": {"sha": "2222222222222222222222222222222222222222"}}
+            ])))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let output = s
+            .repo_branches(RepoBranchesParams {
+                repository: "owner/repo".into(),
+                per_page: 30,
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            output,
+            "# owner/repo branches\n\n\
+             - `main` — `1111111111111111111111111111111111111111`\n\
+             - `release/v1` — `2222222222222222222222222222222222222222`\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn repo_tags_notes_when_the_page_is_full() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/owner/repo/tags$"))
+            .and(wiremock::matchers::query_param("per_page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"name": "v2.0.0", "commit": {"sha": "3333333333333333333333333333333333333333"}},
+                {"name": "v1.9.1", "commit": {"sha": "4444444444444444444444444444444444444444"}}
+            ])))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let output = s
+            .repo_tags(RepoTagsParams {
+                repository: "owner/repo".into(),
+                per_page: 2,
+            })
+            .await
+            .unwrap();
+        assert!(
+            output.starts_with(
+                "# owner/repo tags\n\n- `v2.0.0` — `3333333333333333333333333333333333333333`\n"
+            ),
+            "got:\n{output}"
+        );
+        assert!(
+            output.contains("Showing the first 2 tags; raise --per-page"),
+            "got:\n{output}"
+        );
+    }
+
+    #[tokio::test]
+    async fn repo_commits_on_empty_repository_reports_none() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/commits$"))
+            .respond_with(
+                ResponseTemplate::new(409)
+                    .set_body_json(serde_json::json!({"message": "Git Repository is empty."})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let output = s.repo_commits(commits_params(None)).await.unwrap();
+        assert!(output.contains("No commits found."), "got:\n{output}");
+
+        let err = s
+            .repo_commits(commits_params(Some("../secret")))
+            .await
+            .unwrap_err();
+        assert_eq!(err.exit_code(), 1);
+    }
+
+    #[tokio::test]
+    async fn repo_search_code_lists_files_with_fragments() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/search/code$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "total_count": 12,
+                "incomplete_results": true,
+                "items": [{
+                    "path": "src/lib.rs",
+                    "html_url": "https://github.com/owner/repo/blob/main/src/lib.rs",
+                    "text_matches": [
+                        {"fragment": "pub fn parse_repo() {}\n"},
+                        {"fragment": "let x = parse_repo();"}
+                    ]
+                }]
+            })))
+            .mount(&server)
+            .await;
+
+        let mut s = scout_with_github(&server.uri());
+        s.github = s.github.with_token("test-token");
+        let params = RepoSearchCodeParams {
+            repository: "owner/repo".into(),
+            query: "parse_repo".into(),
+            per_page: 10,
+        };
+
+        let output = s.repo_search_code(params).await.unwrap();
+        assert!(output.starts_with("# Code search: parse_repo in owner/repo\n\n12 matching files (showing 1) — GitHub timed out"), "got:\n{output}");
+        assert!(
+            output
+                .contains("## [src/lib.rs](https://github.com/owner/repo/blob/main/src/lib.rs)\n")
+        );
+        assert!(
+            output.contains("```\npub fn parse_repo() {}\n```\n\n```\nlet x = parse_repo();\n```")
+        );
+    }
+
+    #[tokio::test]
+    async fn repo_search_code_without_token_is_user_error() {
+        let s = scout_with_github("http://localhost:0");
+        let params = RepoSearchCodeParams {
+            repository: "owner/repo".into(),
+            query: "parse_repo".into(),
+            per_page: 10,
+        };
+
+        let err = s.repo_search_code(params).await.unwrap_err();
+        assert_eq!(err.exit_code(), 1);
+        assert!(err.to_string().contains("GITHUB_TOKEN"), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn repo_search_issues_lists_number_title_state_and_url() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/search/issues$"))
+            .and(wiremock::matchers::query_param(
+                "q",
+                "panic label:bug repo:owner/repo",
+            ))
+            .and(wiremock::matchers::query_param("per_page", "5"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "total_count": 7,
+                "incomplete_results": false,
+                "items": [
+                    {"number": 12, "title": "Panic on empty input", "state": "open",
+                     "html_url": "https://github.com/owner/repo/issues/12"},
+                    {"number": 15, "title": "Fix panic in parser", "state": "closed",
+                     "html_url": "https://github.com/owner/repo/pull/15", "pull_request": {}}
+                ]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let output = s
+            .repo_search_issues(RepoSearchIssuesParams {
+                repository: "owner/repo".into(),
+                query: "panic label:bug".into(),
+                per_page: 5,
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            output,
+            "# Issue search: panic label:bug in owner/repo\n\n\
+             7 matching issues (showing 2)\n\n\
+             - [#12](https://github.com/owner/repo/issues/12) Panic on empty input (open)\n\
+             - [#15](https://github.com/owner/repo/pull/15) Fix panic in parser (PR, closed)\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn repo_search_issues_reports_no_matches() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/search/issues$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "total_count": 0,
+                "incomplete_results": false,
+                "items": []
+            })))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let output = s
+            .repo_search_issues(RepoSearchIssuesParams {
+                repository: "owner/repo".into(),
+                query: "nothing matches this".into(),
+                per_page: 10,
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            output,
+            "# Issue search: nothing matches this in owner/repo\n\nNo matching issues found.\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn repo_diff_summarizes_compare_between_refs() {
+        use wiremock::matchers::path;
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/compare/v1.0.0...feature/x%231"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "status": "diverged",
+                "ahead_by": 3,
+                "behind_by": 1,
+                "total_commits": 3,
+                "html_url": "https://github.com/owner/repo/compare/v1.0.0...feature/x%231",
+                "files": [
+                    {"filename": "src/new.rs", "status": "added", "additions": 10, "deletions": 0, "changes": 10, "patch": "@@ -0,0 +1 @@\n+fn new() {}"},
+                    {"filename": "src/lib.rs", "status": "renamed", "previous_filename": "src/old.rs", "additions": 0, "deletions": 0, "changes": 0},
+                    {"filename": "logo.png", "status": "removed", "additions": 0, "deletions": 0, "changes": 0}
+                ]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let params = RepoDiffParams {
+            repository: "owner/repo".into(),
+            base: "v1.0.0".into(),
+            head: "feature/x#1".into(),
+        };
+
+        let output = s.repo_diff(params).await.unwrap();
+        assert!(
+            output.contains("**diverged** — 3 commits ahead, 1 behind · 3 files changed (+10 −0)"),
+            "got:\n{output}"
+        );
+        assert!(output.contains("- `src/new.rs` — added (+10 −0)\n"));
+        assert!(output.contains("- `src/lib.rs` — renamed from `src/old.rs` (+0 −0)\n"));
+        assert!(output.contains("- `logo.png` — removed (+0 −0)\n"));
+        assert!(output.contains("```diff\n@@ -0,0 +1 @@\n+fn new() {}\n```"));
+
+        let bad = RepoDiffParams {
+            repository: "owner/repo".into(),
+            base: "main..evil".into(),
+            head: "main".into(),
+        };
+        assert_eq!(s.repo_diff(bad).await.unwrap_err().exit_code(), 1);
+    }
+
+    #[tokio::test]
+    async fn repo_read_round_trips_multibyte_and_special_paths() {
+        use wiremock::matchers::path;
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(
+                "/repos/owner/repo/contents/docs/%E6%97%A5%E6%9C%AC%E8%AA%9E.md",
+            ))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw("# 日本語ガイド\n本文です。\n", "text/plain; charset=utf-8"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/contents/notes/a%20b%231%5Cc.md"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw("ok\n", "text/plain"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let params = |path: &str| RepoReadParams {
+            repository: "owner/repo".into(),
+            path: path.into(),
+            paths: vec![],
+            ref_: None,
+            pr: None,
+            lines: None,
+            between: None,
+            resolve_tag: false,
+            fenced: false,
+        };
+
+        let output = s.repo_read(params("docs/日本語.md")).await.unwrap();
+        assert!(
+            output.starts_with("docs/日本語.md (2 lines)\n\n"),
+            "got:\n{output}"
+        );
+        assert!(output.contains("本文です。"), "got:\n{output}");
+
+        let output = s.repo_read(params(r"notes/a b#1\c.md")).await.unwrap();
+        assert!(
+            output.starts_with(r"notes/a b#1\c.md (1 lines)"),
+            "got:\n{output}"
+        );
+    }
+
+    #[tokio::test]
+    async fn repo_read_pr_reads_from_the_fork_head() {
+        use wiremock::matchers::query_param;
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/pulls/7"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "number": 7,
+                "title": "Fix parser",
+                "html_url": "https://github.com/owner/repo/pull/7",
+                "state": "open",
+                "mergeable": true,
+                "mergeable_state": "clean",
+                "changed_files": 1,
+                "additions": 1,
+                "deletions": 1,
+                "base": {"ref": "main", "sha": "1a2b3c", "repo": {"full_name": "owner/repo"}},
+                "head": {"ref": "fix-parser", "sha": "d4e5f6", "repo": {"full_name": "contrib/repo-fork"}}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/contrib/repo-fork/contents/src/lib.rs"))
+            .and(query_param("ref", "d4e5f6"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw("fn fixed() {}\n", "text/plain"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/contents/src/lib.rs"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let params = RepoReadParams {
+            repository: "owner/repo".into(),
+            path: "src/lib.rs".into(),
+            paths: vec![],
+            ref_: None,
+            pr: Some(7),
+            lines: None,
+            between: None,
+            resolve_tag: false,
+            fenced: false,
+        };
+
+        let output = s.repo_read(params).await.unwrap();
+        assert!(output.contains("fn fixed() {}"), "got:\n{output}");
+        assert!(
+            output.contains("pull request #7: `d4e5f6` in contrib/repo-fork"),
+            "got:\n{output}"
+        );
+    }
+
+    #[tokio::test]
+    async fn repo_read_resolves_partial_version_tag() {
+        use base64::Engine as _;
+        use wiremock::matchers::query_param;
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/contents/Cargo\.toml$"))
+            .and(query_param("ref", "v1.2"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "message": "No commit found for the ref v1.2"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/owner/repo/commits/v1\.2$"))
+            .respond_with(ResponseTemplate::new(422).set_body_json(serde_json::json!({
+                "message": "No commit found for SHA: v1.2"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/owner/repo/tags$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"name": "v1.3.0", "commit": {"sha": "0000000000000000000000000000000000000000"}},
+                {"name": "v1.2.3", "commit": {"sha": "0000000000000000000000000000000000000000"}},
+                {"name": "v1.2.0", "commit": {"sha": "0000000000000000000000000000000000000000"}},
+                {"name": "v1.12.0", "commit": {"sha": "0000000000000000000000000000000000000000"}}
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/contents/Cargo\.toml$"))
+            .and(query_param("ref", "v1.2.3"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sha": "abc",
+                "content": base64::engine::general_purpose::STANDARD
+                    .encode("[package]\nversion = \"1.2.3\"\n")
+            })))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let params = |resolve_tag| RepoReadParams {
+            repository: "owner/repo".into(),
+            path: "Cargo.toml".into(),
+            paths: vec![],
+            ref_: Some("v1.2".into()),
+            pr: None,
+            lines: None,
+            between: None,
+            resolve_tag,
+            fenced: false,
+        };
+
+        let output = s.repo_read(params(true)).await.unwrap();
+        assert!(output.contains("version = \"1.2.3\""), "got:\n{output}");
+        assert!(
+            output.contains("Ref `v1.2` not found; using tag `v1.2.3`."),
+            "got:\n{output}"
+        );
+
+        let err = s.repo_read(params(false)).await.unwrap_err();
+        assert_eq!(err.exit_code(), 1);
+    }
+
+    #[tokio::test]
+    async fn repo_read_missing_file_at_existing_ref_skips_tag_fallback() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/contents/"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/owner/repo/commits/v1\.2$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sha": "0000000000000000000000000000000000000000"
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/owner/repo/tags$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let params = |paths: Vec<String>| RepoReadParams {
+            repository: "owner/repo".into(),
+            path: "missing.rs".into(),
+            paths,
+            ref_: Some("v1.2".into()),
+            pr: None,
+            lines: None,
+            between: None,
+            resolve_tag: true,
+            fenced: false,
+        };
+
+        let err = s.repo_read(params(vec![])).await.unwrap_err();
+        assert!(err.to_string().contains("missing.rs"), "got: {err}");
+
+        let output = s
+            .repo_read(params(vec!["also-missing.rs".into()]))
+            .await
+            .unwrap();
+        assert!(!output.contains("using tag"), "got:\n{output}");
+        assert!(output.contains("Could not read"), "got:\n{output}");
+    }
+
+    #[tokio::test]
+    async fn repo_read_fenced_infers_language() {
+        use base64::Engine as _;
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/contents/src/main\.rs$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sha": "abc",
+                "content": base64::engine::general_purpose::STANDARD
+                    .encode("use std::io;\n\nfn main() {}\n")
+            })))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let params = RepoReadParams {
+            repository: "owner/repo".into(),
+            path: "src/main.rs".into(),
+            paths: vec![],
+            ref_: None,
+            pr: None,
+            lines: Some("2-3".into()),
+            between: None,
+            resolve_tag: false,
+            fenced: true,
+        };
+
+        let output = s.repo_read(params).await.unwrap();
+        assert_eq!(
+            output,
+            "src/main.rs (3 lines)\n\n```rust\n\nfn main() {}\n```"
+        );
+    }
+
+    #[tokio::test]
+    async fn repo_read_between_markers() {
+        use base64::Engine as _;
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/contents/src/lib\.rs$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sha": "abc",
+                "content": base64::engine::general_purpose::STANDARD
+                    .encode("use a;\n// BEGIN x\nfn x() {}\n// END x\nfn main() {}\n")
+            })))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let params = |start: &str, end: &str| RepoReadParams {
+            repository: "owner/repo".into(),
+            path: "src/lib.rs".into(),
+            paths: vec![],
+            ref_: None,
+            pr: None,
+            lines: None,
+            between: Some(vec![start.into(), end.into()]),
+            resolve_tag: false,
+            fenced: false,
+        };
+
+        let found = s.repo_read(params("BEGIN x", "END x")).await.unwrap();
+        assert!(found.contains("    2\t// BEGIN x\n    3\tfn x() {}\n    4\t// END x"));
+        assert!(!found.contains("fn main"));
+        assert!(!found.contains("Note:"));
+
+        let missing = s.repo_read(params("BEGIN y", "END y")).await.unwrap();
+        assert!(
+            missing.contains("Start marker \"BEGIN y\" not found"),
+            "got:\n{missing}"
+        );
+        assert!(missing.contains("fn main"));
+    }
+
+    #[tokio::test]
+    async fn repo_read_many_applies_range_and_notes_failures() {
+        use base64::Engine as _;
+        let server = MockServer::start().await;
+        for (file, body) in [("a\\.rs", "a1\na2\na3\n"), ("b\\.rs", "b1\nb2\n")] {
+            Mock::given(method("GET"))
+                .and(path_regex(format!(r"/contents/src/{file}$")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "sha": "abc",
+                    "content": base64::engine::general_purpose::STANDARD.encode(body)
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path_regex(r"/contents/src/gone\.rs$"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "message": "Not Found"
+            })))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let params = RepoReadParams {
+            repository: "owner/repo".into(),
+            path: "src/a.rs".into(),
+            paths: vec!["src/gone.rs".into(), "src/b.rs".into(), "src/a.rs".into()],
+            ref_: None,
+            pr: None,
+            lines: Some("2".into()),
+            between: None,
+            resolve_tag: false,
+            fenced: false,
+        };
+
+        let output = s.repo_read(params).await.unwrap();
+        let sections: Vec<&str> = output.split("\n\n---\n\n").collect();
+        assert_eq!(sections.len(), 3, "got:\n{output}");
+        assert!(sections[0].starts_with("src/a.rs (3 lines)\n\n"));
+        assert!(sections[0].contains("a2") && !sections[0].contains("a3"));
+        assert!(
+            sections[1].starts_with("src/gone.rs\n\n> **Note:** Could not read:"),
+            "got:\n{output}"
+        );
+        assert!(sections[2].starts_with("src/b.rs (2 lines)\n\n"));
+    }
+
+    #[tokio::test]
+    async fn repo_read_rejects_invalid_extra_path() {
+        let s = scout_with_github("http://127.0.0.1:1");
+        let params = RepoReadParams {
+            repository: "owner/repo".into(),
+            path: "src/a.rs".into(),
+            paths: vec!["../secret".into()],
+            ref_: None,
+            pr: None,
+            lines: None,
+            between: None,
+            resolve_tag: false,
+            fenced: false,
+        };
+        let err = s.repo_read(params).await.unwrap_err();
+        assert_eq!(err.exit_code(), 1);
+        assert!(err.to_string().contains("../secret"), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn search_success_returns_content() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(r":generateContent$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "candidates": [{
+                    "content": {
+                        "parts": [{"text": "Rust is a systems programming language."}],
+                        "role": "model"
+                    },
+                    "groundingMetadata": {
+                        "groundingChunks": [{
+                            "web": {
+                                "uri": "https://rust-lang.org",
+                                "title": "Rust"
+                            }
+                        }]
+                    }
+                }]
+            })))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_gemini(&server.uri());
+        let params = SearchParams {
+            query: "What is Rust?".into(),
+            lang: Lang::Auto,
+            max_answer_chars: None,
+        };
+
+        let result = s.search(params).await.unwrap();
+        assert!(!result.is_empty());
+        assert!(
+            result.contains("Rust is a systems programming language"),
+            "should contain answer text"
+        );
+        assert!(
+            !result.contains("**Query:**"),
+            "should not contain Query header (redundant for LLMs)"
+        );
+    }
+
+    #[tokio::test]
+    async fn search_caps_long_answer_and_keeps_sources() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(r":generateContent$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "candidates": [{
+                    "content": {
+                        "parts": [{"text": "Rust is fast. It is memory safe without a garbage collector."}],
+                        "role": "model"
+                    },
+                    "groundingMetadata": {
+                        "groundingChunks": [{
+                            "web": { "uri": "https://rust-lang.org", "title": "Rust" }
+                        }]
+                    }
+                }]
+            })))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_gemini(&server.uri());
+        let params = SearchParams {
+            query: "What is Rust?".into(),
+            lang: Lang::Auto,
+            max_answer_chars: Some(40),
+        };
+
+        let result = s.search(params).await.unwrap();
+        assert!(
+            result.starts_with("Rust is fast. (truncated)\n\n---\n**Sources:**\n"),
+            "got:\n{result}"
+        );
+        assert!(result.contains("- [Rust](https://rust-lang.org)"));
+    }
+
+    #[test]
+    fn cap_answer_falls_back_to_word_boundary() {
+        assert_eq!(
+            cap_answer("alpha beta gamma delta".into(), 13),
+            "alpha beta (truncated)"
+        );
+        assert_eq!(cap_answer("短い答え。".into(), 10), "短い答え。");
+        assert_eq!(
+            cap_answer("一文目。二文目は長い".into(), 7),
+            "一文目。 (truncated)"
+        );
+    }
+
+    #[tokio::test]
+    async fn search_prompt_block_surfaces_reason() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(r":generateContent$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "promptFeedback": {"blockReason": "SAFETY"}
+            })))
+            .mount(&server)
+            .await;
+
+        let s = scout_with_gemini(&server.uri());
+        let params = SearchParams {
+            query: "blocked query".into(),
+            lang: Lang::Auto,
+            max_answer_chars: None,
+        };
+
+        let result = s.search(params).await.unwrap();
+        assert!(result.contains("query blocked: SAFETY"), "got: {result}");
+    }
+
+    #[tokio::test]
+    async fn search_without_answer_errors_in_error_mode() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(r":generateContent$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "candidates": []
+            })))
+            .mount(&server)
+            .await;
+
+        let mut s = scout_with_gemini(&server.uri());
+        s.empty_answer = EmptyAnswerMode::Error;
+        let params = SearchParams {
+            query: "empty query".into(),
+            lang: Lang::Auto,
+            max_answer_chars: None,
+        };
+
+        let err = s.search(params).await.unwrap_err();
+        assert_eq!(err.exit_code(), 1);
+        assert!(err.to_string().contains("No answer returned"), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn research_success_returns_report() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(r":generateContent$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "candidates": [{
+                    "content": {
+                        "parts": [{"text": "Rust is a systems programming language focused on safety."}],
+                        "role": "model"
+                    },
+                    "groundingMetadata": {
+                        "groundingChunks": [{
+                            "web": {
+                                "uri": "https://rust-lang.org",
+                                "title": "Rust Language"
+                            }
                         }]
                     }
                 }]
@@ -410,6 +3579,7 @@ mod tests {
             query: "What is Rust?".into(),
             depth: 1,
             lang: Lang::Auto,
+            citations: None,
         };
 
         let result = s.research(params).await.unwrap();
@@ -429,6 +3599,7 @@ mod tests {
             url: "https://example.com".into(),
             markdown: "# Title\n## Section\nContent".into(),
             used_raw_fallback: false,
+            paywalled: false,
         };
         let output = format_fetch_output(&result);
         assert!(output.contains("### Title"), "h1 should shift to h3");
@@ -441,6 +3612,7 @@ mod tests {
             url: "https://example.com".into(),
             markdown: "# Raw Title\nBody".into(),
             used_raw_fallback: true,
+            paywalled: false,
         };
         let output = format_fetch_output(&result);
         assert!(
@@ -450,12 +3622,28 @@ mod tests {
         assert!(output.contains("### Raw Title"), "h1 should shift to h3");
     }
 
+    #[test]
+    fn fetch_output_leads_with_paywall_note() {
+        let result = crate::fetch::converter::FetchResult {
+            url: "https://news.example.com".into(),
+            markdown: "# Markets rally\nSubscribe to continue reading.".into(),
+            used_raw_fallback: false,
+            paywalled: true,
+        };
+        let output = format_fetch_output(&result);
+        assert!(
+            output.starts_with(crate::fetch::converter::PAYWALL_NOTE.trim_end()),
+            "got: {output}"
+        );
+    }
+
     #[test]
     fn fetch_output_truncates_long_content() {
         let result = crate::fetch::converter::FetchResult {
             url: "https://example.com".into(),
             markdown: format!("# Title\n{}", "x".repeat(150_000)),
             used_raw_fallback: false,
+            paywalled: false,
         };
         let output = format_fetch_output(&result);
         assert!(
@@ -469,4 +3657,373 @@ mod tests {
         );
         assert!(output.contains("### Title"), "headings should still be shifted");
     }
+
+    #[test]
+    fn fetch_chunk_reports_position_and_next() {
+        let result = crate::fetch::converter::FetchResult {
+            url: "https://example.com".into(),
+            markdown: format!("# Title\n\n{}\n\n{}", "a".repeat(600), "b".repeat(300)),
+            used_raw_fallback: false,
+            paywalled: false,
+        };
+
+        let first = format_fetch_chunk(&result, 1, 700).unwrap();
+        assert!(
+            first.starts_with("> Chunk 1 of 2 (next: --chunk 2)\n\n### Title"),
+            "got: {first}"
+        );
+        assert!(!first.contains('b'));
+
+        let last = format_fetch_chunk(&result, 2, 700).unwrap();
+        assert_eq!(last, format!("> Chunk 2 of 2\n\n{}\n", "b".repeat(300)));
+    }
+
+    #[test]
+    fn fetch_chunk_out_of_range_is_user_error() {
+        let result = crate::fetch::converter::FetchResult {
+            url: "https://example.com".into(),
+            markdown: "short".into(),
+            used_raw_fallback: false,
+            paywalled: false,
+        };
+        let err = format_fetch_chunk(&result, 3, 500).unwrap_err();
+        assert_eq!(err.exit_code(), 1);
+        assert!(err.to_string().contains("has 1 chunk(s)"), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn fetch_many_isolates_failures_and_keeps_input_order() {
+        use wiremock::matchers::path;
+
+        let server = MockServer::start().await;
+        let filler = "Enough prose to look like a real article body. ".repeat(4);
+        for name in ["one", "two"] {
+            let paragraphs = (0..4)
+                .map(|i| format!("<p>Page {name} paragraph {i}. {filler}</p>"))
+                .collect::<String>();
+            Mock::given(method("GET"))
+                .and(path(format!("/{name}")))
+                .respond_with(ResponseTemplate::new(200).set_body_raw(
+                    format!(
+                        "<html><head><title>{name}</title></head>\
+                         <body><article><h1>{name}</h1>{paragraphs}</article></body></html>"
+                    ),
+                    "text/html",
+                ))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/slow"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(10)))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let http = Client::builder()
+            .resolve("pages.test", *server.address())
+            .build()
+            .unwrap();
+        let base = format!("http://pages.test:{}", server.address().port());
+        let urls = vec![
+            format!("{base}/slow"),
+            format!("{base}/one"),
+            format!("{base}/missing"),
+            "http://127.0.0.1/admin".to_string(),
+            "ftp://example.com/file".to_string(),
+            format!("{base}/two"),
+        ];
+
+        let started = std::time::Instant::now();
+        let outcomes = fetch_urls(
+            &http,
+            urls,
+            crate::fetch::FetchOptions::default(),
+            Duration::from_secs(1),
+            &RequestBudget::default(),
+            &PublicDns,
+        )
+        .await;
+        assert!(started.elapsed() < Duration::from_secs(5));
+        let output = format_fetch_many(&outcomes);
+
+        assert!(
+            output.starts_with("# Fetched 2 of 6 URLs\n\n"),
+            "got:\n{output}"
+        );
+        let one = output.find("Page one paragraph").expect("page one");
+        let two = output.find("Page two paragraph").expect("page two");
+        assert!(one < two);
+        assert!(
+            output.contains(&format!("## {base}/one\n")),
+            "got:\n{output}"
+        );
+        let failed = &output[output.find("## Failed URLs").expect("failed section")..];
+        assert!(failed.contains("/slow (fetch timed out"), "got:\n{failed}");
+        assert!(
+            failed.contains("/missing (fetch failed: status 404)"),
+            "got:\n{failed}"
+        );
+        assert!(
+            failed.contains("127.0.0.1/admin (blocked:"),
+            "got:\n{failed}"
+        );
+        assert!(
+            failed.contains("ftp://example.com/file (invalid URL"),
+            "got:\n{failed}"
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_many_rejects_too_many_urls() {
+        let s = scout_with_github("http://localhost:0");
+        let urls = (0..=MAX_FETCH_MANY_URLS)
+            .map(|i| format!("https://example.com/{i}"))
+            .collect();
+        let err = s
+            .fetch_many(FetchManyParams {
+                urls,
+                raw: None,
+                metadata_only: None,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.exit_code(), 1);
+        assert!(err.to_string().contains("too many URLs"), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn translated_body_replaces_original() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(r":generateContent$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "candidates": [{"content": {
+                    "parts": [{"text": "SOURCE_LANGUAGE: German\n# Hello\n\nWorld"}],
+                    "role": "model"
+                }}]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let s = scout_with_gemini(&server.uri());
+        let result = crate::fetch::converter::FetchResult {
+            url: "https://example.de".into(),
+            markdown: "---\ntitle: \"Hallo\"\n---\n\n# Hallo\n\nWelt\n".into(),
+            used_raw_fallback: false,
+            paywalled: false,
+        };
+
+        let translated = translate_fetch_result(
+            s.gemini().unwrap(),
+            result,
+            "English",
+            None,
+            &RequestBudget::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            translated.markdown,
+            "---\ntitle: \"Hallo\"\n---\n\n\
+             > **Note:** Machine-translated from German into English by Gemini.\n\n\
+             # Hello\n\nWorld\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn translation_skips_body_past_output_budget() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(r":generateContent$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "candidates": [{"content": {
+                    "parts": [{"text": "SOURCE_LANGUAGE: German\nKept"}],
+                    "role": "model"
+                }}]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let s = scout_with_gemini(&server.uri());
+        let body = format!("{}\n\n{}", "a".repeat(50), "z".repeat(5_000));
+        let result = crate::fetch::converter::FetchResult {
+            url: "https://example.de".into(),
+            markdown: body,
+            used_raw_fallback: false,
+            paywalled: false,
+        };
+
+        let translated = translate_fetch_result(
+            s.gemini().unwrap(),
+            result,
+            "English",
+            Some(60),
+            &RequestBudget::default(),
+        )
+        .await
+        .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let sent = String::from_utf8_lossy(&requests[0].body);
+        assert!(!sent.contains("zzzzzzzzzz"), "sent: {sent}");
+        assert!(
+            translated
+                .markdown
+                .ends_with("Kept\n\n(truncated: translated 60 / 5052 bytes)\n"),
+            "got:\n{}",
+            translated.markdown
+        );
+    }
+
+    #[tokio::test]
+    async fn translate_without_gemini_fails_before_fetching() {
+        let s = scout_with_github("http://localhost:0");
+        let params = |lang| FetchParams {
+            url: "https://example.com".into(),
+            js: false,
+            raw: None,
+            charset: None,
+            metadata_only: None,
+            follow_pagination: None,
+            code_only: false,
+            links: false,
+            flatten_links: false,
+            image: false,
+            compare: false,
+            selector: None,
+            max_bytes: None,
+            respect_robots: false,
+            chunk: None,
+            chunk_size: None,
+            translate_to: Some(lang),
+            if_modified_since: None,
+        };
+
+        let err = s.fetch(params(Lang::En)).await.unwrap_err();
+        assert!(err.to_string().contains("GEMINI_API_KEY"), "got: {err}");
+        let err = s.fetch(params(Lang::Auto)).await.unwrap_err();
+        assert!(err.to_string().contains("concrete language"), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn repo_grep_files_previews_matching_files() {
+        use base64::Engine as _;
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/git/trees/main$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "tree": [
+                    {"path": "src/app.ts", "type": "blob", "size": 10},
+                    {"path": "src/app.test.ts", "type": "blob", "size": 60},
+                    {"path": "lib/util.test.ts", "type": "blob", "size": 30}
+                ],
+                "truncated": false
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/contents/src/app\.test\.ts$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sha": "a",
+                "content": base64::engine::general_purpose::STANDARD
+                    .encode("import { app } from './app';\ntest('a', () => {});\nline3\n")
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/contents/lib/util\.test\.ts$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sha": "b",
+                "content": base64::engine::general_purpose::STANDARD.encode("test('util');\n")
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/contents/src/app\.ts$"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let params = RepoGrepFilesParams {
+            repository: "owner/repo".into(),
+            pattern: "*.test.ts".into(),
+            ref_: Some("main".into()),
+            path: None,
+            lines: 2,
+        };
+
+        let output = s.repo_grep_files(params).await.unwrap();
+        assert!(output.contains("files: 2"), "got:\n{output}");
+        assert!(
+            output.contains(
+                "## src/app.test.ts (3 lines)\n\n```typescript\nimport { app } from './app';\ntest('a', () => {});\n```"
+            ),
+            "got:\n{output}"
+        );
+        assert!(!output.contains("line3"), "got:\n{output}");
+        assert!(
+            output.contains("## lib/util.test.ts (1 line)"),
+            "got:\n{output}"
+        );
+        assert!(!output.contains("Not previewed"), "got:\n{output}");
+    }
+
+    #[tokio::test]
+    async fn repo_grep_files_lists_large_files_without_reading_them() {
+        use base64::Engine as _;
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/git/trees/main$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "tree": [
+                    {"path": "fixtures/small.json", "type": "blob", "size": 20},
+                    {"path": "fixtures/huge.json", "type": "blob", "size": 40_000_000}
+                ],
+                "truncated": false
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/contents/fixtures/small\.json$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sha": "a",
+                "content": base64::engine::general_purpose::STANDARD.encode("{\"ok\": true}\n")
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/contents/fixtures/huge\.json$"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let s = scout_with_github(&server.uri());
+        let params = RepoGrepFilesParams {
+            repository: "owner/repo".into(),
+            pattern: "*.json".into(),
+            ref_: Some("main".into()),
+            path: None,
+            lines: 5,
+        };
+
+        let output = s.repo_grep_files(params).await.unwrap();
+        assert!(output.contains("files: 2"), "got:\n{output}");
+        assert!(
+            output.contains("## fixtures/small.json (1 line)"),
+            "got:\n{output}"
+        );
+        assert!(
+            output.contains("## Not previewed (1)\n\n- fixtures/huge.json\n"),
+            "got:\n{output}"
+        );
+    }
 }
@@ -1,6 +1,7 @@
 use clap::{Args, Subcommand};
 
-pub use crate::search::Lang;
+pub use crate::github::format::Verbosity;
+pub use crate::search::{CitationStyle, Lang};
 
 #[derive(Subcommand)]
 pub enum Command {
@@ -8,14 +9,101 @@ pub enum Command {
     Search(SearchParams),
     /// Fetch a web page and convert it to clean Markdown
     Fetch(FetchParams),
+    /// Fetch several web pages concurrently into one Markdown document
+    FetchMany(FetchManyParams),
+    /// Summarize the endpoints of an OpenAPI/Swagger spec (JSON or YAML) by tag
+    OpenapiSummary(OpenapiSummaryParams),
     /// Deep research: search the web, fetch top results, and compile a report
     Research(ResearchParams),
+    /// Search the web and read the top source in full (a one-page research)
+    SearchRead(SearchReadParams),
     /// List files in a remote GitHub repository
     RepoTree(RepoTreeParams),
+    /// List the immediate entries of one directory in a remote GitHub repository
+    RepoLs(RepoLsParams),
     /// Read a file from a remote GitHub repository
     RepoRead(RepoReadParams),
+    /// Preview the first lines of every file matching a glob in a remote GitHub repository
+    RepoGrepFiles(RepoGrepFilesParams),
     /// Get a comprehensive overview of a remote GitHub repository
     RepoOverview(RepoOverviewParams),
+    /// Show the latest GitHub Actions workflow runs for a branch
+    RepoCi(RepoCiParams),
+    /// List recent commits in a remote GitHub repository
+    RepoCommits(RepoCommitsParams),
+    /// List the commits that touched one file in a remote GitHub repository
+    RepoFileHistory(RepoFileHistoryParams),
+    /// List the branches of a remote GitHub repository with their head commits
+    RepoBranches(RepoBranchesParams),
+    /// List the tags of a remote GitHub repository with the commits they point at
+    RepoTags(RepoTagsParams),
+    /// Search code in a remote GitHub repository (requires GITHUB_TOKEN)
+    RepoSearchCode(RepoSearchCodeParams),
+    /// Search issues and pull requests in a remote GitHub repository
+    RepoSearchIssues(RepoSearchIssuesParams),
+    /// Summarize the changes between two refs of a remote GitHub repository
+    RepoDiff(RepoDiffParams),
+    /// List the files a GitHub pull request changes, with its merge status
+    RepoPrFiles(RepoPrFilesParams),
+    /// Read a GitHub issue or pull request thread: the opening post and its comments
+    RepoIssue(RepoIssueParams),
+    /// Digest a GitHub issue thread into its problem, proposals, decisions, and open questions
+    RepoIssueDigest(RepoIssueDigestParams),
+}
+
+impl Command {
+    /// Subcommand names, in declaration order.
+    pub const NAMES: &[&str] = &[
+        "search",
+        "fetch",
+        "fetch-many",
+        "openapi-summary",
+        "research",
+        "search-read",
+        "repo-tree",
+        "repo-ls",
+        "repo-read",
+        "repo-grep-files",
+        "repo-overview",
+        "repo-ci",
+        "repo-commits",
+        "repo-file-history",
+        "repo-branches",
+        "repo-tags",
+        "repo-search-code",
+        "repo-search-issues",
+        "repo-diff",
+        "repo-pr-files",
+        "repo-issue",
+        "repo-issue-digest",
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Search(_) => "search",
+            Self::Fetch(_) => "fetch",
+            Self::FetchMany(_) => "fetch-many",
+            Self::OpenapiSummary(_) => "openapi-summary",
+            Self::Research(_) => "research",
+            Self::SearchRead(_) => "search-read",
+            Self::RepoTree(_) => "repo-tree",
+            Self::RepoLs(_) => "repo-ls",
+            Self::RepoRead(_) => "repo-read",
+            Self::RepoGrepFiles(_) => "repo-grep-files",
+            Self::RepoOverview(_) => "repo-overview",
+            Self::RepoCi(_) => "repo-ci",
+            Self::RepoCommits(_) => "repo-commits",
+            Self::RepoFileHistory(_) => "repo-file-history",
+            Self::RepoBranches(_) => "repo-branches",
+            Self::RepoTags(_) => "repo-tags",
+            Self::RepoSearchCode(_) => "repo-search-code",
+            Self::RepoSearchIssues(_) => "repo-search-issues",
+            Self::RepoDiff(_) => "repo-diff",
+            Self::RepoPrFiles(_) => "repo-pr-files",
+            Self::RepoIssue(_) => "repo-issue",
+            Self::RepoIssueDigest(_) => "repo-issue-digest",
+        }
+    }
 }
 
 #[derive(Args)]
@@ -25,6 +113,9 @@ pub struct SearchParams {
     /// Search language
     #[arg(short, long, value_enum, default_value_t = Lang::Auto)]
     pub lang: Lang,
+    /// Cut the answer to about N characters at a sentence or word boundary; sources are still listed [default: no limit]
+    #[arg(long, value_name = "N")]
+    pub max_answer_chars: Option<usize>,
 }
 
 #[derive(Args)]
@@ -34,9 +125,73 @@ pub struct FetchParams {
     /// Force JavaScript rendering via playwright-cli (auto-detected for SPA pages and thin extractions)
     #[arg(long)]
     pub js: bool,
-    /// Skip Readability extraction and convert entire page
+    /// Skip Readability extraction and convert entire page [default: $SCOUT_FETCH_DEFAULT_RAW or false]
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    pub raw: Option<bool>,
+    /// Decode the page with this charset (e.g. "shift_jis"), overriding what the server declares
+    #[arg(long)]
+    pub charset: Option<String>,
+    /// Return only metadata frontmatter (title, author, date, description, preview image, canonical URL, status, content type, last-modified, download time and size) [default: $SCOUT_FETCH_DEFAULT_META or false]
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    pub metadata_only: Option<bool>,
+    /// Follow up to N "next page" links on the same site and append their content (1-10)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u8).range(1..=crate::fetch::MAX_PAGINATION_PAGES as i64))]
+    pub follow_pagination: Option<u8>,
+    /// Return only the page's code blocks (with language hints), dropping the prose
+    #[arg(long, conflicts_with = "metadata_only")]
+    pub code_only: bool,
+    /// Return only the page's links as a Markdown list of [text](absolute URL)
+    #[arg(long, conflicts_with_all = ["metadata_only", "code_only"])]
+    pub links: bool,
+    /// Replace links with their anchor text, keeping the prose; with --links, list them after the body
+    #[arg(long, conflicts_with_all = ["metadata_only", "code_only"])]
+    pub flatten_links: bool,
+    /// Add the page's preview image URL (og:image, then twitter:image) to the frontmatter as `image`
     #[arg(long)]
-    pub raw: bool,
+    pub image: bool,
+    /// Show the extracted Markdown and the whole-page conversion in two labelled sections, to see what extraction dropped
+    #[arg(long, conflicts_with_all = ["metadata_only", "code_only", "links", "flatten_links", "raw", "selector", "follow_pagination"])]
+    pub compare: bool,
+    /// Convert only the elements matching this CSS selector (e.g. "#main-content", "article.post"), skipping Readability
+    #[arg(long, value_name = "CSS", conflicts_with = "metadata_only")]
+    pub selector: Option<String>,
+    /// Maximum download size in bytes (default 10 MB, at most 50 MB)
+    #[arg(long, value_name = "BYTES", value_parser = clap::value_parser!(u64).range(1..=crate::fetch::MAX_RESPONSE_BYTES_CEILING as u64))]
+    pub max_bytes: Option<u64>,
+    /// Refuse to fetch pages the site's robots.txt disallows for scout
+    #[arg(long)]
+    pub respect_robots: bool,
+    /// Return only chunk N (1-based) of the Markdown, split at paragraph/heading boundaries
+    #[arg(long, value_name = "N", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    pub chunk: Option<usize>,
+    /// Characters per chunk for --chunk (500-100000, default 20000)
+    #[arg(long, value_name = "CHARS", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(500..=100_000))]
+    pub chunk_size: Option<usize>,
+    /// Translate the extracted page into this language with Gemini (requires GEMINI_API_KEY)
+    #[arg(long, value_enum, value_name = "LANG")]
+    pub translate_to: Option<Lang>,
+    /// Only return content if the page changed after this date (RFC 3339 or HTTP date)
+    #[arg(long, value_name = "DATE")]
+    pub if_modified_since: Option<String>,
+}
+
+#[derive(Args)]
+pub struct FetchManyParams {
+    /// URLs to fetch (HTTP or HTTPS, at most 20)
+    #[arg(required = true)]
+    pub urls: Vec<String>,
+    /// Skip Readability extraction and convert entire pages [default: $SCOUT_FETCH_DEFAULT_RAW or false]
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    pub raw: Option<bool>,
+    /// Return only each page's metadata frontmatter [default: $SCOUT_FETCH_DEFAULT_META or false]
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    pub metadata_only: Option<bool>,
+}
+
+#[derive(Args)]
+pub struct OpenapiSummaryParams {
+    /// URL of the spec (HTTP or HTTPS)
+    pub url: String,
 }
 
 #[derive(Args)]
@@ -49,6 +204,18 @@ pub struct ResearchParams {
     /// Search language
     #[arg(short, long, value_enum, default_value_t = Lang::Auto)]
     pub lang: Lang,
+    /// How to cite sources: a list after the report, or inline `[^n]` footnotes [default: list]
+    #[arg(long, value_enum)]
+    pub citations: Option<CitationStyle>,
+}
+
+#[derive(Args)]
+pub struct SearchReadParams {
+    /// Search query
+    pub query: String,
+    /// Search language
+    #[arg(short, long, value_enum, default_value_t = Lang::Auto)]
+    pub lang: Lang,
 }
 
 #[derive(Args)]
@@ -61,9 +228,32 @@ pub struct RepoTreeParams {
     /// Filter to files under this path prefix (e.g., "src/components/")
     #[arg(short, long)]
     pub path: Option<String>,
-    /// Glob pattern to filter filenames (e.g., "*.rs", "*.{ts,tsx}")
+    /// Glob pattern to filter filenames (e.g., "*.rs", "*.{ts,tsx}"); a pattern with "/" matches the full path (e.g., "src/**/*.rs")
     #[arg(long)]
     pub pattern: Option<String>,
+    /// Inline the contents of files smaller than this many bytes (total budget applies)
+    #[arg(long, value_name = "BYTES")]
+    pub inline_under_bytes: Option<u64>,
+    /// Show the files as an indented tree diagram instead of a flat path list
+    #[arg(long, conflicts_with = "inline_under_bytes")]
+    pub tree_view: bool,
+    /// Detail level: compact, normal, or detailed [default: normal]
+    #[arg(long, value_enum)]
+    pub verbosity: Option<Verbosity>,
+}
+
+#[derive(Args)]
+pub struct RepoLsParams {
+    /// GitHub repository in "owner/repo" format (e.g., "facebook/react")
+    pub repository: String,
+    /// Directory path within the repository (defaults to the root)
+    pub path: Option<String>,
+    /// Git ref: branch name, tag, or commit SHA (defaults to the default branch)
+    #[arg(long, name = "ref")]
+    pub ref_: Option<String>,
+    /// Detail level: compact, normal, or detailed [default: normal]
+    #[arg(long, value_enum)]
+    pub verbosity: Option<Verbosity>,
 }
 
 #[derive(Args)]
@@ -72,16 +262,187 @@ pub struct RepoReadParams {
     pub repository: String,
     /// File path within the repository (e.g., "src/index.ts")
     pub path: String,
+    /// More files to read with the same options, concurrently (at most 10 files in total)
+    #[arg(value_name = "MORE_PATHS")]
+    pub paths: Vec<String>,
     /// Git ref: branch name, tag, or commit SHA
     #[arg(long, name = "ref")]
     pub ref_: Option<String>,
+    /// Read at this pull request's head commit, from the fork it was opened from if any
+    #[arg(long, value_name = "NUMBER", value_parser = clap::value_parser!(u64).range(1..), conflicts_with = "ref")]
+    pub pr: Option<u64>,
     /// Line range: "1-80", "50-", or "100" (first N lines)
     #[arg(short, long)]
     pub lines: Option<String>,
+    /// Read from the first line containing START to the next line containing END
+    #[arg(long, num_args = 2, value_names = ["START", "END"], conflicts_with = "lines")]
+    pub between: Option<Vec<String>>,
+    /// If --ref is not found and looks like a partial version (e.g. "v1.2"), use the best matching tag
+    #[arg(long, requires = "ref")]
+    pub resolve_tag: bool,
+    /// Wrap the content in a code fence tagged with the file's language, without line numbers
+    #[arg(long)]
+    pub fenced: bool,
+}
+
+#[derive(Args)]
+pub struct RepoGrepFilesParams {
+    /// GitHub repository in "owner/repo" format (e.g., "facebook/react")
+    pub repository: String,
+    /// Glob pattern for filenames (e.g., "*.test.ts"), or for full paths when it contains "/" (e.g., "src/**/*.rs")
+    pub pattern: String,
+    /// Git ref: branch name, tag, or commit SHA (defaults to the default branch)
+    #[arg(long, name = "ref")]
+    pub ref_: Option<String>,
+    /// Only consider files under this path prefix (e.g., "src/")
+    #[arg(short, long)]
+    pub path: Option<String>,
+    /// Lines to preview from each file (1-200)
+    #[arg(short = 'n', long, default_value_t = 20, value_parser = clap::value_parser!(u8).range(1..=200))]
+    pub lines: u8,
 }
 
 #[derive(Args)]
 pub struct RepoOverviewParams {
     /// GitHub repository in "owner/repo" format (e.g., "facebook/react")
     pub repository: String,
+    /// Topics/labels shown per line before collapsing the rest into "+N more" (1-100)
+    #[arg(long, value_name = "N", default_value_t = crate::github::format::DEFAULT_MAX_LABELS, value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub max_labels: u8,
+    /// Sections to include, comma-separated (default: all)
+    #[arg(long, value_delimiter = ',', value_parser = crate::github::format::OVERVIEW_SECTIONS)]
+    pub sections: Option<Vec<String>>,
+    /// Normalize the README through GitHub's Markdown renderer (one extra API call)
+    #[arg(long)]
+    pub render_readme: bool,
+    /// Detail level: compact, normal, or detailed [default: normal]
+    #[arg(long, value_enum)]
+    pub verbosity: Option<Verbosity>,
+}
+
+#[derive(Args)]
+pub struct RepoCiParams {
+    /// GitHub repository in "owner/repo" format (e.g., "facebook/react")
+    pub repository: String,
+    /// Branch to inspect (defaults to the repository's default branch)
+    #[arg(long, name = "ref")]
+    pub ref_: Option<String>,
+    /// Number of runs to show (1-50)
+    #[arg(short = 'n', long, default_value_t = 10, value_parser = clap::value_parser!(u8).range(1..=50))]
+    pub per_page: u8,
+}
+
+#[derive(Args)]
+pub struct RepoCommitsParams {
+    /// GitHub repository in "owner/repo" format (e.g., "facebook/react")
+    pub repository: String,
+    /// Branch, tag, or commit SHA to list from (defaults to the default branch)
+    #[arg(long, name = "ref")]
+    pub ref_: Option<String>,
+    /// Only commits touching this file or directory (e.g., "src/lib.rs")
+    #[arg(short, long)]
+    pub path: Option<String>,
+    /// Number of commits to show (1-100)
+    #[arg(short = 'n', long, default_value_t = 20, value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub per_page: u8,
+}
+
+#[derive(Args)]
+pub struct RepoFileHistoryParams {
+    /// GitHub repository in "owner/repo" format (e.g., "facebook/react")
+    pub repository: String,
+    /// File or directory path within the repository (e.g., "src/lib.rs")
+    pub path: String,
+    /// Branch, tag, or commit SHA to list from (defaults to the default branch)
+    #[arg(long, name = "ref")]
+    pub ref_: Option<String>,
+    /// Number of commits to show (1-100)
+    #[arg(short = 'n', long, default_value_t = 20, value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub per_page: u8,
+}
+
+#[derive(Args)]
+pub struct RepoBranchesParams {
+    /// GitHub repository in "owner/repo" format (e.g., "facebook/react")
+    pub repository: String,
+    /// Number of branches to show (1-100)
+    #[arg(short = 'n', long, default_value_t = 30, value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub per_page: u8,
+}
+
+#[derive(Args)]
+pub struct RepoTagsParams {
+    /// GitHub repository in "owner/repo" format (e.g., "facebook/react")
+    pub repository: String,
+    /// Number of tags to show (1-100)
+    #[arg(short = 'n', long, default_value_t = 30, value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub per_page: u8,
+}
+
+#[derive(Args)]
+pub struct RepoSearchCodeParams {
+    /// GitHub repository in "owner/repo" format (e.g., "facebook/react")
+    pub repository: String,
+    /// Code search query, e.g. a symbol name, optionally with qualifiers like "language:rust"
+    pub query: String,
+    /// Number of matching files to show (1-100)
+    #[arg(short = 'n', long, default_value_t = 10, value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub per_page: u8,
+}
+
+#[derive(Args)]
+pub struct RepoSearchIssuesParams {
+    /// GitHub repository in "owner/repo" format (e.g., "facebook/react")
+    pub repository: String,
+    /// Issue search query: keywords, optionally with qualifiers like "is:open label:bug"
+    pub query: String,
+    /// Number of matching issues to show (1-100)
+    #[arg(short = 'n', long, default_value_t = 10, value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub per_page: u8,
+}
+
+#[derive(Args)]
+pub struct RepoDiffParams {
+    /// GitHub repository in "owner/repo" format (e.g., "facebook/react")
+    pub repository: String,
+    /// Base ref: branch, tag, or commit SHA (e.g., "v1.0.0")
+    pub base: String,
+    /// Head ref compared against the base (e.g., "main")
+    pub head: String,
+}
+
+#[derive(Args)]
+pub struct RepoPrFilesParams {
+    /// GitHub repository in "owner/repo" format (e.g., "facebook/react")
+    pub repository: String,
+    /// Pull request number
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    pub number: u64,
+    /// Number of changed files to show (1-100)
+    #[arg(short = 'n', long, default_value_t = 30, value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub per_page: u8,
+    /// Include each file's unified diff
+    #[arg(long)]
+    pub patches: bool,
+}
+
+#[derive(Args)]
+pub struct RepoIssueParams {
+    /// GitHub repository in "owner/repo" format (e.g., "facebook/react")
+    pub repository: String,
+    /// Issue or pull request number
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    pub number: u64,
+    /// Number of comments to show, oldest first (1-100)
+    #[arg(short = 'n', long, default_value_t = 30, value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub max_comments: u8,
+}
+
+#[derive(Args)]
+pub struct RepoIssueDigestParams {
+    /// GitHub repository in "owner/repo" format (e.g., "facebook/react")
+    pub repository: String,
+    /// Issue or pull request number
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    pub number: u64,
 }